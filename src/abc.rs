@@ -0,0 +1,6 @@
+mod abc_tune;
+pub use abc_tune::AbcTune;
+
+mod parser;
+
+mod writer;
@@ -0,0 +1,87 @@
+use std::fmt::Display;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::abc::{parser, writer};
+use crate::scores::Melody;
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct AbcTune {
+    reference_number: u32,
+    title: Option<String>,
+    composer: Option<String>,
+    melody: Melody,
+}
+
+impl Display for AbcTune {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", writer::write_tune(self))
+    }
+}
+
+impl AbcTune {
+    pub fn new(reference_number: u32, title: Option<String>, composer: Option<String>, melody: Melody) -> AbcTune {
+        AbcTune {
+            reference_number,
+            title,
+            composer,
+            melody,
+        }
+    }
+
+    /// Reads a single tune. Repeats and first and second endings are unfolded, so the melody holds every note in the order it is played
+    pub fn try_parse(text: &str) -> Result<AbcTune, Box<dyn CrispiiError>> {
+        parser::try_parse_tune(text)
+    }
+
+    /// Reads every tune in a file, each of which starts with an X: field
+    pub fn try_parse_all(text: &str) -> Result<Vec<AbcTune>, Box<dyn CrispiiError>> {
+        let mut tunes = Vec::new();
+        let mut current: Option<String> = None;
+
+        for line in text.lines() {
+            if line.trim_start().starts_with("X:") {
+                if let Some(tune) = current.take() {
+                    tunes.push(parser::try_parse_tune(&tune)?);
+                }
+
+                current = Some(String::new());
+            }
+
+            if let Some(tune) = current.as_mut() {
+                tune.push_str(line);
+                tune.push('\n');
+            }
+        }
+
+        if let Some(tune) = current {
+            tunes.push(parser::try_parse_tune(&tune)?);
+        }
+
+        if tunes.is_empty() {
+            return Err(Box::new(InvalidArgumentError::new("text", "No tunes starting with an X: field were found")));
+        }
+
+        Ok(tunes)
+    }
+
+    pub fn get_reference_number(&self) -> u32 {
+        self.reference_number
+    }
+
+    pub fn get_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn get_composer(&self) -> Option<&str> {
+        self.composer.as_deref()
+    }
+
+    pub fn get_melody(&self) -> &Melody {
+        &self.melody
+    }
+
+    pub fn into_melody(self) -> Melody {
+        self.melody
+    }
+}
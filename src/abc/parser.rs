@@ -0,0 +1,607 @@
+use std::collections::HashMap;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::abc::AbcTune;
+use crate::chords::ChordSymbol;
+use crate::keys::{Key, Mode};
+use crate::notes::{LetterNote, Modifier, Octave};
+use crate::rhythm::{Duration, NoteValue, Tempo, TimeSignature};
+use crate::scores::{Event, EventKind, Melody};
+
+/// A multiple of the unit note length, as a numerator and denominator
+type Length = (u32, u32);
+
+pub(crate) fn try_parse_tune(text: &str) -> Result<AbcTune, Box<dyn CrispiiError>> {
+    let mut reference_number = 1;
+    let mut title = None;
+    let mut composer = None;
+    let mut time_signature = TimeSignature::default();
+    let mut unit = None;
+    let mut tempo = None;
+    let mut body: Option<BodyParser> = None;
+
+    for line in text.lines() {
+        let line = strip_comment(line);
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if let Some((field, value)) = get_field(trimmed) {
+            let value = value.trim();
+
+            match (&mut body, field) {
+                (None, 'X') => reference_number = value.parse().unwrap_or(1),
+                (None, 'T') if title.is_none() => title = Some(value.to_string()),
+                (None, 'C') if composer.is_none() => composer = Some(value.to_string()),
+                (None, 'M') => time_signature = try_parse_meter(value)?,
+                (None, 'L') => unit = Some(try_parse_unit(value)?),
+                (None, 'Q') => tempo = Some(value.to_string()),
+                (None, 'K') => {
+                    let unit = unit.unwrap_or_else(|| get_default_unit(time_signature));
+                    let tempo = match &tempo {
+                        Some(tempo) => try_parse_tempo(tempo, unit)?,
+                        None => Tempo::default(),
+                    };
+
+                    body = Some(BodyParser::new(try_parse_key(value)?, time_signature, unit, tempo));
+                }
+                (Some(parser), 'K') => parser.key = try_parse_key(value)?,
+                (Some(parser), 'M') => parser.time_signature = try_parse_meter(value)?,
+                (Some(parser), 'L') => parser.unit = try_parse_unit(value)?,
+                (Some(parser), 'w') => parser.add_lyrics(value),
+                _ => (),
+            }
+
+            continue;
+        }
+
+        match body.as_mut() {
+            Some(parser) => parser.try_parse_music_line(line)?,
+            None => return Err(Box::new(InvalidArgumentError::new("text", format!("Found music before the K: field in '{trimmed}'").as_str()))),
+        }
+    }
+
+    let parser = body.ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", "ABC tunes need a K: field to end their header")) })?;
+
+    Ok(AbcTune::new(reference_number, title, composer, parser.into_melody()))
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut previous = ' ';
+
+    for (index, character) in line.char_indices() {
+        if character == '%' && previous != '\\' {
+            return &line[..index];
+        }
+
+        previous = character;
+    }
+
+    line
+}
+
+fn get_field(line: &str) -> Option<(char, &str)> {
+    let mut characters = line.chars();
+    let field = characters.next()?;
+
+    if field.is_ascii_alphabetic() && characters.next() == Some(':') && !(line.starts_with("|:")) {
+        Some((field, &line[2..]))
+    } else {
+        None
+    }
+}
+
+fn try_parse_fraction(text: &str, argument: &str) -> Result<Duration, Box<dyn CrispiiError>> {
+    let (numerator, denominator) = text.trim().split_once('/')
+        .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new(argument, format!("'{text}' is not a fraction").as_str())) })?;
+    let numerator = numerator.trim().parse::<u32>().map_err(|_| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new(argument, format!("'{text}' is not a fraction").as_str())) })?;
+    let denominator = denominator.trim().parse::<u32>().map_err(|_| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new(argument, format!("'{text}' is not a fraction").as_str())) })?;
+
+    Duration::try_new(numerator, denominator)
+}
+
+fn try_parse_meter(text: &str) -> Result<TimeSignature, Box<dyn CrispiiError>> {
+    match text.trim() {
+        "C" => Ok(TimeSignature::default()),
+        "C|" => TimeSignature::try_new(2, 2),
+        "none" | "" => Ok(TimeSignature::default()),
+        meter => {
+            let fraction = meter.split_whitespace().next().unwrap_or(meter);
+            let (numerator, denominator) = fraction.split_once('/')
+                .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("M", format!("'{meter}' is not a meter").as_str())) })?;
+            let numerator = numerator.split('+').try_fold(0u8, |total, part| part.trim().parse::<u8>().ok().and_then(|part| total.checked_add(part)))
+                .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("M", format!("'{meter}' is not a meter").as_str())) })?;
+            let denominator = denominator.trim().parse::<u8>()
+                .map_err(|_| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("M", format!("'{meter}' is not a meter").as_str())) })?;
+
+            TimeSignature::try_new(numerator, denominator)
+        }
+    }
+}
+
+fn try_parse_unit(text: &str) -> Result<Duration, Box<dyn CrispiiError>> {
+    let unit = try_parse_fraction(text, "L")?;
+
+    if unit.is_zero() {
+        return Err(Box::new(InvalidArgumentError::new("L", "The unit note length must not be zero")));
+    }
+
+    Ok(unit)
+}
+
+/// Meters shorter than 3/4 default to sixteenth note units, everything else to eighth notes
+fn get_default_unit(time_signature: TimeSignature) -> Duration {
+    if time_signature.get_measure_duration() < NoteValue::Half.get_dotted_duration(1) {
+        NoteValue::Sixteenth.get_duration()
+    } else {
+        NoteValue::Eighth.get_duration()
+    }
+}
+
+fn try_parse_tempo(text: &str, unit: Duration) -> Result<Tempo, Box<dyn CrispiiError>> {
+    let without_text: String = text.split('"').enumerate().filter(|(index, _)| index % 2 == 0).map(|(_, part)| part).collect();
+
+    match without_text.split_once('=') {
+        Some((beats, beats_per_minute)) => {
+            let beat = beats.split_whitespace().map(|beat| try_parse_fraction(beat, "Q")).try_fold(Duration::zero(), |total, beat| total.try_add(beat?))?;
+            let beats_per_minute = beats_per_minute.trim().parse::<u16>()
+                .map_err(|_| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("Q", format!("'{text}' is not a tempo").as_str())) })?;
+
+            Tempo::try_new(beat, beats_per_minute)
+        }
+        None => match without_text.trim().parse::<u16>() {
+            Ok(beats_per_minute) => Tempo::try_new(unit, beats_per_minute),
+            Err(_) => Ok(Tempo::default()),
+        },
+    }
+}
+
+pub(crate) fn try_parse_key(text: &str) -> Result<Key, Box<dyn CrispiiError>> {
+    let text = text.trim();
+
+    if text.is_empty() || text.starts_with("none") || text.starts_with("clef") {
+        return Ok(Key::default());
+    }
+
+    // Highland pipe music is written without a key signature but is in D mixolydian
+    if text.starts_with("HP") || text.starts_with("Hp") {
+        return Key::try_new(LetterNote::D(Modifier::Default, Octave::Four), Mode::Mixolydian);
+    }
+
+    let mut characters = text.char_indices().peekable();
+    let (_, letter) = characters.next().expect("Empty keys have already been factored out");
+    let mut tonic = LetterNote::try_from_letter(letter, Modifier::Default, Octave::Four)?;
+
+    if let Some((_, accidental)) = characters.peek() {
+        match accidental {
+            '#' => tonic = tonic.with_modifier(Modifier::Sharp),
+            'b' => tonic = tonic.with_modifier(Modifier::Flat),
+            _ => (),
+        }
+
+        if tonic.get_modifier() != Modifier::Default {
+            characters.next();
+        }
+    }
+
+    let rest = characters.peek().map(|(index, _)| &text[*index..]).unwrap_or("").trim_start();
+    let mode_word: String = rest.chars().take_while(|character| character.is_ascii_alphabetic()).collect::<String>().to_ascii_lowercase();
+    let mode = match mode_word.get(..3).unwrap_or(mode_word.as_str()) {
+        "m" | "min" | "aeo" => Mode::Minor,
+        "mix" => Mode::Mixolydian,
+        "dor" => Mode::Dorian,
+        "phr" => Mode::Phrygian,
+        "lyd" => Mode::Lydian,
+        "loc" => Mode::Locrian,
+        _ => Mode::Major,
+    };
+
+    Key::try_new(tonic, mode)
+}
+
+struct BodyParser {
+    key: Key,
+    time_signature: TimeSignature,
+    unit: Duration,
+    melody: Melody,
+    bar_modifiers: HashMap<(u8, Octave), Modifier>,
+    pending_chord_symbol: Option<ChordSymbol>,
+    next_multiplier: Option<(u32, u32)>,
+    tuplet: Option<(u32, u32, u32)>,
+    repeat_start: usize,
+    first_ending_start: Option<usize>,
+    lyric_start: usize,
+    lyrics_pending: bool,
+}
+
+impl BodyParser {
+    fn new(key: Key, time_signature: TimeSignature, unit: Duration, tempo: Tempo) -> BodyParser {
+        BodyParser {
+            key,
+            time_signature,
+            unit,
+            melody: Melody::new(key, time_signature, tempo, Vec::new()),
+            bar_modifiers: HashMap::new(),
+            pending_chord_symbol: None,
+            next_multiplier: None,
+            tuplet: None,
+            repeat_start: 0,
+            first_ending_start: None,
+            lyric_start: 0,
+            lyrics_pending: false,
+        }
+    }
+
+    fn into_melody(self) -> Melody {
+        self.melody
+    }
+
+    fn events(&mut self) -> &mut Vec<Event> {
+        self.melody.get_events_mut()
+    }
+
+    fn try_parse_music_line(&mut self, line: &str) -> Result<(), Box<dyn CrispiiError>> {
+        if !self.lyrics_pending {
+            self.lyric_start = self.melody.get_events().len();
+            self.lyrics_pending = true;
+        }
+
+        let characters: Vec<char> = line.chars().collect();
+        let mut index = 0;
+
+        while index < characters.len() {
+            let character = characters[index];
+
+            match character {
+                ' ' | '\t' | '\\' | '`' | ')' => index += 1,
+                '"' => {
+                    let end = find_from(&characters, index + 1, '"').unwrap_or(characters.len());
+                    let text: String = characters[index + 1..end].iter().collect();
+
+                    if !text.starts_with(['^', '_', '<', '>', '@']) {
+                        self.pending_chord_symbol = ChordSymbol::try_parse(&text).ok();
+                    }
+
+                    index = end + 1;
+                }
+                '!' | '+' => index = find_from(&characters, index + 1, character).map(|end| end + 1).unwrap_or(characters.len()),
+                '{' => index = find_from(&characters, index + 1, '}').map(|end| end + 1).unwrap_or(characters.len()),
+                '.' | '~' | 'H' | 'L' | 'M' | 'O' | 'P' | 'S' | 'T' | 'u' | 'v' => index += 1,
+                '-' => {
+                    if let Some(event) = self.events().last_mut().filter(|event| !event.is_rest()) {
+                        event.set_tied(true);
+                    }
+
+                    index += 1;
+                }
+                '>' | '<' => {
+                    let count = characters[index..].iter().take_while(|other| **other == character).count() as u32;
+
+                    if count >= u32::BITS - 1 {
+                        return Err(Box::new(InvalidArgumentError::new("text", format!("Too many '{character}' in a row in '{line}'").as_str())));
+                    }
+
+                    let shortened = 1 << count;
+                    let lengthened = (2 << count) - 1;
+                    let (previous, next) = if character == '>' { ((lengthened, shortened), (1, shortened)) } else { ((1, shortened), (lengthened, shortened)) };
+
+                    if let Some(event) = self.events().last_mut() {
+                        event.set_duration(event.get_duration().try_multiply(previous.0, previous.1)?);
+                    }
+
+                    self.next_multiplier = Some(next);
+                    index += count as usize;
+                }
+                '(' => {
+                    let (numbers, end) = try_read_tuplet(&characters, index + 1)?;
+
+                    match numbers.first() {
+                        Some(notes) if *notes > 0 => {
+                            let in_time_of = numbers.get(1).copied().filter(|number| *number > 0).unwrap_or(match notes {
+                                3 | 6 => 2,
+                                2 | 4 | 8 => 3,
+                                _ if self.time_signature.is_compound() => 3,
+                                _ => 2,
+                            });
+                            let count = numbers.get(2).copied().filter(|number| *number > 0).unwrap_or(*notes);
+                            self.tuplet = Some((count, in_time_of, *notes));
+                        }
+                        _ => (),
+                    }
+
+                    index = end;
+                }
+                '[' if characters.get(index + 1).is_some_and(|next| next.is_ascii_alphabetic()) && characters.get(index + 2) == Some(&':') => {
+                    let end = find_from(&characters, index + 1, ']').unwrap_or(characters.len());
+                    let field: String = characters[index + 1..end].iter().collect();
+                    let value = &field[2..];
+
+                    match characters[index + 1] {
+                        'K' => self.key = try_parse_key(value)?,
+                        'M' => self.time_signature = try_parse_meter(value)?,
+                        'L' => self.unit = try_parse_unit(value)?,
+                        _ => (),
+                    }
+
+                    index = end + 1;
+                }
+                '[' if characters.get(index + 1) == Some(&'|') => index = self.read_bar(&characters, index + 1),
+                '[' if characters.get(index + 1).is_some_and(|next| next.is_ascii_digit()) => {
+                    self.start_ending(characters[index + 1]);
+                    index += 2;
+                }
+                '[' => {
+                    let end = find_from(&characters, index + 1, ']')
+                        .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("Unterminated chord in '{line}'").as_str())) })?;
+                    let mut notes = Vec::new();
+                    let mut inner_length = None;
+                    let mut inner = index + 1;
+
+                    while inner < end {
+                        match characters[inner] {
+                            '^' | '_' | '=' | 'A'..='G' | 'a'..='g' => {
+                                let (note, length, next) = self.try_read_note(&characters, inner)?;
+                                notes.push(note);
+                                inner_length.get_or_insert(length);
+                                inner = next;
+                            }
+                            _ => inner += 1,
+                        }
+                    }
+
+                    let (outer_length, next) = try_read_length(&characters, end + 1)?;
+
+                    if !notes.is_empty() {
+                        let length = inner_length.unwrap_or((1, 1));
+                        let numerator = length.0.checked_mul(outer_length.0);
+                        let denominator = length.1.checked_mul(outer_length.1);
+                        let length = numerator.zip(denominator)
+                            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("The chord lengths in '{line}' are too large to combine").as_str())) })?;
+                        self.try_push_event(EventKind::Chord(notes), length)?;
+                    }
+
+                    index = next;
+                }
+                '|' | ':' | ']' => index = self.read_bar(&characters, index),
+                'z' | 'x' => {
+                    let (length, next) = try_read_length(&characters, index + 1)?;
+                    self.try_push_event(EventKind::Rest, length)?;
+                    index = next;
+                }
+                'Z' | 'X' => {
+                    let (length, next) = try_read_length(&characters, index + 1)?;
+                    let measures = length.0 / length.1.max(1);
+                    let duration = self.time_signature.get_measure_duration().try_multiply(measures.max(1), 1)?;
+                    self.events().push(Event::new_rest(duration));
+                    index = next;
+                }
+                '^' | '_' | '=' | 'A'..='G' | 'a'..='g' => {
+                    let (note, length, next) = self.try_read_note(&characters, index)?;
+                    self.try_push_event(EventKind::Note(note), length)?;
+                    index = next;
+                }
+                'y' | ',' | '\'' => index += 1,
+                _ => return Err(Box::new(InvalidArgumentError::new("text", format!("Unexpected '{character}' in '{line}'").as_str()))),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn try_push_event(&mut self, kind: EventKind, length: Length) -> Result<(), Box<dyn CrispiiError>> {
+        let mut duration = self.unit.try_multiply(length.0, length.1.max(1))?;
+
+        if let Some((numerator, denominator)) = self.next_multiplier.take() {
+            duration = duration.try_multiply(numerator, denominator)?;
+        }
+
+        if let Some((remaining, in_time_of, notes)) = self.tuplet {
+            duration = duration.try_multiply(in_time_of, notes)?;
+            self.tuplet = (remaining > 1).then_some((remaining - 1, in_time_of, notes));
+        }
+
+        let mut event = Event::new(kind, duration);
+        event.set_chord_symbol(self.pending_chord_symbol.take());
+        self.events().push(event);
+        Ok(())
+    }
+
+    fn try_read_note(&mut self, characters: &[char], start: usize) -> Result<(LetterNote, Length, usize), Box<dyn CrispiiError>> {
+        let mut index = start;
+        let mut accidental: Option<i8> = None;
+
+        while let Some(character) = characters.get(index) {
+            match character {
+                '^' => accidental = Some(accidental.unwrap_or(0) + 1),
+                '_' => accidental = Some(accidental.unwrap_or(0) - 1),
+                '=' => accidental = Some(0),
+                _ => break,
+            }
+
+            index += 1;
+        }
+
+        let letter = *characters.get(index)
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", "Expected a note after an accidental")) })?;
+        let mut octave = if letter.is_ascii_lowercase() { 5 } else { 4 };
+        index += 1;
+
+        while let Some(mark) = characters.get(index) {
+            match mark {
+                ',' => octave -= 1,
+                '\'' => octave += 1,
+                _ => break,
+            }
+
+            index += 1;
+        }
+
+        let octave = Octave::try_from_number(octave)?;
+        let natural = LetterNote::try_from_letter(letter, Modifier::Default, octave)?;
+        let bar_key = (natural.get_letter_index(), octave);
+        let modifier = match accidental {
+            Some(offset) => {
+                let modifier = Modifier::try_from_semitone_offset(offset)?;
+                self.bar_modifiers.insert(bar_key, modifier);
+                modifier
+            }
+            None => self.bar_modifiers.get(&bar_key).copied().unwrap_or_else(|| self.key.get_modifier_for_letter(natural.get_letter_index())),
+        };
+        let (length, index) = try_read_length(characters, index)?;
+
+        Ok((natural.with_modifier(modifier), length, index))
+    }
+
+    fn read_bar(&mut self, characters: &[char], start: usize) -> usize {
+        let mut index = start;
+        let mut symbol = String::new();
+
+        while let Some(character) = characters.get(index).filter(|character| matches!(character, '|' | ':' | ']')) {
+            symbol.push(*character);
+            index += 1;
+        }
+
+        self.bar_modifiers.clear();
+
+        if symbol.starts_with(':') {
+            let repeat_end = self.first_ending_start.take().unwrap_or(self.melody.get_events().len());
+            let repeated: Vec<Event> = self.melody.get_events()[self.repeat_start..repeat_end].to_vec();
+            self.events().extend(repeated);
+            self.repeat_start = self.melody.get_events().len();
+        }
+
+        if symbol.ends_with(':') {
+            self.repeat_start = self.melody.get_events().len();
+            self.first_ending_start = None;
+        }
+
+        if let Some(ending) = characters.get(index).filter(|character| character.is_ascii_digit()) {
+            self.start_ending(*ending);
+            index += 1;
+        } else if characters.get(index) == Some(&'[') && characters.get(index + 1).is_some_and(|next| next.is_ascii_digit()) {
+            self.start_ending(characters[index + 1]);
+            index += 2;
+        }
+
+        while characters.get(index).is_some_and(|character| character.is_ascii_digit() || *character == ',' || *character == '-') {
+            index += 1;
+        }
+
+        index
+    }
+
+    fn start_ending(&mut self, ending: char) {
+        if ending == '1' {
+            self.first_ending_start = Some(self.melody.get_events().len());
+        }
+    }
+
+    /// Lines up the syllables of a w: field with the notes of the music lines since the last one, skipping rests and tied continuations
+    fn add_lyrics(&mut self, text: &str) {
+        let mut syllables = Vec::new();
+        let mut current = String::new();
+
+        for character in text.chars() {
+            match character {
+                ' ' | '\t' => {
+                    if !current.is_empty() {
+                        syllables.push(std::mem::take(&mut current));
+                    }
+                }
+                '-' => {
+                    current.push('-');
+                    syllables.push(std::mem::take(&mut current));
+                }
+                '_' | '*' => {
+                    if !current.is_empty() {
+                        syllables.push(std::mem::take(&mut current));
+                    }
+
+                    syllables.push(character.to_string());
+                }
+                '|' => (),
+                '~' => current.push(' '),
+                _ => current.push(character),
+            }
+        }
+
+        if !current.is_empty() {
+            syllables.push(current);
+        }
+
+        let start = self.lyric_start;
+        let mut syllables = syllables.into_iter().filter(|syllable| syllable != "-");
+        let mut tied_from_previous = start > 0 && self.melody.get_events()[start - 1].is_tied();
+
+        for event in self.events()[start..].iter_mut() {
+            let continuation = tied_from_previous;
+            tied_from_previous = event.is_tied();
+
+            if event.is_rest() || continuation {
+                continue;
+            }
+
+            match syllables.next() {
+                Some(syllable) if syllable == "_" || syllable == "*" => (),
+                Some(syllable) => event.set_lyric(Some(syllable)),
+                None => break,
+            }
+        }
+
+        self.lyrics_pending = false;
+    }
+}
+
+fn find_from(characters: &[char], start: usize, target: char) -> Option<usize> {
+    characters.iter().skip(start).position(|character| *character == target).map(|offset| start + offset)
+}
+
+/// Reads any digits at the start, giving `None` if there are none and failing if there are too many to fit in 32 bits
+fn try_read_number(characters: &[char], start: usize) -> Result<(Option<u32>, usize), Box<dyn CrispiiError>> {
+    let digits: String = characters.iter().skip(start).take_while(|character| character.is_ascii_digit()).collect();
+
+    if digits.is_empty() {
+        return Ok((None, start));
+    }
+
+    let number = digits.parse()
+        .map_err(|_| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("{digits} is too large a number").as_str())) })?;
+
+    Ok((Some(number), start + digits.len()))
+}
+
+fn try_read_tuplet(characters: &[char], start: usize) -> Result<(Vec<u32>, usize), Box<dyn CrispiiError>> {
+    let mut numbers = Vec::new();
+    let (number, mut index) = try_read_number(characters, start)?;
+    numbers.push(number.unwrap_or(0));
+
+    while characters.get(index) == Some(&':') {
+        let (number, next) = try_read_number(characters, index + 1)?;
+        numbers.push(number.unwrap_or(0));
+        index = next;
+    }
+
+    Ok((numbers, index))
+}
+
+/// Reads a length multiplier such as "2", "/", "//", "3/2" or "/4" as a fraction of the unit note length
+fn try_read_length(characters: &[char], start: usize) -> Result<(Length, usize), Box<dyn CrispiiError>> {
+    let (numerator, mut index) = try_read_number(characters, start)?;
+    let mut denominator: u32 = 1;
+
+    while characters.get(index) == Some(&'/') {
+        let (divisor, next) = try_read_number(characters, index + 1)?;
+        denominator = denominator.checked_mul(divisor.unwrap_or(2))
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", "Note lengths cannot be divided that finely")) })?;
+        index = next;
+    }
+
+    if denominator == 0 {
+        return Err(Box::new(InvalidArgumentError::new("text", "Note lengths cannot be divided by zero")));
+    }
+
+    Ok(((numerator.unwrap_or(1), denominator), index))
+}
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use crate::abc::AbcTune;
+use crate::keys::{Key, Mode};
+use crate::notes::{LetterNote, Modifier, Octave};
+use crate::rhythm::{Duration, NoteValue};
+use crate::scores::{Event, EventKind};
+
+const MEASURES_PER_LINE: usize = 4;
+
+pub(crate) fn write_tune(tune: &AbcTune) -> String {
+    let melody = tune.get_melody();
+    // ABC key signatures stop at seven sharps or flats, so theoretical keys are written as their enharmonic equivalents and the notes
+    // take accidentals against that
+    let key = match melody.get_key().get_fifths().abs() > 7 {
+        true => melody.get_key().transpose(0),
+        false => melody.get_key(),
+    };
+    let unit = NoteValue::Eighth.get_duration();
+    let tempo = melody.get_tempo();
+    let mut output = format!("X:{}\n", tune.get_reference_number());
+
+    if let Some(title) = tune.get_title() {
+        output.push_str(&format!("T:{title}\n"));
+    }
+
+    if let Some(composer) = tune.get_composer() {
+        output.push_str(&format!("C:{composer}\n"));
+    }
+
+    output.push_str(&format!("M:{}\n", melody.get_time_signature()));
+    output.push_str(&format!("L:{unit}\n"));
+    output.push_str(&format!("Q:{}={}\n", tempo.get_beat(), tempo.get_beats_per_minute()));
+    output.push_str(&format!("K:{}\n", get_key_text(key)));
+
+    let score = melody.to_score("Melody");
+    let measures = score.get_parts().first()
+        .and_then(|part| part.get_staves().first())
+        .map(|staff| staff.get_measures().to_vec())
+        .unwrap_or_default();
+    let mut tied_from_previous = false;
+    let mut has_lyrics = false;
+
+    for (line_index, line) in measures.chunks(MEASURES_PER_LINE).enumerate() {
+        let mut music = String::new();
+        let mut lyrics = String::new();
+
+        for (measure_index, measure) in line.iter().enumerate() {
+            let mut bar_modifiers: HashMap<(u8, Octave), Modifier> = HashMap::new();
+            let events = measure.get_voices().first().map(|voice| voice.get_events()).unwrap_or_default();
+
+            for event in events {
+                music.push_str(&write_event(event, key, unit, &mut bar_modifiers));
+
+                if !event.is_rest() && !tied_from_previous {
+                    match event.get_lyric() {
+                        Some(lyric) => {
+                            has_lyrics = true;
+                            let lyric = lyric.replace(' ', "~");
+                            lyrics.push_str(&lyric);
+
+                            if !lyric.ends_with('-') {
+                                lyrics.push(' ');
+                            }
+                        }
+                        None => lyrics.push_str("* "),
+                    }
+                }
+
+                tied_from_previous = event.is_tied() && !event.is_rest();
+            }
+
+            let is_last = line_index * MEASURES_PER_LINE + measure_index + 1 == measures.len();
+            music = music.trim_end().to_string();
+            music.push_str(if is_last { " |]" } else { " | " });
+        }
+
+        output.push_str(music.trim_end());
+        output.push('\n');
+
+        let lyrics = lyrics.trim_end().trim_end_matches(['*', ' ']);
+
+        if has_lyrics && !lyrics.is_empty() {
+            output.push_str(&format!("w:{lyrics}\n"));
+        }
+    }
+
+    output
+}
+
+fn write_event(event: &Event, key: Key, unit: Duration, bar_modifiers: &mut HashMap<(u8, Octave), Modifier>) -> String {
+    let mut output = String::new();
+
+    if let Some(chord_symbol) = event.get_chord_symbol() {
+        output.push_str(&format!("\"{chord_symbol}\""));
+    }
+
+    let length = write_length(event.get_duration(), unit);
+
+    match event.get_kind() {
+        EventKind::Note(note) => output.push_str(&write_note(*note, key, bar_modifiers)),
+        EventKind::Chord(notes) => {
+            output.push('[');
+            notes.iter().for_each(|note| output.push_str(&write_note(*note, key, bar_modifiers)));
+            output.push(']');
+        }
+        EventKind::Rest => output.push('z'),
+    }
+
+    output.push_str(&length);
+
+    if event.is_tied() && !event.is_rest() {
+        output.push('-');
+    }
+
+    output.push(' ');
+    output
+}
+
+fn write_note(note: LetterNote, key: Key, bar_modifiers: &mut HashMap<(u8, Octave), Modifier>) -> String {
+    let bar_key = (note.get_letter_index(), note.get_octave());
+    let expected = bar_modifiers.get(&bar_key).copied().unwrap_or_else(|| key.get_modifier_for_letter(note.get_letter_index()));
+    let mut output = String::new();
+
+    if note.get_modifier() != expected {
+        output.push_str(match note.get_modifier() {
+            Modifier::DoubleFlat => "__",
+            Modifier::Flat => "_",
+            Modifier::Default => "=",
+            Modifier::Sharp => "^",
+            Modifier::DoubleSharp => "^^",
+        });
+        bar_modifiers.insert(bar_key, note.get_modifier());
+    }
+
+    let octave = note.get_octave().get_number();
+
+    if octave >= 5 {
+        output.push(note.get_letter().to_ascii_lowercase());
+        (5..octave).for_each(|_| output.push('\''));
+    } else {
+        output.push(note.get_letter());
+        (octave..4).for_each(|_| output.push(','));
+    }
+
+    output
+}
+
+fn write_length(duration: Duration, unit: Duration) -> String {
+    let ratio = duration.multiply(unit.get_denominator(), unit.get_numerator().max(1));
+
+    match (ratio.get_numerator(), ratio.get_denominator()) {
+        (1, 1) => String::new(),
+        (numerator, 1) => numerator.to_string(),
+        (1, 2) => "/".to_string(),
+        (1, denominator) => format!("/{denominator}"),
+        (numerator, denominator) => format!("{numerator}/{denominator}"),
+    }
+}
+
+fn get_key_text(key: Key) -> String {
+    let tonic = key.get_tonic();
+    let mode = match key.get_mode() {
+        Mode::Major => "",
+        Mode::Minor => "m",
+        Mode::Dorian => "dor",
+        Mode::Phrygian => "phr",
+        Mode::Lydian => "lyd",
+        Mode::Mixolydian => "mix",
+        Mode::Locrian => "loc",
+    };
+
+    format!("{}{}{mode}", tonic.get_letter(), tonic.get_modifier().get_symbol())
+}
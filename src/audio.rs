@@ -0,0 +1,32 @@
+mod waveform;
+pub use waveform::Waveform;
+
+mod envelope;
+pub use envelope::Envelope;
+
+mod timbre;
+pub use timbre::Timbre;
+
+mod sample_format;
+pub use sample_format::SampleFormat;
+
+mod pcm_buffer;
+pub use pcm_buffer::PcmBuffer;
+
+mod synth_note;
+pub use synth_note::SynthNote;
+
+mod synthesiser;
+pub use synthesiser::Synthesiser;
+
+mod pitch_estimate;
+pub use pitch_estimate::PitchEstimate;
+
+mod pitch_detector;
+pub use pitch_detector::PitchDetector;
+
+mod detected_note;
+pub use detected_note::DetectedNote;
+
+mod transcriber;
+pub use transcriber::Transcriber;
@@ -0,0 +1,31 @@
+use crate::audio::PitchEstimate;
+
+/// A note found in a recording, with its onset and length in seconds and the median pitch over its frames
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DetectedNote {
+    start: f64,
+    duration: f64,
+    pitch: PitchEstimate,
+}
+
+impl DetectedNote {
+    pub(crate) fn new(start: f64, duration: f64, pitch: PitchEstimate) -> DetectedNote {
+        DetectedNote { start, duration, pitch }
+    }
+
+    pub fn get_start(&self) -> f64 {
+        self.start
+    }
+
+    pub fn get_duration(&self) -> f64 {
+        self.duration
+    }
+
+    pub fn get_end(&self) -> f64 {
+        self.start + self.duration
+    }
+
+    pub fn get_pitch(&self) -> PitchEstimate {
+        self.pitch
+    }
+}
@@ -0,0 +1,81 @@
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+/// An ADSR envelope, with times in seconds and the sustain level from 0 to 1
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Envelope {
+    attack: f64,
+    decay: f64,
+    sustain: f64,
+    release: f64,
+}
+
+impl Default for Envelope {
+    /// A quick, slightly soft start that settles to 70% and fades over a tenth of a second
+    fn default() -> Self {
+        Envelope { attack: 0.01, decay: 0.1, sustain: 0.7, release: 0.1 }
+    }
+}
+
+impl Envelope {
+    pub fn try_new(attack: f64, decay: f64, sustain: f64, release: f64) -> Result<Envelope, Box<dyn CrispiiError>> {
+        for (name, time) in [("attack", attack), ("decay", decay), ("release", release)] {
+            if !(time.is_finite() && time >= 0.0) {
+                return Err(Box::new(InvalidArgumentError::new(name, "Must be finite and not negative")));
+            }
+        }
+
+        if !(0.0..=1.0).contains(&sustain) {
+            return Err(Box::new(InvalidArgumentError::new("sustain", "Must be between 0 and 1 (inclusive)")));
+        }
+
+        Ok(Envelope { attack, decay, sustain, release })
+    }
+
+    pub fn get_attack(&self) -> f64 {
+        self.attack
+    }
+
+    pub fn get_decay(&self) -> f64 {
+        self.decay
+    }
+
+    pub fn get_sustain(&self) -> f64 {
+        self.sustain
+    }
+
+    pub fn get_release(&self) -> f64 {
+        self.release
+    }
+
+    /// The level at a time after the note starts, for a note released after `held` seconds. Releasing early fades from wherever the attack
+    /// or decay had got to
+    pub fn get_level(&self, time: f64, held: f64) -> f64 {
+        if time < 0.0 {
+            return 0.0;
+        }
+
+        if time < held {
+            return self.get_held_level(time);
+        }
+
+        let released = time - held;
+
+        match released < self.release {
+            true => self.get_held_level(held) * (1.0 - released / self.release),
+            false => 0.0,
+        }
+    }
+
+    fn get_held_level(&self, time: f64) -> f64 {
+        if time < self.attack {
+            return time / self.attack;
+        }
+
+        let decayed = time - self.attack;
+
+        match decayed < self.decay {
+            true => 1.0 - (1.0 - self.sustain) * decayed / self.decay,
+            false => self.sustain,
+        }
+    }
+}
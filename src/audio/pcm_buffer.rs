@@ -0,0 +1,185 @@
+use std::path::Path;
+
+use crispii_errors::{CrispiiError, ImpossibleOperationError, InvalidArgumentError};
+
+use crate::audio::SampleFormat;
+
+/// Audio samples from -1 to 1, with the channels of each frame interleaved
+#[derive(Clone, PartialEq, Debug)]
+pub struct PcmBuffer {
+    sample_rate: u32,
+    channels: u16,
+    samples: Vec<f32>,
+}
+
+impl Default for PcmBuffer {
+    /// An empty mono buffer at 44.1 kHz
+    fn default() -> Self {
+        PcmBuffer { sample_rate: 44_100, channels: 1, samples: Vec::new() }
+    }
+}
+
+impl PcmBuffer {
+    pub fn try_new(sample_rate: u32, channels: u16, samples: Vec<f32>) -> Result<PcmBuffer, Box<dyn CrispiiError>> {
+        if sample_rate == 0 {
+            return Err(Box::new(InvalidArgumentError::new("sample_rate", "Must be above 0")));
+        }
+
+        if channels == 0 {
+            return Err(Box::new(InvalidArgumentError::new("channels", "Must be at least 1")));
+        }
+
+        if samples.len() % channels as usize != 0 {
+            return Err(Box::new(InvalidArgumentError::new("samples", "Must hold a whole number of frames")));
+        }
+
+        Ok(PcmBuffer { sample_rate, channels, samples })
+    }
+
+    /// Reads a RIFF WAVE file holding 8, 16, 24 or 32-bit integer or 32 or 64-bit float samples, including the extensible format
+    pub fn try_from_wav_bytes(bytes: &[u8]) -> Result<PcmBuffer, Box<dyn CrispiiError>> {
+        let invalid = |explanation: &str| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("bytes", explanation)) };
+
+        if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+            return Err(invalid("WAV files must start with a RIFF WAVE header"));
+        }
+
+        let mut format = None;
+        let mut data = None;
+        let mut offset = 12;
+
+        while offset + 8 <= bytes.len() {
+            let id = &bytes[offset..offset + 4];
+            let length = u32::from_le_bytes(bytes[offset + 4..offset + 8].try_into().expect("The slice is four bytes long")) as usize;
+            let body = bytes.get(offset + 8..offset + 8 + length).ok_or_else(|| invalid("A chunk runs past the end of the file"))?;
+
+            match id {
+                b"fmt " if length >= 16 => {
+                    let read_u16 = |at: usize| u16::from_le_bytes([body[at], body[at + 1]]);
+                    let tag = match read_u16(0) {
+                        0xFFFE if length >= 26 => read_u16(24),
+                        tag => tag,
+                    };
+
+                    format = Some((tag, read_u16(2), u32::from_le_bytes(body[4..8].try_into().expect("The slice is four bytes long")), read_u16(14)));
+                }
+                b"data" => data = Some(body),
+                _ => {}
+            }
+
+            offset += 8 + length + length % 2;
+        }
+
+        let (tag, channels, sample_rate, bits_per_sample) = format.ok_or_else(|| invalid("WAV files must have a format chunk"))?;
+        let data = data.ok_or_else(|| invalid("WAV files must have a data chunk"))?;
+        let samples: Vec<f32> = match (tag, bits_per_sample) {
+            (1, 8) => data.iter().map(|byte| (*byte as f32 - 128.0) / 128.0).collect(),
+            (1, 16) => data.chunks_exact(2).map(|chunk| i16::from_le_bytes([chunk[0], chunk[1]]) as f32 / 32_768.0).collect(),
+            (1, 24) => data.chunks_exact(3).map(|chunk| i32::from_le_bytes([0, chunk[0], chunk[1], chunk[2]]) as f32 / 2_147_483_648.0).collect(),
+            (1, 32) => data.chunks_exact(4).map(|chunk| i32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) as f32 / 2_147_483_648.0).collect(),
+            (3, 32) => data.chunks_exact(4).map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])).collect(),
+            (3, 64) => data.chunks_exact(8).map(|chunk| f64::from_le_bytes(chunk.try_into().expect("The chunk is eight bytes long")) as f32).collect(),
+            _ => return Err(Box::new(ImpossibleOperationError::new(format!("WAV files with format {tag} and {bits_per_sample} bits per sample are not supported").as_str()))),
+        };
+        let frame_length = (samples.len() / channels.max(1) as usize) * channels.max(1) as usize;
+
+        PcmBuffer::try_new(sample_rate, channels, samples[..frame_length].to_vec())
+    }
+
+    pub fn try_read_wav_from_path<P: AsRef<Path>>(path: P) -> Result<PcmBuffer, Box<dyn CrispiiError>> {
+        let bytes = std::fs::read(path)
+            .map_err(|error| -> Box<dyn CrispiiError> { Box::new(ImpossibleOperationError::new(format!("Could not read the WAV file: {error}").as_str())) })?;
+
+        PcmBuffer::try_from_wav_bytes(&bytes)
+    }
+
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn get_channels(&self) -> u16 {
+        self.channels
+    }
+
+    pub fn get_samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    pub fn get_samples_mut(&mut self) -> &mut Vec<f32> {
+        &mut self.samples
+    }
+
+    pub fn get_frame_count(&self) -> usize {
+        self.samples.len() / self.channels as usize
+    }
+
+    pub fn get_seconds(&self) -> f64 {
+        self.get_frame_count() as f64 / self.sample_rate as f64
+    }
+
+    /// Averages the channels of each frame
+    pub fn to_mono(&self) -> PcmBuffer {
+        let samples = self.samples.chunks(self.channels as usize).map(|frame| frame.iter().sum::<f32>() / frame.len() as f32).collect();
+
+        PcmBuffer { sample_rate: self.sample_rate, channels: 1, samples }
+    }
+
+    /// Scales the buffer so its loudest sample reaches the given peak. Silent buffers are left alone
+    pub fn normalise(&mut self, peak: f32) {
+        let loudest = self.samples.iter().fold(0.0f32, |loudest, sample| loudest.max(sample.abs()));
+
+        if loudest > 0.0 {
+            self.samples.iter_mut().for_each(|sample| *sample *= peak / loudest);
+        }
+    }
+
+    /// A complete RIFF WAVE file. Samples beyond -1 to 1 are clipped when written as integers. Float files carry the extended format
+    /// chunk and the fact chunk the format asks for non-integer data. Fails if the channels, sample rate or length do not fit the header
+    pub fn try_to_wav_bytes(&self, format: SampleFormat) -> Result<Vec<u8>, Box<dyn CrispiiError>> {
+        let too_large = |field: &str| -> Box<dyn CrispiiError> { Box::new(ImpossibleOperationError::new(format!("The buffer's {field} is too large for a WAV file").as_str())) };
+        let bytes_per_sample = format.get_bits_per_sample() / 8;
+        let block_align = self.channels.checked_mul(bytes_per_sample).ok_or_else(|| too_large("channel count"))?;
+        let byte_rate = self.sample_rate.checked_mul(block_align as u32).ok_or_else(|| too_large("sample rate"))?;
+        let data_length = self.samples.len().checked_mul(bytes_per_sample as usize).and_then(|length| u32::try_from(length).ok()).ok_or_else(|| too_large("length"))?;
+        let is_float = format == SampleFormat::Float32;
+        let header_length = if is_float { 58 } else { 44 };
+        let riff_length = (header_length as u32 - 8).checked_add(data_length).ok_or_else(|| too_large("length"))?;
+        let mut bytes = Vec::with_capacity(header_length + data_length as usize);
+
+        bytes.extend(b"RIFF");
+        bytes.extend(riff_length.to_le_bytes());
+        bytes.extend(b"WAVE");
+        bytes.extend(b"fmt ");
+        bytes.extend(if is_float { 18u32 } else { 16u32 }.to_le_bytes());
+        bytes.extend(format.get_format_tag().to_le_bytes());
+        bytes.extend(self.channels.to_le_bytes());
+        bytes.extend(self.sample_rate.to_le_bytes());
+        bytes.extend(byte_rate.to_le_bytes());
+        bytes.extend(block_align.to_le_bytes());
+        bytes.extend(format.get_bits_per_sample().to_le_bytes());
+
+        if is_float {
+            bytes.extend(0u16.to_le_bytes());
+            bytes.extend(b"fact");
+            bytes.extend(4u32.to_le_bytes());
+            bytes.extend((self.get_frame_count() as u32).to_le_bytes());
+        }
+
+        bytes.extend(b"data");
+        bytes.extend(data_length.to_le_bytes());
+
+        for sample in &self.samples {
+            match format {
+                SampleFormat::Int16 => bytes.extend(((sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16).to_le_bytes()),
+                SampleFormat::Float32 => bytes.extend(sample.to_le_bytes()),
+            }
+        }
+
+        Ok(bytes)
+    }
+
+    pub fn try_write_wav_to_path<P: AsRef<Path>>(&self, path: P, format: SampleFormat) -> Result<(), Box<dyn CrispiiError>> {
+        std::fs::write(path, self.try_to_wav_bytes(format)?)
+            .map_err(|error| -> Box<dyn CrispiiError> { Box::new(ImpossibleOperationError::new(format!("Could not write the WAV file: {error}").as_str())) })
+    }
+}
@@ -0,0 +1,225 @@
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::audio::{PcmBuffer, PitchEstimate};
+
+/// Finds the fundamental frequency of monophonic audio with the YIN algorithm (de Cheveigné and Kawahara, 2002)
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PitchDetector {
+    sample_rate: u32,
+    threshold: f64,
+    min_frequency: f64,
+    max_frequency: f64,
+}
+
+impl Default for PitchDetector {
+    /// 44.1 kHz audio with the usual YIN threshold of 0.1, listening from 50 Hz to 2 kHz
+    fn default() -> Self {
+        PitchDetector { sample_rate: 44_100, threshold: 0.1, min_frequency: 50.0, max_frequency: 2_000.0 }
+    }
+}
+
+impl PitchDetector {
+    pub fn try_new(sample_rate: u32) -> Result<PitchDetector, Box<dyn CrispiiError>> {
+        if sample_rate == 0 {
+            return Err(Box::new(InvalidArgumentError::new("sample_rate", "Must be above 0")));
+        }
+
+        Ok(PitchDetector { sample_rate, ..PitchDetector::default() })
+    }
+
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn get_threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    pub fn get_frequency_range(&self) -> (f64, f64) {
+        (self.min_frequency, self.max_frequency)
+    }
+
+    pub fn try_set_sample_rate(&mut self, sample_rate: u32) -> Result<(), Box<dyn CrispiiError>> {
+        if sample_rate == 0 {
+            return Err(Box::new(InvalidArgumentError::new("sample_rate", "Must be above 0")));
+        }
+
+        self.sample_rate = sample_rate;
+        Ok(())
+    }
+
+    /// How aperiodic a frame may be and still count as pitched. Lower values reject more noise, higher ones catch breathier sounds
+    pub fn try_set_threshold(&mut self, threshold: f64) -> Result<(), Box<dyn CrispiiError>> {
+        if !(threshold > 0.0 && threshold < 1.0) {
+            return Err(Box::new(InvalidArgumentError::new("threshold", "Must be between 0 and 1 (exclusive)")));
+        }
+
+        self.threshold = threshold;
+        Ok(())
+    }
+
+    pub fn try_set_frequency_range(&mut self, min_frequency: f64, max_frequency: f64) -> Result<(), Box<dyn CrispiiError>> {
+        if !(min_frequency.is_finite() && min_frequency > 0.0) {
+            return Err(Box::new(InvalidArgumentError::new("min_frequency", "Must be finite and above 0")));
+        }
+
+        if !(max_frequency.is_finite() && max_frequency > min_frequency) {
+            return Err(Box::new(InvalidArgumentError::new("max_frequency", "Must be finite and above the minimum frequency")));
+        }
+
+        self.min_frequency = min_frequency;
+        self.max_frequency = max_frequency;
+        Ok(())
+    }
+
+    /// The shortest frame that can catch the lowest frequency: two of its periods
+    pub fn get_min_frame_length(&self) -> usize {
+        2 * (self.sample_rate as f64 / self.min_frequency).ceil() as usize + 2
+    }
+
+    /// Estimates the pitch of one frame of mono samples, or `None` if it is silent or too noisy to have one
+    pub fn try_detect(&self, frame: &[f32]) -> Result<Option<PitchEstimate>, Box<dyn CrispiiError>> {
+        if frame.len() < self.get_min_frame_length() {
+            return Err(Box::new(InvalidArgumentError::new("frame", format!("Must hold at least {} samples to catch {} Hz", self.get_min_frame_length(), self.min_frequency).as_str())));
+        }
+
+        if frame.iter().all(|sample| *sample == 0.0) {
+            return Ok(None);
+        }
+
+        let sample_rate = self.sample_rate as f64;
+        let min_lag = ((sample_rate / self.max_frequency).floor() as usize).max(2);
+        let max_lag = ((sample_rate / self.min_frequency).ceil() as usize).min(frame.len() / 2);
+        let window = frame.len() - max_lag;
+
+        // The cumulative mean normalised difference, so a perfectly periodic signal dips to 0 at its period
+        let mut normalised = vec![1.0; max_lag + 1];
+        let mut running_sum = 0.0;
+
+        for lag in 1..=max_lag {
+            let difference: f64 = (0..window).map(|index| (frame[index] as f64 - frame[index + lag] as f64).powi(2)).sum();
+            running_sum += difference;
+            normalised[lag] = if running_sum == 0.0 { 1.0 } else { difference * lag as f64 / running_sum };
+        }
+
+        let Some(mut lag) = (min_lag..max_lag).find(|lag| normalised[*lag] < self.threshold) else {
+            return Ok(None);
+        };
+
+        while lag + 1 < max_lag && normalised[lag + 1] < normalised[lag] {
+            lag += 1;
+        }
+
+        // Fit a parabola through the dip and its neighbours to find the period between samples
+        let (before, at, after) = (normalised[lag - 1], normalised[lag], normalised[lag + 1]);
+        let curvature = before - 2.0 * at + after;
+        let shift = if curvature.abs() > f64::EPSILON { (0.5 * (before - after) / curvature).clamp(-1.0, 1.0) } else { 0.0 };
+        let period = lag as f64 + shift;
+
+        Ok(Some(PitchEstimate::new(sample_rate / period, 1.0 - at)))
+    }
+
+    /// Runs over a buffer (mixed down to mono) in overlapping frames, giving any pitch found for each. Frame `i` starts `i * hop_length`
+    /// samples in
+    pub fn try_track(&self, buffer: &PcmBuffer, frame_length: usize, hop_length: usize) -> Result<Vec<Option<PitchEstimate>>, Box<dyn CrispiiError>> {
+        if buffer.get_sample_rate() != self.sample_rate {
+            return Err(Box::new(InvalidArgumentError::new("buffer", format!("Must be sampled at {} Hz like the detector, not {} Hz", self.sample_rate, buffer.get_sample_rate()).as_str())));
+        }
+
+        if hop_length == 0 {
+            return Err(Box::new(InvalidArgumentError::new("hop_length", "Must be at least 1")));
+        }
+
+        let mono = buffer.to_mono();
+        let samples = mono.get_samples();
+        let mut track = Vec::new();
+        let mut start = 0;
+
+        while start + frame_length <= samples.len() {
+            track.push(self.try_detect(&samples[start..start + frame_length])?);
+            start += hop_length;
+        }
+
+        Ok(track)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::audio::{PitchDetector, SynthNote, Synthesiser, Timbre, Waveform};
+    use crate::keys::{Key, Mode};
+    use crate::notes::{LetterNote, Modifier, Octave};
+
+    const FRAME_LENGTH: usize = 2_048;
+
+    /// A frame from the middle of a second long note, well clear of the attack and release
+    fn render_frame(timbre: Timbre, frequency: f64) -> Vec<f32> {
+        let mut synthesiser = Synthesiser::default();
+        synthesiser.set_timbre(timbre);
+        let buffer = synthesiser.render(&[SynthNote::try_new(frequency, 0.0, 1.0, 100).unwrap()]);
+        let start = buffer.get_sample_rate() as usize / 2;
+
+        buffer.get_samples()[start..start + FRAME_LENGTH].to_vec()
+    }
+
+    fn assert_detects(timbre: Timbre, frequency: f64) {
+        let estimate = PitchDetector::default().try_detect(&render_frame(timbre.clone(), frequency)).unwrap()
+            .unwrap_or_else(|| panic!("No pitch found for {frequency} Hz with {timbre:?}"));
+        let error_cents = 1200.0 * (estimate.get_frequency() / frequency).log2();
+
+        assert!(error_cents.abs() < 5.0, "Found {} Hz for {frequency} Hz with {timbre:?}", estimate.get_frequency());
+        assert!(estimate.get_confidence() > 0.8);
+    }
+
+    #[test]
+    fn detects_sine_tones() {
+        for frequency in [82.41, 110.0, 220.0, 261.63, 440.0, 659.25, 987.77, 1_760.0] {
+            assert_detects(Timbre::default(), frequency);
+        }
+    }
+
+    #[test]
+    fn detects_the_fundamental_of_rich_timbres() {
+        for timbre in [Timbre::organ(), Timbre::clarinet(), Timbre::strings(), Timbre::new(Waveform::Sawtooth), Timbre::new(Waveform::Square)] {
+            for frequency in [98.0, 196.0, 329.63, 523.25] {
+                assert_detects(timbre.clone(), frequency);
+            }
+        }
+    }
+
+    #[test]
+    fn finds_the_nearest_note_and_cents_offset() {
+        let estimate = PitchDetector::default().try_detect(&render_frame(Timbre::default(), 445.0)).unwrap().unwrap();
+        let (note, cents) = estimate.try_get_nearest_note(Key::try_new(LetterNote::C(Modifier::Default, Octave::Four), Mode::Major).unwrap()).unwrap();
+
+        assert_eq!(estimate.get_nearest_pitch_number(), 69);
+        assert_eq!(note, LetterNote::A(Modifier::Default, Octave::Four));
+        assert!((cents - 19.56).abs() < 3.0, "Found {cents} cents");
+
+        let estimate = PitchDetector::default().try_detect(&render_frame(Timbre::clarinet(), 227.0)).unwrap().unwrap();
+        let (note, cents) = estimate.try_get_nearest_note(Key::try_new(LetterNote::E(Modifier::Flat, Octave::Four), Mode::Major).unwrap()).unwrap();
+
+        assert_eq!(note, LetterNote::B(Modifier::Flat, Octave::Three));
+        assert!((cents - -45.76).abs() < 3.0, "Found {cents} cents");
+    }
+
+    #[test]
+    fn finds_no_pitch_in_silence_or_noise() {
+        let detector = PitchDetector::default();
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let noise: Vec<f32> = (0..FRAME_LENGTH).map(|_| rng.random_range(-0.5..0.5)).collect();
+
+        assert_eq!(detector.try_detect(&[0.0; FRAME_LENGTH]).unwrap(), None);
+        assert_eq!(detector.try_detect(&noise).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_frames_too_short_for_the_lowest_frequency() {
+        let detector = PitchDetector::default();
+
+        assert!(detector.try_detect(&vec![0.0; detector.get_min_frame_length() - 1]).is_err());
+    }
+}
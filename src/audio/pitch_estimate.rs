@@ -0,0 +1,40 @@
+use crispii_errors::CrispiiError;
+
+use crate::keys::Key;
+use crate::notes::LetterNote;
+
+/// A detected fundamental frequency in hertz, with a confidence from 0 (a guess) to 1 (a perfectly periodic signal)
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PitchEstimate {
+    frequency: f64,
+    confidence: f64,
+}
+
+impl PitchEstimate {
+    pub(crate) fn new(frequency: f64, confidence: f64) -> PitchEstimate {
+        PitchEstimate { frequency, confidence: confidence.clamp(0.0, 1.0) }
+    }
+
+    pub fn get_frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    pub fn get_confidence(&self) -> f64 {
+        self.confidence
+    }
+
+    /// The nearest equal tempered pitch as a MIDI style pitch number (60 is middle C), with A4 at 440 Hz
+    pub fn get_nearest_pitch_number(&self) -> i16 {
+        (69.0 + 12.0 * (self.frequency / 440.0).log2()).round() as i16
+    }
+
+    /// How far the frequency is from the nearest equal tempered pitch, from -50 to 50 cents
+    pub fn get_cents_offset(&self) -> f64 {
+        100.0 * (12.0 * (self.frequency / 440.0).log2() + 69.0 - self.get_nearest_pitch_number() as f64)
+    }
+
+    /// The nearest note, spelt as the key would spell it, along with the cents offset from it
+    pub fn try_get_nearest_note(&self, key: Key) -> Result<(LetterNote, f64), Box<dyn CrispiiError>> {
+        Ok((key.try_spell(self.get_nearest_pitch_number())?, self.get_cents_offset()))
+    }
+}
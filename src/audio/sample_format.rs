@@ -0,0 +1,35 @@
+use std::fmt::Display;
+
+/// How each sample is stored in a WAV file
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum SampleFormat {
+    #[default]
+    Int16,
+    Float32,
+}
+
+impl Display for SampleFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SampleFormat::Int16 => write!(f, "16-bit Integer"),
+            SampleFormat::Float32 => write!(f, "32-bit Float"),
+        }
+    }
+}
+
+impl SampleFormat {
+    pub fn get_bits_per_sample(&self) -> u16 {
+        match self {
+            SampleFormat::Int16 => 16,
+            SampleFormat::Float32 => 32,
+        }
+    }
+
+    /// The WAV format tag: 1 for integer PCM and 3 for IEEE floats
+    pub fn get_format_tag(&self) -> u16 {
+        match self {
+            SampleFormat::Int16 => 1,
+            SampleFormat::Float32 => 3,
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::notes::LetterNote;
+
+/// A note to synthesise, with times in seconds and a MIDI style velocity from 0 to 127
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SynthNote {
+    frequency: f64,
+    start: f64,
+    duration: f64,
+    velocity: u8,
+}
+
+impl SynthNote {
+    pub fn try_new(frequency: f64, start: f64, duration: f64, velocity: u8) -> Result<SynthNote, Box<dyn CrispiiError>> {
+        if !(frequency.is_finite() && frequency > 0.0) {
+            return Err(Box::new(InvalidArgumentError::new("frequency", "Must be finite and above 0")));
+        }
+
+        if !(start.is_finite() && start >= 0.0) {
+            return Err(Box::new(InvalidArgumentError::new("start", "Must be finite and not negative")));
+        }
+
+        if !(duration.is_finite() && duration >= 0.0) {
+            return Err(Box::new(InvalidArgumentError::new("duration", "Must be finite and not negative")));
+        }
+
+        if velocity > 127 {
+            return Err(Box::new(InvalidArgumentError::new("velocity", "Must be between 0 and 127 (inclusive)")));
+        }
+
+        Ok(SynthNote { frequency, start, duration, velocity })
+    }
+
+    /// Sounds the note at its equal tempered frequency
+    pub fn try_from_note(note: LetterNote, start: f64, duration: f64, velocity: u8) -> Result<SynthNote, Box<dyn CrispiiError>> {
+        SynthNote::try_new(note.get_frequency(), start, duration, velocity)
+    }
+
+    pub fn get_frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    pub fn get_start(&self) -> f64 {
+        self.start
+    }
+
+    pub fn get_duration(&self) -> f64 {
+        self.duration
+    }
+
+    pub fn get_velocity(&self) -> u8 {
+        self.velocity
+    }
+}
@@ -0,0 +1,142 @@
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::audio::{Envelope, PcmBuffer, SynthNote, Timbre};
+use crate::rhythm::{Duration, Tempo};
+use crate::scores::{Melody, Score};
+
+const DEFAULT_VELOCITY: u8 = 100;
+
+/// Renders notes to mono PCM by adding up the timbre's partials under an ADSR envelope
+#[derive(Clone, PartialEq, Debug)]
+pub struct Synthesiser {
+    sample_rate: u32,
+    timbre: Timbre,
+    envelope: Envelope,
+    gain: f64,
+}
+
+impl Default for Synthesiser {
+    /// A sine tone at 44.1 kHz, with enough headroom for four loud notes at once
+    fn default() -> Self {
+        Synthesiser {
+            sample_rate: 44_100,
+            timbre: Timbre::default(),
+            envelope: Envelope::default(),
+            gain: 0.25,
+        }
+    }
+}
+
+impl Synthesiser {
+    pub fn try_new(sample_rate: u32, timbre: Timbre, envelope: Envelope) -> Result<Synthesiser, Box<dyn CrispiiError>> {
+        if sample_rate == 0 {
+            return Err(Box::new(InvalidArgumentError::new("sample_rate", "Must be above 0")));
+        }
+
+        Ok(Synthesiser { sample_rate, timbre, envelope, ..Synthesiser::default() })
+    }
+
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn get_timbre(&self) -> &Timbre {
+        &self.timbre
+    }
+
+    pub fn get_envelope(&self) -> Envelope {
+        self.envelope
+    }
+
+    pub fn get_gain(&self) -> f64 {
+        self.gain
+    }
+
+    pub fn set_timbre(&mut self, timbre: Timbre) {
+        self.timbre = timbre;
+    }
+
+    pub fn set_envelope(&mut self, envelope: Envelope) {
+        self.envelope = envelope;
+    }
+
+    /// The level a single note at full velocity peaks at
+    pub fn try_set_gain(&mut self, gain: f64) -> Result<(), Box<dyn CrispiiError>> {
+        if !(gain.is_finite() && gain >= 0.0) {
+            return Err(Box::new(InvalidArgumentError::new("gain", "Must be finite and not negative")));
+        }
+
+        self.gain = gain;
+        Ok(())
+    }
+
+    /// Mixes the notes into one buffer long enough for the last release to finish. The mix is clipped to -1 to 1
+    pub fn render(&self, notes: &[SynthNote]) -> PcmBuffer {
+        let sample_rate = self.sample_rate as f64;
+        let end = notes.iter().map(|note| note.get_start() + note.get_duration() + self.envelope.get_release()).fold(0.0, f64::max);
+        let mut samples = vec![0.0f64; (end * sample_rate).ceil() as usize];
+
+        for note in notes {
+            let first = (note.get_start() * sample_rate).round() as usize;
+            let length = ((note.get_duration() + self.envelope.get_release()) * sample_rate).ceil() as usize;
+            let amplitude = self.gain * note.get_velocity() as f64 / 127.0;
+
+            for (offset, sample) in samples.iter_mut().skip(first).take(length).enumerate() {
+                let time = offset as f64 / sample_rate;
+                let level = self.envelope.get_level(time, note.get_duration());
+
+                *sample += amplitude * level * self.timbre.get_level(note.get_frequency(), time, sample_rate / 2.0);
+            }
+        }
+
+        let samples = samples.into_iter().map(|sample| sample.clamp(-1.0, 1.0) as f32).collect();
+
+        PcmBuffer::try_new(self.sample_rate, 1, samples).expect("The sample rate has already been checked")
+    }
+
+    pub fn render_melody(&self, melody: &Melody) -> PcmBuffer {
+        self.render_score(&melody.to_score("Melody"))
+    }
+
+    /// Renders every sounding note of every part, following the score's tempo changes
+    pub fn render_score(&self, score: &Score) -> PcmBuffer {
+        let timings = get_measure_timings(score);
+        let notes: Vec<SynthNote> = score.get_timed_notes().iter()
+            .filter_map(|timed_note| {
+                let start = get_seconds_at(&timings, timed_note.get_start());
+                let end = get_seconds_at(&timings, timed_note.get_end());
+
+                SynthNote::try_from_note(timed_note.get_note(), start, end - start, DEFAULT_VELOCITY).ok()
+            })
+            .collect();
+
+        self.render(&notes)
+    }
+}
+
+/// Where each measure starts, both in the score and in seconds, along with the tempo it is played at
+fn get_measure_timings(score: &Score) -> Vec<(Duration, f64, Tempo)> {
+    let mut timings: Vec<(Duration, f64, Tempo)> = Vec::new();
+
+    for measure_index in 0..score.get_measure_count().max(1) {
+        let tempo = score.get_tempo_at(measure_index);
+        let timing = match timings.last() {
+            Some((start, seconds, previous_tempo)) => {
+                let measure_duration = score.get_time_signature_at(measure_index - 1).get_measure_duration();
+                (*start + measure_duration, seconds + previous_tempo.get_seconds(measure_duration), tempo)
+            }
+            None => (Duration::zero(), 0.0, tempo),
+        };
+
+        timings.push(timing);
+    }
+
+    timings
+}
+
+/// How many seconds into the score a position falls, with each measure played at its own tempo
+fn get_seconds_at(timings: &[(Duration, f64, Tempo)], position: Duration) -> f64 {
+    let (start, seconds, tempo) = timings.iter().rev().find(|(start, _, _)| *start <= position).unwrap_or(&timings[0]);
+
+    seconds + tempo.get_seconds(position.try_subtract(*start).unwrap_or_else(|_| Duration::zero()))
+}
@@ -0,0 +1,98 @@
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::audio::Waveform;
+
+/// A sound built by adding up partials, each a waveform at some multiple of the note's frequency
+#[derive(Clone, PartialEq, Debug)]
+pub struct Timbre {
+    waveform: Waveform,
+    partials: Vec<(f64, f64)>,
+}
+
+impl Default for Timbre {
+    /// A pure sine tone
+    fn default() -> Self {
+        Timbre::new(Waveform::Sine)
+    }
+}
+
+impl Timbre {
+    /// A single partial at the note's frequency
+    pub fn new(waveform: Waveform) -> Timbre {
+        Timbre { waveform, partials: vec![(1.0, 1.0)] }
+    }
+
+    /// Sine partials at the given frequency ratios and amplitudes
+    pub fn try_from_partials(partials: &[(f64, f64)]) -> Result<Timbre, Box<dyn CrispiiError>> {
+        let mut timbre = Timbre { waveform: Waveform::Sine, partials: Vec::new() };
+
+        for (ratio, amplitude) in partials {
+            timbre.try_add_partial(*ratio, *amplitude)?;
+        }
+
+        Ok(timbre)
+    }
+
+    /// Drawbars at the fundamental, octave, twelfth, fifteenth, seventeenth and nineteenth
+    pub fn organ() -> Timbre {
+        Timbre::try_from_partials(&[(1.0, 1.0), (2.0, 0.8), (3.0, 0.6), (4.0, 0.5), (5.0, 0.3), (6.0, 0.3)]).expect("Preset partials are valid")
+    }
+
+    /// Mostly odd harmonics, like a clarinet's closed pipe
+    pub fn clarinet() -> Timbre {
+        Timbre::try_from_partials(&[(1.0, 1.0), (3.0, 0.6), (5.0, 0.35), (7.0, 0.2), (9.0, 0.1), (2.0, 0.05)]).expect("Preset partials are valid")
+    }
+
+    /// Every harmonic, fading as 1/n like a bowed string
+    pub fn strings() -> Timbre {
+        let partials: Vec<(f64, f64)> = (1..=10).map(|harmonic| (harmonic as f64, 1.0 / harmonic as f64)).collect();
+
+        Timbre::try_from_partials(&partials).expect("Preset partials are valid")
+    }
+
+    /// Inharmonic partials like a struck bell or glockenspiel bar
+    pub fn bell() -> Timbre {
+        Timbre::try_from_partials(&[(1.0, 1.0), (2.76, 0.5), (5.40, 0.25), (8.93, 0.12)]).expect("Preset partials are valid")
+    }
+
+    pub fn get_waveform(&self) -> Waveform {
+        self.waveform
+    }
+
+    /// Each partial's frequency ratio to the note and its amplitude
+    pub fn get_partials(&self) -> &[(f64, f64)] {
+        &self.partials
+    }
+
+    pub fn set_waveform(&mut self, waveform: Waveform) {
+        self.waveform = waveform;
+    }
+
+    pub fn try_add_partial(&mut self, ratio: f64, amplitude: f64) -> Result<(), Box<dyn CrispiiError>> {
+        if !(ratio.is_finite() && ratio > 0.0) {
+            return Err(Box::new(InvalidArgumentError::new("ratio", "Must be finite and above 0")));
+        }
+
+        if !(amplitude.is_finite() && amplitude >= 0.0) {
+            return Err(Box::new(InvalidArgumentError::new("amplitude", "Must be finite and not negative")));
+        }
+
+        self.partials.push((ratio, amplitude));
+        Ok(())
+    }
+
+    /// The level at a time (in seconds) into a note of the given frequency, scaled so the partials together peak at no more than 1. Partials
+    /// at or above the Nyquist frequency are left out
+    pub fn get_level(&self, frequency: f64, time: f64, nyquist: f64) -> f64 {
+        let total: f64 = self.partials.iter().map(|(_, amplitude)| amplitude).sum();
+
+        if total == 0.0 {
+            return 0.0;
+        }
+
+        self.partials.iter()
+            .filter(|(ratio, _)| frequency * ratio < nyquist)
+            .map(|(ratio, amplitude)| amplitude * self.waveform.get_level(frequency * ratio * time))
+            .sum::<f64>() / total
+    }
+}
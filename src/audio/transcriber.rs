@@ -0,0 +1,308 @@
+use std::path::Path;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::audio::{DetectedNote, PcmBuffer, PitchDetector, PitchEstimate};
+use crate::keys::Key;
+use crate::rhythm::{Duration, NoteValue, Tempo, TimeSignature};
+use crate::scores::{Event, Melody};
+
+const FRAME_SECONDS: f64 = 0.046;
+
+/// Turns a monophonic recording into a melody: tracks the pitch frame by frame, groups frames into notes, then snaps them to a rhythmic
+/// grid at the given tempo and spells them in a key
+#[derive(Clone, PartialEq, Debug)]
+pub struct Transcriber {
+    detector: PitchDetector,
+    tempo: Tempo,
+    time_signature: TimeSignature,
+    key: Option<Key>,
+    grid: Duration,
+    min_note_seconds: f64,
+    min_confidence: f64,
+    silence_threshold: f64,
+}
+
+impl Default for Transcriber {
+    /// Quantises to sixteenths at the default tempo, guesses the key, and ignores notes shorter than 80 ms or quieter than -40 dBFS
+    fn default() -> Self {
+        Transcriber {
+            detector: PitchDetector::default(),
+            tempo: Tempo::default(),
+            time_signature: TimeSignature::default(),
+            key: None,
+            grid: NoteValue::Sixteenth.get_duration(),
+            min_note_seconds: 0.08,
+            min_confidence: 0.8,
+            silence_threshold: 0.01,
+        }
+    }
+}
+
+impl Transcriber {
+    pub fn new(tempo: Tempo) -> Transcriber {
+        Transcriber { tempo, ..Transcriber::default() }
+    }
+
+    pub fn get_detector(&self) -> PitchDetector {
+        self.detector
+    }
+
+    pub fn get_tempo(&self) -> Tempo {
+        self.tempo
+    }
+
+    pub fn get_time_signature(&self) -> TimeSignature {
+        self.time_signature
+    }
+
+    pub fn get_key(&self) -> Option<Key> {
+        self.key
+    }
+
+    pub fn get_grid(&self) -> Duration {
+        self.grid
+    }
+
+    pub fn get_min_note_seconds(&self) -> f64 {
+        self.min_note_seconds
+    }
+
+    pub fn get_min_confidence(&self) -> f64 {
+        self.min_confidence
+    }
+
+    pub fn get_silence_threshold(&self) -> f64 {
+        self.silence_threshold
+    }
+
+    /// The detector's threshold and frequency range are used as they are. Its sample rate is matched to each recording
+    pub fn set_detector(&mut self, detector: PitchDetector) {
+        self.detector = detector;
+    }
+
+    pub fn set_tempo(&mut self, tempo: Tempo) {
+        self.tempo = tempo;
+    }
+
+    pub fn set_time_signature(&mut self, time_signature: TimeSignature) {
+        self.time_signature = time_signature;
+    }
+
+    /// The key to spell notes in, or `None` to estimate it from the notes found
+    pub fn set_key(&mut self, key: Option<Key>) {
+        self.key = key;
+    }
+
+    /// The shortest step onsets and lengths are rounded to
+    pub fn try_set_grid(&mut self, grid: Duration) -> Result<(), Box<dyn CrispiiError>> {
+        if grid.is_zero() {
+            return Err(Box::new(InvalidArgumentError::new("grid", "Must not be zero")));
+        }
+
+        self.grid = grid;
+        Ok(())
+    }
+
+    /// Anything shorter is folded into a neighbouring note
+    pub fn try_set_min_note_seconds(&mut self, min_note_seconds: f64) -> Result<(), Box<dyn CrispiiError>> {
+        if !(min_note_seconds.is_finite() && min_note_seconds >= 0.0) {
+            return Err(Box::new(InvalidArgumentError::new("min_note_seconds", "Must be finite and not negative")));
+        }
+
+        self.min_note_seconds = min_note_seconds;
+        Ok(())
+    }
+
+    pub fn try_set_min_confidence(&mut self, min_confidence: f64) -> Result<(), Box<dyn CrispiiError>> {
+        if !(0.0..=1.0).contains(&min_confidence) {
+            return Err(Box::new(InvalidArgumentError::new("min_confidence", "Must be between 0 and 1 (inclusive)")));
+        }
+
+        self.min_confidence = min_confidence;
+        Ok(())
+    }
+
+    /// The RMS level from 0 to 1 below which a frame counts as silence
+    pub fn try_set_silence_threshold(&mut self, silence_threshold: f64) -> Result<(), Box<dyn CrispiiError>> {
+        if !(0.0..=1.0).contains(&silence_threshold) {
+            return Err(Box::new(InvalidArgumentError::new("silence_threshold", "Must be between 0 and 1 (inclusive)")));
+        }
+
+        self.silence_threshold = silence_threshold;
+        Ok(())
+    }
+
+    /// Finds the notes in a recording, in seconds and unquantised. A note starts wherever the nearest semitone changes, or where the
+    /// level dips and swells again on the same pitch
+    pub fn try_find_notes(&self, buffer: &PcmBuffer) -> Result<Vec<DetectedNote>, Box<dyn CrispiiError>> {
+        let mut detector = self.detector;
+        detector.try_set_sample_rate(buffer.get_sample_rate())?;
+
+        let sample_rate = buffer.get_sample_rate() as f64;
+        let frame_length = ((FRAME_SECONDS * sample_rate) as usize).max(detector.get_min_frame_length());
+        let hop_length = (frame_length / 8).max(1);
+        let hop_seconds = hop_length as f64 / sample_rate;
+        let mono = buffer.to_mono();
+        let samples = mono.get_samples();
+
+        let mut frames: Vec<(f64, Option<PitchEstimate>)> = Vec::new();
+        let mut start = 0;
+
+        while start + frame_length <= samples.len() {
+            let frame = &samples[start..start + frame_length];
+            let level = (frame.iter().map(|sample| (*sample as f64).powi(2)).sum::<f64>() / frame_length as f64).sqrt();
+            let pitch = match level >= self.silence_threshold {
+                true => detector.try_detect(frame)?.filter(|pitch| pitch.get_confidence() >= self.min_confidence),
+                false => None,
+            };
+
+            frames.push((level, pitch));
+            start += hop_length;
+        }
+
+        // Runs of frames sharing a nearest semitone (or silence), as (pitch number, first frame, frame after the last)
+        let mut runs: Vec<(Option<i16>, usize, usize)> = Vec::new();
+
+        for (index, (_, pitch)) in frames.iter().enumerate() {
+            let pitch_number = pitch.map(|pitch| pitch.get_nearest_pitch_number());
+
+            match runs.last_mut() {
+                Some((last, _, end)) if *last == pitch_number => *end = index + 1,
+                _ => runs.push((pitch_number, index, index + 1)),
+            }
+        }
+
+        // Short pitched runs are usually the mix of two notes as one gives way to the next, so they start the note after them. Short gaps
+        // belong to the note before
+        let min_frames = (self.min_note_seconds / hop_seconds).ceil() as usize;
+        let mut merged: Vec<(Option<i16>, usize, usize)> = Vec::new();
+        let mut pending_first = None;
+
+        for (pitch_number, first, end) in runs {
+            if end - first < min_frames {
+                match (pitch_number, merged.last_mut()) {
+                    (None, Some((_, _, last_end))) if pending_first.is_none() => *last_end = end,
+                    _ => pending_first = pending_first.or(Some(first)),
+                }
+
+                continue;
+            }
+
+            let first = pending_first.take().unwrap_or(first);
+
+            match merged.last_mut() {
+                Some((last, _, last_end)) if *last == pitch_number => *last_end = end,
+                _ => merged.push((pitch_number, first, end)),
+            }
+        }
+
+        if let (Some(_), Some((_, _, last_end))) = (pending_first, merged.last_mut()) {
+            *last_end = frames.len();
+        }
+
+        let mut notes = Vec::new();
+
+        for (pitch_number, first, end) in merged {
+            if pitch_number.is_none() || end - first < min_frames {
+                continue;
+            }
+
+            for (first, end) in split_at_level_dips(&frames, first, end, min_frames) {
+                let mut pitches: Vec<PitchEstimate> = frames[first..end].iter().filter_map(|(_, pitch)| *pitch).collect();
+
+                if pitches.is_empty() {
+                    continue;
+                }
+
+                pitches.sort_by(|pitch, other| pitch.get_frequency().total_cmp(&other.get_frequency()));
+
+                let median = pitches[pitches.len() / 2].get_frequency();
+                let confidence = pitches.iter().map(|pitch| pitch.get_confidence()).sum::<f64>() / pitches.len() as f64;
+                // Each frame stands for the moment at its centre
+                let start = (first * hop_length) as f64 / sample_rate + frame_length as f64 / sample_rate / 2.0;
+
+                notes.push(DetectedNote::new(start, (end - first) as f64 * hop_seconds, PitchEstimate::new(median, confidence)));
+            }
+        }
+
+        Ok(notes)
+    }
+
+    /// Finds the notes, then places them on the grid from the first onset, with rests for the gaps and ties for lengths no single note
+    /// value covers
+    pub fn try_transcribe(&self, buffer: &PcmBuffer) -> Result<Melody, Box<dyn CrispiiError>> {
+        let notes = self.try_find_notes(buffer)?;
+        let key = match self.key {
+            Some(key) => key,
+            None => {
+                let mut weights = [0.0; 12];
+
+                for note in &notes {
+                    weights[note.get_pitch().get_nearest_pitch_number().rem_euclid(12) as usize] += note.get_duration();
+                }
+
+                Key::estimate(weights)
+            }
+        };
+
+        let grid_seconds = self.tempo.get_seconds(self.grid);
+        let offset = notes.first().map(|note| note.get_start()).unwrap_or(0.0);
+        let mut events = Vec::new();
+        let mut position = 0u32;
+
+        for note in &notes {
+            let start = (((note.get_start() - offset) / grid_seconds).round() as u32).max(position);
+            let end = (((note.get_end() - offset) / grid_seconds).round() as u32).max(start.saturating_add(1));
+
+            if start > position {
+                for (note_value, dots) in self.grid.try_multiply(start - position, 1)?.try_split_into_note_values()? {
+                    events.push(Event::new_rest(note_value.get_dotted_duration(dots)));
+                }
+            }
+
+            let letter_note = key.try_spell(note.get_pitch().get_nearest_pitch_number())?;
+            let parts = self.grid.try_multiply(end - start, 1)?.try_split_into_note_values()?;
+
+            for (index, (note_value, dots)) in parts.iter().enumerate() {
+                let mut event = Event::new_note(letter_note, note_value.get_dotted_duration(*dots));
+                event.set_tied(index + 1 < parts.len());
+                events.push(event);
+            }
+
+            position = end;
+        }
+
+        Ok(Melody::new(key, self.time_signature, self.tempo, events))
+    }
+
+    pub fn try_transcribe_wav<P: AsRef<Path>>(&self, path: P) -> Result<Melody, Box<dyn CrispiiError>> {
+        self.try_transcribe(&PcmBuffer::try_read_wav_from_path(path)?)
+    }
+}
+
+/// Splits a run of frames wherever the level falls below half of the peaks on both sides of it, which is where a repeated note is
+/// re-articulated
+fn split_at_level_dips(frames: &[(f64, Option<PitchEstimate>)], first: usize, end: usize, min_frames: usize) -> Vec<(usize, usize)> {
+    let mut parts = Vec::new();
+    let mut part_first = first;
+    let mut index = first + min_frames.max(1);
+
+    while index + min_frames.max(1) < end {
+        let level = frames[index].0;
+        let peak_before = frames[part_first..index].iter().map(|(level, _)| *level).fold(0.0, f64::max);
+        let peak_after = frames[index + 1..end].iter().map(|(level, _)| *level).fold(0.0, f64::max);
+        let is_valley = level <= frames[index - 1].0 && level <= frames[index + 1].0;
+
+        if is_valley && level < 0.5 * peak_before.min(peak_after) {
+            parts.push((part_first, index));
+            part_first = index;
+            index += min_frames.max(1);
+        } else {
+            index += 1;
+        }
+    }
+
+    parts.push((part_first, end));
+    parts
+}
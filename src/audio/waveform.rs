@@ -0,0 +1,38 @@
+use std::f64::consts::TAU;
+use std::fmt::Display;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum Waveform {
+    #[default]
+    Sine,
+    Square,
+    Sawtooth,
+    Triangle,
+}
+
+impl Display for Waveform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Waveform::Sine => write!(f, "Sine"),
+            Waveform::Square => write!(f, "Square"),
+            Waveform::Sawtooth => write!(f, "Sawtooth"),
+            Waveform::Triangle => write!(f, "Triangle"),
+        }
+    }
+}
+
+impl Waveform {
+    pub const ALL: [Waveform; 4] = [Waveform::Sine, Waveform::Square, Waveform::Sawtooth, Waveform::Triangle];
+
+    /// The level from -1 to 1 at a point in the cycle, where the phase counts whole cycles (so only its fractional part matters)
+    pub fn get_level(&self, phase: f64) -> f64 {
+        let phase = phase.rem_euclid(1.0);
+
+        match self {
+            Waveform::Sine => (TAU * phase).sin(),
+            Waveform::Square => if phase < 0.5 { 1.0 } else { -1.0 },
+            Waveform::Sawtooth => 2.0 * phase - 1.0,
+            Waveform::Triangle => 1.0 - 4.0 * (phase - 0.5).abs(),
+        }
+    }
+}
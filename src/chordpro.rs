@@ -0,0 +1,13 @@
+mod section_kind;
+pub use section_kind::SectionKind;
+
+mod chordpro_line;
+pub use chordpro_line::{ChordProLine, InlineChord, LyricSegment};
+
+mod chordpro_section;
+pub use chordpro_section::ChordProSection;
+
+mod chordpro_sheet;
+pub use chordpro_sheet::ChordProSheet;
+
+mod parser;
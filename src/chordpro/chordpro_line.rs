@@ -0,0 +1,85 @@
+use std::fmt::Display;
+
+use crate::chords::ChordSymbol;
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum InlineChord {
+    Symbol(ChordSymbol),
+    /// Anything in square brackets that is not a chord, such as [N.C.] or an [*annotation]
+    Text(String),
+}
+
+impl Display for InlineChord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InlineChord::Symbol(chord_symbol) => write!(f, "{chord_symbol}"),
+            InlineChord::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+/// A run of lyrics, along with the chord (if any) written above its first syllable
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct LyricSegment {
+    chord: Option<InlineChord>,
+    text: String,
+}
+
+impl Display for LyricSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.chord {
+            Some(chord) => write!(f, "[{chord}]{}", self.text),
+            None => write!(f, "{}", self.text),
+        }
+    }
+}
+
+impl LyricSegment {
+    pub fn new(chord: Option<InlineChord>, text: &str) -> LyricSegment {
+        LyricSegment {
+            chord,
+            text: text.to_string(),
+        }
+    }
+
+    pub fn get_chord(&self) -> Option<&InlineChord> {
+        self.chord.as_ref()
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_chord(&mut self, chord: Option<InlineChord>) {
+        self.chord = chord;
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ChordProLine {
+    /// A {name: value} line other than those opening or closing a section. Short names such as t or soc are expanded
+    Directive(String, Option<String>),
+    Lyrics(Vec<LyricSegment>),
+    /// A line starting with #
+    Comment(String),
+    /// A line from a tab or grid section, kept as written
+    Text(String),
+    Empty,
+}
+
+impl Display for ChordProLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChordProLine::Directive(name, Some(value)) => write!(f, "{{{name}: {value}}}"),
+            ChordProLine::Directive(name, None) => write!(f, "{{{name}}}"),
+            ChordProLine::Lyrics(segments) => segments.iter().try_for_each(|segment| write!(f, "{segment}")),
+            ChordProLine::Comment(text) => write!(f, "#{text}"),
+            ChordProLine::Text(text) => write!(f, "{text}"),
+            ChordProLine::Empty => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt::Display;
+
+use crate::chordpro::{ChordProLine, SectionKind};
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct ChordProSection {
+    kind: SectionKind,
+    label: Option<String>,
+    lines: Vec<ChordProLine>,
+}
+
+impl Display for ChordProSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = self.kind.get_directive_name();
+
+        if let Some(name) = name {
+            match &self.label {
+                Some(label) => writeln!(f, "{{start_of_{name}: {label}}}")?,
+                None => writeln!(f, "{{start_of_{name}}}")?,
+            }
+        }
+
+        for line in &self.lines {
+            writeln!(f, "{line}")?;
+        }
+
+        if let Some(name) = name {
+            writeln!(f, "{{end_of_{name}}}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ChordProSection {
+    pub fn new(kind: SectionKind, label: Option<String>, lines: Vec<ChordProLine>) -> ChordProSection {
+        ChordProSection { kind, label, lines }
+    }
+
+    pub fn get_kind(&self) -> &SectionKind {
+        &self.kind
+    }
+
+    pub fn get_label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn get_lines(&self) -> &[ChordProLine] {
+        &self.lines
+    }
+
+    pub fn get_lines_mut(&mut self) -> &mut Vec<ChordProLine> {
+        &mut self.lines
+    }
+
+    pub fn add_line(&mut self, line: ChordProLine) {
+        self.lines.push(line);
+    }
+}
@@ -0,0 +1,250 @@
+use std::fmt::Display;
+
+use crispii_errors::CrispiiError;
+
+use crate::chordpro::{parser, ChordProLine, ChordProSection, InlineChord, SectionKind};
+use crate::chords::{ChordQuality, ChordSymbol};
+use crate::keys::{Key, Mode};
+use crate::notes::{LetterNote, Octave};
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct ChordProSheet {
+    sections: Vec<ChordProSection>,
+}
+
+impl Display for ChordProSheet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.sections.iter().try_for_each(|section| write!(f, "{section}"))
+    }
+}
+
+impl ChordProSheet {
+    pub fn new(sections: Vec<ChordProSection>) -> ChordProSheet {
+        ChordProSheet { sections }
+    }
+
+    /// Reads a ChordPro file. Inline chords that cannot be read as chord symbols, such as [N.C.], are kept as text
+    pub fn try_parse(text: &str) -> Result<ChordProSheet, Box<dyn CrispiiError>> {
+        parser::try_parse_sheet(text)
+    }
+
+    pub fn get_sections(&self) -> &[ChordProSection] {
+        &self.sections
+    }
+
+    pub fn get_sections_mut(&mut self) -> &mut Vec<ChordProSection> {
+        &mut self.sections
+    }
+
+    pub fn add_section(&mut self, section: ChordProSection) {
+        self.sections.push(section);
+    }
+
+    /// The value of the first directive with the given (full) name
+    pub fn get_directive(&self, name: &str) -> Option<&str> {
+        self.get_lines().find_map(|line| match line {
+            ChordProLine::Directive(directive, Some(value)) if directive == name => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn get_title(&self) -> Option<&str> {
+        self.get_directive("title")
+    }
+
+    pub fn get_subtitle(&self) -> Option<&str> {
+        self.get_directive("subtitle")
+    }
+
+    pub fn get_artist(&self) -> Option<&str> {
+        self.get_directive("artist")
+    }
+
+    /// The (sounding) key from the first key directive, if it can be read
+    pub fn get_key(&self) -> Option<Key> {
+        self.get_directive("key").and_then(parse_key)
+    }
+
+    /// The capo position, which is 0 without a capo directive
+    pub fn get_capo(&self) -> u8 {
+        self.get_directive("capo").and_then(|capo| capo.trim().parse().ok()).unwrap_or(0)
+    }
+
+    /// Every inline chord that could be read as a chord symbol, in order
+    pub fn get_chords(&self) -> Vec<ChordSymbol> {
+        self.get_lines()
+            .filter_map(|line| match line {
+                ChordProLine::Lyrics(segments) => Some(segments),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|segment| match segment.get_chord() {
+                Some(InlineChord::Symbol(chord_symbol)) => Some(*chord_symbol),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Moves every chord so that the song is in the given key, updating any key directives to match. Without a key directive the song is
+    /// taken to be in the key its first chord sounds in once any capo is taken into account
+    pub fn try_transpose(&self, key: Key) -> Result<ChordProSheet, Box<dyn CrispiiError>> {
+        let Some(source) = self.get_sounding_key() else {
+            return Ok(self.clone());
+        };
+        let (letter_steps, semitones) = get_interval(source.get_tonic(), key.get_tonic());
+
+        self.try_map_chords(letter_steps, semitones, true)
+    }
+
+    /// Moves every chord up (or down) by a number of semitones, spelling them to suit the new key
+    pub fn try_transpose_by_semitones(&self, semitones: i8) -> Result<ChordProSheet, Box<dyn CrispiiError>> {
+        match self.get_sounding_key() {
+            Some(source) => self.try_transpose(source.transpose(semitones)),
+            None => Ok(self.clone()),
+        }
+    }
+
+    /// Rewrites the chords as the shapes played with the capo at the given fret, keeping the song sounding in the same key. A capo of 0 removes
+    /// the capo directive
+    pub fn try_set_capo(&self, capo: u8) -> Result<ChordProSheet, Box<dyn CrispiiError>> {
+        let current = self.get_capo();
+        let mut sheet = match self.get_chord_key() {
+            Some(chord_key) => {
+                let (letter_steps, semitones) = get_interval(chord_key.get_tonic(), chord_key.transpose(current as i8 - capo as i8).get_tonic());
+                self.try_map_chords(letter_steps, semitones, false)?
+            }
+            None => self.clone(),
+        };
+
+        sheet.set_directive("capo", (capo > 0).then(|| capo.to_string()));
+        Ok(sheet)
+    }
+
+    fn get_lines(&self) -> impl Iterator<Item = &ChordProLine> {
+        self.sections.iter().flat_map(|section| section.get_lines())
+    }
+
+    /// The key the song sounds in, which is the key directive, or failing that the key of the first chord moved up by the capo
+    fn get_sounding_key(&self) -> Option<Key> {
+        match self.get_key() {
+            Some(key) => Some(key),
+            None => self.get_chord_key().map(|chord_key| chord_key.transpose(self.get_capo() as i8)),
+        }
+    }
+
+    /// The key the chords are written in, which is the key directive moved down by the capo, or failing that the key of the first chord
+    fn get_chord_key(&self) -> Option<Key> {
+        match self.get_key() {
+            Some(key) => Some(key.transpose(-(self.get_capo() as i8))),
+            None => {
+                let first = *self.get_chords().first()?;
+                let mode = match first.get_quality() {
+                    ChordQuality::Minor | ChordQuality::MinorSixth | ChordQuality::MinorSeventh | ChordQuality::MinorMajorSeventh | ChordQuality::MinorNinth => Mode::Minor,
+                    _ => Mode::Major,
+                };
+
+                Key::try_new(first.get_root(), mode).ok()
+            }
+        }
+    }
+
+    fn try_map_chords(&self, letter_steps: i8, semitones: i16, include_key_directives: bool) -> Result<ChordProSheet, Box<dyn CrispiiError>> {
+        let mut sheet = self.clone();
+
+        for section in sheet.sections.iter_mut() {
+            let is_grid = *section.get_kind() == SectionKind::Grid;
+
+            for line in section.get_lines_mut().iter_mut() {
+                match line {
+                    ChordProLine::Lyrics(segments) => {
+                        for segment in segments.iter_mut() {
+                            match segment.get_chord() {
+                                Some(InlineChord::Symbol(chord_symbol)) => {
+                                    segment.set_chord(Some(InlineChord::Symbol(chord_symbol.try_transpose(letter_steps, semitones)?)));
+                                }
+                                Some(InlineChord::Text(text)) => {
+                                    segment.set_chord(Some(InlineChord::Text(parser::try_transpose_chord_text(text, letter_steps, semitones)?)));
+                                }
+                                None => (),
+                            }
+                        }
+                    }
+                    ChordProLine::Directive(name, Some(value)) if include_key_directives && name == "key" => {
+                        if let Ok(chord_symbol) = ChordSymbol::try_parse(value) {
+                            *value = chord_symbol.try_transpose(letter_steps, semitones)?.to_string();
+                        }
+                    }
+                    ChordProLine::Text(text) if is_grid => {
+                        let tokens = text.split(' ')
+                            .map(|token| match parser::parse_inline_chord(token) {
+                                InlineChord::Symbol(chord_symbol) => chord_symbol.try_transpose(letter_steps, semitones).map(|chord_symbol| chord_symbol.to_string()),
+                                InlineChord::Text(token) => parser::try_transpose_chord_text(&token, letter_steps, semitones),
+                            })
+                            .collect::<Result<Vec<String>, Box<dyn CrispiiError>>>()?;
+
+                        *text = tokens.join(" ");
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        Ok(sheet)
+    }
+
+    /// Replaces the value of every directive with this name, or adds one after the opening directives if there is none. A value of None
+    /// removes the directive
+    fn set_directive(&mut self, name: &str, value: Option<String>) {
+        let mut found = false;
+
+        for section in self.sections.iter_mut() {
+            section.get_lines_mut().retain_mut(|line| match line {
+                ChordProLine::Directive(directive, directive_value) if directive == name => {
+                    found = true;
+                    *directive_value = value.clone();
+                    value.is_some()
+                }
+                _ => true,
+            });
+        }
+
+        if found || value.is_none() {
+            return;
+        }
+
+        if self.sections.first().is_none_or(|section| *section.get_kind() != SectionKind::Unmarked) {
+            self.sections.insert(0, ChordProSection::default());
+        }
+
+        let lines = self.sections[0].get_lines_mut();
+        let index = lines.iter().position(|line| !matches!(line, ChordProLine::Directive(_, _))).unwrap_or(lines.len());
+
+        lines.insert(index, ChordProLine::Directive(name.to_string(), value));
+    }
+}
+
+/// Reads key directives such as "G", "Bb" or "F#m"
+fn parse_key(text: &str) -> Option<Key> {
+    let chord_symbol = ChordSymbol::try_parse(text).ok()?;
+    let mode = match chord_symbol.get_quality() {
+        ChordQuality::Minor => Mode::Minor,
+        ChordQuality::Major => Mode::Major,
+        _ => return None,
+    };
+
+    Key::try_new(chord_symbol.get_root(), mode).ok()
+}
+
+/// The number of letters and semitones from one note up (or down) to the nearest spelling of another
+fn get_interval(from: LetterNote, to: LetterNote) -> (i8, i16) {
+    let mut letter_steps = (to.get_letter_index() as i8 - from.get_letter_index() as i8).rem_euclid(7);
+
+    if letter_steps > 3 {
+        letter_steps -= 7;
+    }
+
+    let semitones = (to.with_octave(Octave::Four).get_pitch_number() - from.with_octave(Octave::Four).get_pitch_number()).rem_euclid(12);
+    let octaves = ((semitones as f64 - letter_steps as f64 * 12.0 / 7.0) / 12.0).round() as i16;
+
+    (letter_steps, semitones - 12 * octaves)
+}
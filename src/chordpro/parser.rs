@@ -0,0 +1,159 @@
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::chordpro::{ChordProLine, ChordProSection, ChordProSheet, InlineChord, LyricSegment, SectionKind};
+use crate::chords::ChordSymbol;
+use crate::notes::{LetterNote, Modifier, Octave};
+
+pub(crate) fn try_parse_sheet(text: &str) -> Result<ChordProSheet, Box<dyn CrispiiError>> {
+    let mut sections = Vec::new();
+    let mut current = ChordProSection::default();
+
+    for (line_index, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        let trimmed = line.trim_start();
+
+        if let Some((name, value)) = parse_directive(trimmed) {
+            if let Some(kind) = name.strip_prefix("start_of_") {
+                if *current.get_kind() != SectionKind::Unmarked {
+                    return Err(Box::new(InvalidArgumentError::new("text", format!("Line {}: {{{name}}} cannot start inside a {} section", line_index + 1, current.get_kind()).as_str())));
+                }
+
+                if !current.get_lines().is_empty() {
+                    sections.push(current);
+                }
+
+                current = ChordProSection::new(SectionKind::from_directive_name(kind), value.map(|label| parse_label(&label)), Vec::new());
+                continue;
+            }
+
+            if let Some(kind) = name.strip_prefix("end_of_") {
+                if current.get_kind().get_directive_name() != Some(kind) {
+                    return Err(Box::new(InvalidArgumentError::new("text", format!("Line {}: {{{name}}} does not close the open {} section", line_index + 1, current.get_kind()).as_str())));
+                }
+
+                sections.push(std::mem::take(&mut current));
+                continue;
+            }
+
+            current.add_line(ChordProLine::Directive(name, value));
+        } else if let Some(comment) = trimmed.strip_prefix('#') {
+            current.add_line(ChordProLine::Comment(comment.to_string()));
+        } else if matches!(current.get_kind(), SectionKind::Tab | SectionKind::Grid) {
+            current.add_line(ChordProLine::Text(line.to_string()));
+        } else if trimmed.is_empty() {
+            current.add_line(ChordProLine::Empty);
+        } else {
+            current.add_line(ChordProLine::Lyrics(parse_segments(line)));
+        }
+    }
+
+    // An unclosed section at the end of the file is taken to end there
+    if *current.get_kind() != SectionKind::Unmarked || !current.get_lines().is_empty() {
+        sections.push(current);
+    }
+
+    Ok(ChordProSheet::new(sections))
+}
+
+pub(crate) fn parse_inline_chord(text: &str) -> InlineChord {
+    match ChordSymbol::try_parse(text) {
+        Ok(chord_symbol) => InlineChord::Symbol(chord_symbol),
+        Err(_) => InlineChord::Text(text.to_string()),
+    }
+}
+
+/// Transposes a chord whose suffix is not a recognised quality, such as [C2] or [E7sus4], by moving its root and any /bass note and
+/// keeping the suffix as written. Text that does not start with a note letter, such as [N.C.] or [*Coda], is left alone
+pub(crate) fn try_transpose_chord_text(text: &str, letter_steps: i8, semitones: i16) -> Result<String, Box<dyn CrispiiError>> {
+    let Some((root, rest)) = parse_note_prefix(text) else {
+        return Ok(text.to_string());
+    };
+    // A slash only starts a bass note when a lone note follows it, so the slash in C6/9 stays part of the suffix
+    let (suffix, bass) = match rest.rsplit_once('/').map(|(suffix, bass)| (suffix, parse_note_prefix(bass))) {
+        Some((suffix, Some((bass, "")))) => (suffix, Some(bass)),
+        _ => (rest, None),
+    };
+
+    let root = root.try_offset(letter_steps, semitones)?;
+    let mut transposed = format!("{}{}{}", root.get_letter(), root.get_modifier().get_symbol(), suffix);
+
+    if let Some(bass) = bass {
+        let bass = bass.try_offset(letter_steps, semitones)?;
+        transposed.push_str(format!("/{}{}", bass.get_letter(), bass.get_modifier().get_symbol()).as_str());
+    }
+
+    Ok(transposed)
+}
+
+/// Reads a capital note letter and any sharps or flats from the start of the text, returning the note and the rest of the text
+fn parse_note_prefix(text: &str) -> Option<(LetterNote, &str)> {
+    let letter = text.chars().next().filter(|letter| ('A'..='G').contains(letter))?;
+    let note = LetterNote::try_from_letter(letter, Modifier::Default, Octave::Four).ok()?;
+    let rest = &text[letter.len_utf8()..];
+    let (modifier, length) = Modifier::parse_symbol_prefix(rest);
+
+    Some((note.with_modifier(modifier), &rest[length..]))
+}
+
+/// Splits a directive line into its (expanded) name and value
+fn parse_directive(line: &str) -> Option<(String, Option<String>)> {
+    let inner = line.strip_prefix('{')?.strip_suffix('}')?.trim();
+    let (name, value) = match inner.find(|character: char| character == ':' || character.is_whitespace()) {
+        Some(index) => (&inner[..index], inner[index + 1..].trim()),
+        None => (inner, ""),
+    };
+    let name = name.to_lowercase();
+    let name = match name.as_str() {
+        "t" => "title",
+        "st" => "subtitle",
+        "c" => "comment",
+        "ci" => "comment_italic",
+        "cb" => "comment_box",
+        "soc" => "start_of_chorus",
+        "eoc" => "end_of_chorus",
+        "sov" => "start_of_verse",
+        "eov" => "end_of_verse",
+        "sob" => "start_of_bridge",
+        "eob" => "end_of_bridge",
+        "sot" => "start_of_tab",
+        "eot" => "end_of_tab",
+        "sog" => "start_of_grid",
+        "eog" => "end_of_grid",
+        "ns" => "new_song",
+        "np" => "new_page",
+        "npp" => "new_physical_page",
+        "colb" => "column_break",
+        "col" => "columns",
+        name => name,
+    };
+
+    Some((name.to_string(), (!value.is_empty()).then(|| value.to_string())))
+}
+
+/// Section labels can be given bare or as label="..."
+fn parse_label(value: &str) -> String {
+    value.strip_prefix("label=").map(|label| label.trim_matches('"')).unwrap_or(value).to_string()
+}
+
+fn parse_segments(line: &str) -> Vec<LyricSegment> {
+    let mut segments = Vec::new();
+    let mut chord = None;
+    let mut rest = line;
+
+    while let Some((start, end)) = rest.find('[').and_then(|start| rest[start..].find(']').map(|length| (start, start + length))) {
+        let text = &rest[..start];
+
+        if chord.is_some() || !text.is_empty() {
+            segments.push(LyricSegment::new(chord.take(), text));
+        }
+
+        chord = Some(parse_inline_chord(&rest[start + 1..end]));
+        rest = &rest[end + 1..];
+    }
+
+    if chord.is_some() || !rest.is_empty() {
+        segments.push(LyricSegment::new(chord, rest));
+    }
+
+    segments
+}
@@ -0,0 +1,54 @@
+use std::fmt::Display;
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum SectionKind {
+    /// Lines outside any start_of/end_of pair
+    #[default]
+    Unmarked,
+    Verse,
+    Chorus,
+    Bridge,
+    Tab,
+    Grid,
+    Custom(String),
+}
+
+impl Display for SectionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SectionKind::Unmarked => write!(f, "Unmarked"),
+            SectionKind::Verse => write!(f, "Verse"),
+            SectionKind::Chorus => write!(f, "Chorus"),
+            SectionKind::Bridge => write!(f, "Bridge"),
+            SectionKind::Tab => write!(f, "Tab"),
+            SectionKind::Grid => write!(f, "Grid"),
+            SectionKind::Custom(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+impl SectionKind {
+    /// The name used in the section's start_of_ and end_of_ directives
+    pub fn get_directive_name(&self) -> Option<&str> {
+        match self {
+            SectionKind::Unmarked => None,
+            SectionKind::Verse => Some("verse"),
+            SectionKind::Chorus => Some("chorus"),
+            SectionKind::Bridge => Some("bridge"),
+            SectionKind::Tab => Some("tab"),
+            SectionKind::Grid => Some("grid"),
+            SectionKind::Custom(name) => Some(name),
+        }
+    }
+
+    pub fn from_directive_name(name: &str) -> SectionKind {
+        match name {
+            "verse" => SectionKind::Verse,
+            "chorus" => SectionKind::Chorus,
+            "bridge" => SectionKind::Bridge,
+            "tab" => SectionKind::Tab,
+            "grid" => SectionKind::Grid,
+            _ => SectionKind::Custom(name.to_string()),
+        }
+    }
+}
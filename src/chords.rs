@@ -0,0 +1,8 @@
+mod chord_quality;
+pub use chord_quality::ChordQuality;
+
+mod chord_symbol;
+pub use chord_symbol::ChordSymbol;
+
+mod relative_chord;
+pub use relative_chord::RelativeChord;
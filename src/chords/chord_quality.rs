@@ -0,0 +1,135 @@
+use std::fmt::Display;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum ChordQuality {
+    #[default]
+    Major,
+    Minor,
+    Diminished,
+    Augmented,
+    SuspendedSecond,
+    SuspendedFourth,
+    Power,
+    MajorSixth,
+    MinorSixth,
+    DominantSeventh,
+    MajorSeventh,
+    MinorSeventh,
+    MinorMajorSeventh,
+    HalfDiminishedSeventh,
+    DiminishedSeventh,
+    AugmentedSeventh,
+    DominantNinth,
+    MajorNinth,
+    MinorNinth,
+    AddNine,
+}
+
+impl Display for ChordQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChordQuality::Major => write!(f, "Major"),
+            ChordQuality::Minor => write!(f, "Minor"),
+            ChordQuality::Diminished => write!(f, "Diminished"),
+            ChordQuality::Augmented => write!(f, "Augmented"),
+            ChordQuality::SuspendedSecond => write!(f, "Suspended Second"),
+            ChordQuality::SuspendedFourth => write!(f, "Suspended Fourth"),
+            ChordQuality::Power => write!(f, "Power"),
+            ChordQuality::MajorSixth => write!(f, "Major Sixth"),
+            ChordQuality::MinorSixth => write!(f, "Minor Sixth"),
+            ChordQuality::DominantSeventh => write!(f, "Dominant Seventh"),
+            ChordQuality::MajorSeventh => write!(f, "Major Seventh"),
+            ChordQuality::MinorSeventh => write!(f, "Minor Seventh"),
+            ChordQuality::MinorMajorSeventh => write!(f, "Minor Major Seventh"),
+            ChordQuality::HalfDiminishedSeventh => write!(f, "Half Diminished Seventh"),
+            ChordQuality::DiminishedSeventh => write!(f, "Diminished Seventh"),
+            ChordQuality::AugmentedSeventh => write!(f, "Augmented Seventh"),
+            ChordQuality::DominantNinth => write!(f, "Dominant Ninth"),
+            ChordQuality::MajorNinth => write!(f, "Major Ninth"),
+            ChordQuality::MinorNinth => write!(f, "Minor Ninth"),
+            ChordQuality::AddNine => write!(f, "Add Nine"),
+        }
+    }
+}
+
+impl ChordQuality {
+    pub const ALL: [ChordQuality; 20] = [
+        ChordQuality::Major,
+        ChordQuality::Minor,
+        ChordQuality::Diminished,
+        ChordQuality::Augmented,
+        ChordQuality::SuspendedSecond,
+        ChordQuality::SuspendedFourth,
+        ChordQuality::Power,
+        ChordQuality::MajorSixth,
+        ChordQuality::MinorSixth,
+        ChordQuality::DominantSeventh,
+        ChordQuality::MajorSeventh,
+        ChordQuality::MinorSeventh,
+        ChordQuality::MinorMajorSeventh,
+        ChordQuality::HalfDiminishedSeventh,
+        ChordQuality::DiminishedSeventh,
+        ChordQuality::AugmentedSeventh,
+        ChordQuality::DominantNinth,
+        ChordQuality::MajorNinth,
+        ChordQuality::MinorNinth,
+        ChordQuality::AddNine,
+    ];
+
+    /// The chord tones above the root as (letter steps, semitones) pairs, starting with the root itself
+    pub fn get_tones(&self) -> &'static [(i8, i16)] {
+        match self {
+            ChordQuality::Major => &[(0, 0), (2, 4), (4, 7)],
+            ChordQuality::Minor => &[(0, 0), (2, 3), (4, 7)],
+            ChordQuality::Diminished => &[(0, 0), (2, 3), (4, 6)],
+            ChordQuality::Augmented => &[(0, 0), (2, 4), (4, 8)],
+            ChordQuality::SuspendedSecond => &[(0, 0), (1, 2), (4, 7)],
+            ChordQuality::SuspendedFourth => &[(0, 0), (3, 5), (4, 7)],
+            ChordQuality::Power => &[(0, 0), (4, 7)],
+            ChordQuality::MajorSixth => &[(0, 0), (2, 4), (4, 7), (5, 9)],
+            ChordQuality::MinorSixth => &[(0, 0), (2, 3), (4, 7), (5, 9)],
+            ChordQuality::DominantSeventh => &[(0, 0), (2, 4), (4, 7), (6, 10)],
+            ChordQuality::MajorSeventh => &[(0, 0), (2, 4), (4, 7), (6, 11)],
+            ChordQuality::MinorSeventh => &[(0, 0), (2, 3), (4, 7), (6, 10)],
+            ChordQuality::MinorMajorSeventh => &[(0, 0), (2, 3), (4, 7), (6, 11)],
+            ChordQuality::HalfDiminishedSeventh => &[(0, 0), (2, 3), (4, 6), (6, 10)],
+            ChordQuality::DiminishedSeventh => &[(0, 0), (2, 3), (4, 6), (6, 9)],
+            ChordQuality::AugmentedSeventh => &[(0, 0), (2, 4), (4, 8), (6, 10)],
+            ChordQuality::DominantNinth => &[(0, 0), (2, 4), (4, 7), (6, 10), (8, 14)],
+            ChordQuality::MajorNinth => &[(0, 0), (2, 4), (4, 7), (6, 11), (8, 14)],
+            ChordQuality::MinorNinth => &[(0, 0), (2, 3), (4, 7), (6, 10), (8, 14)],
+            ChordQuality::AddNine => &[(0, 0), (2, 4), (4, 7), (8, 14)],
+        }
+    }
+
+    /// The suffix written after the root in a chord symbol, such as the "m7" of "Am7"
+    pub fn get_symbol(&self) -> &'static str {
+        self.get_symbol_aliases()[0]
+    }
+
+    /// Every suffix that is read as this quality, with the preferred one first
+    pub fn get_symbol_aliases(&self) -> &'static [&'static str] {
+        match self {
+            ChordQuality::Major => &["", "maj", "M"],
+            ChordQuality::Minor => &["m", "min", "-"],
+            ChordQuality::Diminished => &["dim", "°", "o"],
+            ChordQuality::Augmented => &["aug", "+"],
+            ChordQuality::SuspendedSecond => &["sus2"],
+            ChordQuality::SuspendedFourth => &["sus4", "sus"],
+            ChordQuality::Power => &["5"],
+            ChordQuality::MajorSixth => &["6", "maj6", "M6"],
+            ChordQuality::MinorSixth => &["m6", "min6", "-6"],
+            ChordQuality::DominantSeventh => &["7", "dom7"],
+            ChordQuality::MajorSeventh => &["maj7", "M7", "Δ7", "Δ"],
+            ChordQuality::MinorSeventh => &["m7", "min7", "-7"],
+            ChordQuality::MinorMajorSeventh => &["mMaj7", "mM7", "m(maj7)", "minMaj7", "-Δ7"],
+            ChordQuality::HalfDiminishedSeventh => &["m7b5", "ø7", "ø", "min7b5", "-7b5"],
+            ChordQuality::DiminishedSeventh => &["dim7", "°7", "o7"],
+            ChordQuality::AugmentedSeventh => &["aug7", "+7", "7#5"],
+            ChordQuality::DominantNinth => &["9", "dom9"],
+            ChordQuality::MajorNinth => &["maj9", "M9", "Δ9"],
+            ChordQuality::MinorNinth => &["m9", "min9", "-9"],
+            ChordQuality::AddNine => &["add9", "add2"],
+        }
+    }
+}
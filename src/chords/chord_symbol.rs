@@ -0,0 +1,122 @@
+use std::fmt::Display;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::chords::ChordQuality;
+use crate::notes::{LetterNote, Modifier, Octave};
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct ChordSymbol {
+    root: LetterNote,
+    quality: ChordQuality,
+    bass: Option<LetterNote>,
+}
+
+impl Display for ChordSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", get_name(self.root), self.quality.get_symbol())?;
+
+        match self.bass {
+            Some(bass) => write!(f, "/{}", get_name(bass)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl ChordSymbol {
+    /// The octaves of the root and bass are ignored
+    pub fn new(root: LetterNote, quality: ChordQuality, bass: Option<LetterNote>) -> ChordSymbol {
+        ChordSymbol {
+            root: root.with_octave(Octave::Four),
+            quality,
+            bass: bass.map(|bass| bass.with_octave(Octave::Three)),
+        }
+    }
+
+    /// Reads symbols such as "C", "F#m7", "Bbmaj7/D" or "Ebm7b5"
+    pub fn try_parse(text: &str) -> Result<ChordSymbol, Box<dyn CrispiiError>> {
+        let text = text.trim();
+        let (chord, bass) = match text.rsplit_once('/') {
+            Some((chord, bass)) if !bass.is_empty() && bass.chars().next().is_some_and(|letter| "ABCDEFG".contains(letter)) => (chord, Some(bass)),
+            _ => (text, None),
+        };
+
+        let (root, suffix) = try_parse_note_prefix(chord)?;
+        let quality = ChordQuality::ALL.iter()
+            .flat_map(|quality| quality.get_symbol_aliases().iter().map(move |alias| (*quality, *alias)))
+            .filter(|(_, alias)| suffix == *alias || suffix.strip_prefix(alias).is_some_and(|rest| rest.chars().all(|character| character == ' ')))
+            .max_by_key(|(_, alias)| alias.len())
+            .map(|(quality, _)| quality)
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("'{suffix}' is not a recognised chord quality in '{text}'").as_str())) })?;
+
+        let bass = match bass {
+            Some(bass) => {
+                let (bass, rest) = try_parse_note_prefix(bass)?;
+
+                if !rest.trim().is_empty() {
+                    return Err(Box::new(InvalidArgumentError::new("text", format!("Unexpected '{rest}' after the bass note in '{text}'").as_str())));
+                }
+
+                Some(bass)
+            }
+            None => None,
+        };
+
+        Ok(ChordSymbol::new(root, quality, bass))
+    }
+
+    pub fn get_root(&self) -> LetterNote {
+        self.root
+    }
+
+    pub fn get_quality(&self) -> ChordQuality {
+        self.quality
+    }
+
+    pub fn get_bass(&self) -> Option<LetterNote> {
+        self.bass
+    }
+
+    /// Moves the root and bass up (or down) by a number of letters, choosing modifiers that land them the given number of semitones away, so
+    /// transposing by (1, 2) turns Bb/F into C/G
+    pub fn try_transpose(&self, letter_steps: i8, semitones: i16) -> Result<ChordSymbol, Box<dyn CrispiiError>> {
+        let root = self.root.try_offset(letter_steps, semitones)?;
+        let bass = self.bass.map(|bass| bass.try_offset(letter_steps, semitones)).transpose()?;
+
+        Ok(ChordSymbol::new(root, self.quality, bass))
+    }
+
+    /// The chord tones stacked up from the root in octave four, with any separate bass note placed below in octave three
+    pub fn try_get_notes(&self) -> Result<Vec<LetterNote>, Box<dyn CrispiiError>> {
+        let mut notes = Vec::new();
+
+        if let Some(bass) = self.bass {
+            notes.push(bass);
+        }
+
+        for (letter_steps, semitones) in self.quality.get_tones() {
+            notes.push(self.root.try_offset(*letter_steps, *semitones)?);
+        }
+
+        Ok(notes)
+    }
+}
+
+fn get_name(note: LetterNote) -> String {
+    format!("{}{}", note.get_letter(), note.get_modifier().get_symbol())
+}
+
+fn try_parse_note_prefix(text: &str) -> Result<(LetterNote, &str), Box<dyn CrispiiError>> {
+    let letter = text.chars().next()
+        .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", "Chord symbols must start with a root note")) })?;
+    let note = LetterNote::try_from_letter(letter, Modifier::Default, Octave::Four)?;
+
+    if !letter.is_ascii_uppercase() {
+        return Err(Box::new(InvalidArgumentError::new("text", format!("Chord roots must be capital letters, not '{letter}'").as_str())));
+    }
+
+    let rest = &text[letter.len_utf8()..];
+    let (modifier, length) = Modifier::parse_symbol_prefix(rest);
+
+    Ok((note.with_modifier(modifier), &rest[length..]))
+}
@@ -0,0 +1,112 @@
+use std::fmt::Display;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::chords::{ChordQuality, ChordSymbol};
+use crate::keys::Key;
+use crate::notes::{Modifier, NumberNote, Octave};
+
+/// A chord described by the degrees of its root and bass rather than their letters, so the same progression reads alike in every key
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct RelativeChord {
+    root: NumberNote,
+    quality: ChordQuality,
+    bass: Option<NumberNote>,
+}
+
+impl Display for RelativeChord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", get_name(self.root), self.quality.get_symbol())?;
+
+        match self.bass {
+            Some(bass) => write!(f, "/{}", get_name(bass)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl RelativeChord {
+    /// The octaves of the root and bass are ignored
+    pub fn new(root: NumberNote, quality: ChordQuality, bass: Option<NumberNote>) -> RelativeChord {
+        RelativeChord {
+            root: NumberNote::from_degree(root.get_degree(), root.get_modifier(), Octave::Four),
+            quality,
+            bass: bass.map(|bass| NumberNote::from_degree(bass.get_degree(), bass.get_modifier(), Octave::Four)),
+        }
+    }
+
+    /// Reads chords written as a degree with any modifier in front and a chord symbol suffix after, such as "1", "b7maj7", "#4dim7" or "5/7"
+    pub fn try_parse(text: &str) -> Result<RelativeChord, Box<dyn CrispiiError>> {
+        let text = text.trim();
+        let (chord, bass) = match text.rsplit_once('/') {
+            Some((chord, bass)) if !bass.is_empty() => (chord, Some(bass)),
+            _ => (text, None),
+        };
+
+        let (root, suffix) = try_parse_degree_prefix(chord)?;
+        let quality = ChordQuality::ALL.iter()
+            .flat_map(|quality| quality.get_symbol_aliases().iter().map(move |alias| (*quality, *alias)))
+            .filter(|(_, alias)| suffix == *alias)
+            .map(|(quality, _)| quality)
+            .next()
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("'{suffix}' is not a recognised chord quality in '{text}'").as_str())) })?;
+
+        let bass = match bass {
+            Some(bass) => {
+                let (bass, rest) = try_parse_degree_prefix(bass)?;
+
+                if !rest.is_empty() {
+                    return Err(Box::new(InvalidArgumentError::new("text", format!("Unexpected '{rest}' after the bass degree in '{text}'").as_str())));
+                }
+
+                Some(bass)
+            }
+            None => None,
+        };
+
+        Ok(RelativeChord::new(root, quality, bass))
+    }
+
+    /// Describes a chord symbol by its degrees in the given key
+    pub fn try_from_chord_symbol(chord_symbol: ChordSymbol, key: Key) -> Result<RelativeChord, Box<dyn CrispiiError>> {
+        let root = key.try_get_number_note(chord_symbol.get_root())?;
+        let bass = chord_symbol.get_bass().map(|bass| key.try_get_number_note(bass)).transpose()?;
+
+        Ok(RelativeChord::new(root, chord_symbol.get_quality(), bass))
+    }
+
+    pub fn get_root(&self) -> NumberNote {
+        self.root
+    }
+
+    pub fn get_quality(&self) -> ChordQuality {
+        self.quality
+    }
+
+    pub fn get_bass(&self) -> Option<NumberNote> {
+        self.bass
+    }
+
+    /// Spells the chord in the given key
+    pub fn try_to_chord_symbol(&self, key: Key) -> Result<ChordSymbol, Box<dyn CrispiiError>> {
+        let root = key.try_get_letter_note(self.root)?;
+        let bass = self.bass.map(|bass| key.try_get_letter_note(bass)).transpose()?;
+
+        Ok(ChordSymbol::new(root, self.quality, bass))
+    }
+}
+
+fn get_name(number_note: NumberNote) -> String {
+    format!("{}{}", number_note.get_modifier().get_symbol(), number_note.get_degree())
+}
+
+fn try_parse_degree_prefix(text: &str) -> Result<(NumberNote, &str), Box<dyn CrispiiError>> {
+    let (modifier, length) = Modifier::parse_symbol_prefix(text);
+    let rest = &text[length..];
+    let degree = rest.chars().next()
+        .and_then(|digit| digit.to_digit(10))
+        .filter(|degree| (1..=7).contains(degree))
+        .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("Expected a degree from 1 to 7 in '{text}'").as_str())) })?;
+
+    Ok((NumberNote::from_degree(degree as u8, modifier, Octave::Four), &rest[1..]))
+}
@@ -0,0 +1,19 @@
+mod fret_position;
+pub use fret_position::FretPosition;
+
+mod tuning;
+pub use tuning::Tuning;
+
+mod chord_shape;
+pub use chord_shape::ChordShape;
+
+mod fretboard;
+pub use fretboard::Fretboard;
+
+mod tab_event;
+pub use tab_event::TabEvent;
+
+mod tablature;
+pub use tablature::Tablature;
+
+mod ascii_tab;
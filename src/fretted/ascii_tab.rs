@@ -0,0 +1,181 @@
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::fretted::{FretPosition, TabEvent, Tablature, Tuning};
+use crate::rhythm::{Duration, NoteValue};
+
+const LINE_WIDTH: usize = 80;
+const REST_SYMBOL: char = 'r';
+
+pub(crate) fn write_tablature(tablature: &Tablature) -> String {
+    let tuning = tablature.get_tuning();
+    let labels = get_labels(tuning);
+    let label_width = labels.iter().map(|label| label.chars().count()).max().unwrap_or(0);
+    let string_count = tuning.get_string_count() as usize;
+    let measure_duration = tablature.get_time_signature().map(|time_signature| time_signature.get_measure_duration());
+    let mut systems: Vec<Vec<String>> = Vec::new();
+    let mut lines = vec![String::new(); string_count];
+    let mut position = Duration::zero();
+
+    let events = tablature.get_events();
+
+    for (index, event) in events.iter().enumerate() {
+        let frets: Vec<Option<String>> = (1..=string_count as u8)
+            .map(|string| event.get_positions().iter().find(|position| position.get_string() == string).map(|position| position.get_fret().to_string()))
+            .collect();
+        // Digits after the first of a fret are padding rather than time, so every event takes exactly its own number of columns
+        let fret_width = frets.iter().flatten().map(String::len).max().unwrap_or(1);
+        let columns = get_columns(event.get_duration());
+
+        for (line, fret) in lines.iter_mut().zip(&frets) {
+            let fret = match (fret, event.is_rest()) {
+                (_, true) => REST_SYMBOL.to_string(),
+                (Some(fret), false) => fret.clone(),
+                (None, false) => String::new(),
+            };
+            line.push_str(&fret);
+            line.push_str(&"-".repeat(fret_width + columns - 1 - fret.len()));
+        }
+
+        position = position + event.get_duration();
+
+        if let Some(measure_duration) = measure_duration {
+            if position.multiply(measure_duration.get_denominator(), measure_duration.get_numerator()).get_denominator() == 1 {
+                lines.iter_mut().for_each(|line| line.push('|'));
+
+                if lines[0].len() + label_width + 1 >= LINE_WIDTH {
+                    systems.push(std::mem::replace(&mut lines, vec![String::new(); string_count]));
+                }
+            }
+        } else if lines[0].len() + label_width + 1 >= LINE_WIDTH {
+            lines.iter_mut().for_each(|line| line.push('|'));
+            systems.push(std::mem::replace(&mut lines, vec![String::new(); string_count]));
+        }
+
+        // A one column note runs straight into the next one, so a fret followed by another on the same string is kept apart with a
+        // column of spaces, which takes no time
+        let runs_together = events.get(index + 1).is_some_and(|next| {
+            next.get_positions().iter().any(|next_position| event.get_positions().iter().any(|position| position.get_string() == next_position.get_string()))
+        });
+
+        if columns == 1 && runs_together && !lines[0].is_empty() && !lines[0].ends_with('|') {
+            lines.iter_mut().for_each(|line| line.push(' '));
+        }
+    }
+
+    if !lines[0].is_empty() || systems.is_empty() {
+        if !lines[0].ends_with('|') {
+            lines.iter_mut().for_each(|line| line.push('|'));
+        }
+
+        systems.push(lines);
+    }
+
+    systems.iter()
+        .map(|system| {
+            system.iter()
+                .zip(&labels)
+                .map(|(line, label)| format!("{label:<label_width$}|{line}\n"))
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub(crate) fn try_parse_tablature(text: &str, tuning: Tuning) -> Result<Tablature, Box<dyn CrispiiError>> {
+    let string_count = tuning.get_string_count() as usize;
+    let mut systems: Vec<Vec<&str>> = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+
+        match line.split_once('|') {
+            Some((label, content)) if label.chars().count() <= 3 && content.contains(['-', REST_SYMBOL]) => current.push(content),
+            _ if !current.is_empty() => systems.push(std::mem::take(&mut current)),
+            _ => (),
+        }
+    }
+
+    if !current.is_empty() {
+        systems.push(current);
+    }
+
+    let mut onsets: Vec<(u32, Option<Vec<FretPosition>>)> = Vec::new();
+    let mut time = 0u32;
+
+    for (system_index, system) in systems.iter().enumerate() {
+        if system.len() != string_count {
+            return Err(Box::new(InvalidArgumentError::new("text", format!("System {} has {} lines but the tuning has {string_count} strings", system_index + 1, system.len()).as_str())));
+        }
+
+        let rows: Vec<Vec<char>> = system.iter().map(|line| line.chars().collect()).collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+        for column in 0..width {
+            if rows.iter().all(|row| row.get(column).is_none_or(|character| *character == '|' || *character == ' ')) {
+                continue;
+            }
+
+            let is_digit_at = |row: &Vec<char>, column: usize| row.get(column).is_some_and(char::is_ascii_digit);
+            let is_padding = column > 0 && rows.iter().any(|row| is_digit_at(row, column) && is_digit_at(row, column - 1));
+            let positions: Vec<FretPosition> = rows.iter()
+                .enumerate()
+                .filter(|(_, row)| is_digit_at(row, column) && (column == 0 || !is_digit_at(row, column - 1)))
+                .map(|(string_index, row)| {
+                    let fret: String = row[column..].iter().take_while(|character| character.is_ascii_digit()).collect();
+
+                    fret.parse::<u8>()
+                        .map(|fret| FretPosition::new(string_index as u8 + 1, fret))
+                        .map_err(|_| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("'{fret}' is not a valid fret").as_str())) })
+                })
+                .collect::<Result<Vec<FretPosition>, Box<dyn CrispiiError>>>()?;
+
+            match onsets.last_mut() {
+                // Frets starting among the padding of a longer one are struck with it
+                Some((_, Some(last_positions))) if is_padding => last_positions.extend(positions),
+                _ if !positions.is_empty() => onsets.push((time, Some(positions))),
+                _ if rows.iter().any(|row| row.get(column) == Some(&REST_SYMBOL)) => onsets.push((time, None)),
+                _ => (),
+            }
+
+            if !is_padding {
+                time += 1;
+            }
+        }
+    }
+
+    let mut tablature = Tablature::new(tuning, None);
+
+    if let Some((first, _)) = onsets.first().filter(|(first, _)| *first > 0) {
+        tablature.add_event(TabEvent::new_rest(NoteValue::ThirtySecond.get_duration().try_multiply(*first, 1)?));
+    }
+
+    for (index, (start, positions)) in onsets.iter().enumerate() {
+        let end = onsets.get(index + 1).map(|(next, _)| *next).unwrap_or(time);
+        let duration = NoteValue::ThirtySecond.get_duration().try_multiply(end - start, 1)?;
+
+        match positions {
+            Some(positions) => tablature.add_event(TabEvent::new(positions.clone(), duration)),
+            None => tablature.add_event(TabEvent::new_rest(duration)),
+        }
+    }
+
+    Ok(tablature)
+}
+
+/// Each string is named after its open note, with the highest string in lower case when it shares a name with a lower one (so e B G D A E)
+fn get_labels(tuning: &Tuning) -> Vec<String> {
+    let names: Vec<String> = tuning.get_strings().iter().map(|note| format!("{}{}", note.get_letter(), note.get_modifier().get_symbol())).collect();
+
+    names.iter()
+        .enumerate()
+        .map(|(index, name)| match names[index + 1..].contains(name) {
+            true => name.to_lowercase(),
+            false => name.clone(),
+        })
+        .collect()
+}
+
+fn get_columns(duration: Duration) -> usize {
+    (duration.get_quarter_notes() * 8.0).round().max(1.0) as usize
+}
@@ -0,0 +1,56 @@
+use std::fmt::Display;
+
+use crate::fretted::FretPosition;
+
+/// The fret played on each string (None for a muted string), listed from string 1 (the highest) down but displayed from the lowest string up,
+/// so an open C major chord on guitar is x32010
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct ChordShape {
+    frets: Vec<Option<u8>>,
+}
+
+impl Display for ChordShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let frets: Vec<String> = self.frets.iter().rev().map(|fret| fret.map(|fret| fret.to_string()).unwrap_or("x".to_string())).collect();
+        let separator = if self.frets.iter().flatten().any(|fret| *fret > 9) { "-" } else { "" };
+
+        write!(f, "{}", frets.join(separator))
+    }
+}
+
+impl ChordShape {
+    pub fn new(frets: Vec<Option<u8>>) -> ChordShape {
+        ChordShape { frets }
+    }
+
+    pub fn get_frets(&self) -> &[Option<u8>] {
+        &self.frets
+    }
+
+    /// The positions of the strings that are played
+    pub fn get_positions(&self) -> Vec<FretPosition> {
+        self.frets.iter()
+            .enumerate()
+            .filter_map(|(index, fret)| fret.map(|fret| FretPosition::new(index as u8 + 1, fret)))
+            .collect()
+    }
+
+    pub fn get_muted_count(&self) -> usize {
+        self.frets.iter().filter(|fret| fret.is_none()).count()
+    }
+
+    /// The lowest fret stopped by a finger, ignoring open strings (and any capo, which counts as open)
+    pub fn get_lowest_fret(&self, capo: u8) -> Option<u8> {
+        self.frets.iter().flatten().copied().filter(|fret| *fret > capo).min()
+    }
+
+    /// How many frets the fingers have to cover, which is 1 when they all sit on the same fret
+    pub fn get_span(&self, capo: u8) -> u8 {
+        let fretted = self.frets.iter().flatten().copied().filter(|fret| *fret > capo);
+
+        match (fretted.clone().min(), fretted.max()) {
+            (Some(lowest), Some(highest)) => highest - lowest + 1,
+            _ => 0,
+        }
+    }
+}
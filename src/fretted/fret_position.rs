@@ -0,0 +1,32 @@
+use std::fmt::Display;
+
+/// A string and fret, where string 1 is the highest sounding string and fret 0 is the open string
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct FretPosition {
+    string: u8,
+    fret: u8,
+}
+
+impl Display for FretPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "String {} Fret {}", self.string, self.fret)
+    }
+}
+
+impl FretPosition {
+    pub fn new(string: u8, fret: u8) -> FretPosition {
+        FretPosition { string, fret }
+    }
+
+    pub fn get_string(&self) -> u8 {
+        self.string
+    }
+
+    pub fn get_fret(&self) -> u8 {
+        self.fret
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.fret == 0
+    }
+}
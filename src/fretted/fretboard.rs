@@ -0,0 +1,172 @@
+use std::collections::BTreeSet;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::chords::ChordSymbol;
+use crate::fretted::{ChordShape, FretPosition, Tuning};
+use crate::keys::Key;
+use crate::notes::LetterNote;
+
+const MAX_FINGERS: usize = 4;
+
+/// A tuning laid out over a number of frets, optionally with a capo. Frets are always counted from the nut, so with a capo on fret 2 the
+/// open strings sound at fret 2 and nothing below it can be played
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Fretboard {
+    tuning: Tuning,
+    capo: u8,
+    fret_count: u8,
+}
+
+impl Default for Fretboard {
+    fn default() -> Self {
+        Fretboard::new(Tuning::guitar(), 20)
+    }
+}
+
+impl Fretboard {
+    pub fn new(tuning: Tuning, fret_count: u8) -> Fretboard {
+        Fretboard {
+            tuning,
+            capo: 0,
+            fret_count,
+        }
+    }
+
+    pub fn get_tuning(&self) -> &Tuning {
+        &self.tuning
+    }
+
+    /// The fret the capo is on, which is 0 without one
+    pub fn get_capo(&self) -> u8 {
+        self.capo
+    }
+
+    pub fn get_fret_count(&self) -> u8 {
+        self.fret_count
+    }
+
+    pub fn try_set_capo(&mut self, capo: u8) -> Result<(), Box<dyn CrispiiError>> {
+        if capo >= self.fret_count {
+            return Err(Box::new(InvalidArgumentError::new("capo", format!("Must be below the last fret ({})", self.fret_count).as_str())));
+        }
+
+        self.capo = capo;
+        Ok(())
+    }
+
+    /// The MIDI style pitch number (60 is middle C) sounding at a position
+    pub fn try_get_pitch_number(&self, position: FretPosition) -> Result<i16, Box<dyn CrispiiError>> {
+        if !(self.capo..=self.fret_count).contains(&position.get_fret()) {
+            return Err(Box::new(InvalidArgumentError::new("position", format!("The fret must be between {} and {}", self.capo, self.fret_count).as_str())));
+        }
+
+        self.tuning.try_get_pitch_number(position)
+    }
+
+    /// The note sounding at a position, spelt to suit the given key
+    pub fn try_get_note(&self, position: FretPosition, key: Key) -> Result<LetterNote, Box<dyn CrispiiError>> {
+        key.try_spell(self.try_get_pitch_number(position)?)
+    }
+
+    /// Every playable note on a string, from the capo (or open string) up to the last fret
+    pub fn try_get_string_notes(&self, string: u8, key: Key) -> Result<Vec<LetterNote>, Box<dyn CrispiiError>> {
+        (self.capo..=self.fret_count).map(|fret| self.try_get_note(FretPosition::new(string, fret), key)).collect()
+    }
+
+    /// Every playable position of the note, ignoring spelling, so C# and Db are found in the same places
+    pub fn get_positions(&self, note: LetterNote) -> Vec<FretPosition> {
+        self.tuning.get_positions(note, self.fret_count).into_iter().filter(|position| position.get_fret() >= self.capo).collect()
+    }
+
+    /// Searches for shapes that sound every tone of the chord (the fifth may be left out of chords with four or more tones) with the root, or
+    /// the bass of a slash chord, lowest. The fingers must fit within the given number of frets and a barre counts as one finger. The easiest
+    /// shapes come first: fewest muted strings inside the chord, then nearest the nut, then fewest muted strings
+    pub fn find_chord_shapes(&self, chord_symbol: ChordSymbol, max_span: u8) -> Result<Vec<ChordShape>, Box<dyn CrispiiError>> {
+        let notes = chord_symbol.try_get_notes()?;
+        let bass = get_pitch_class(chord_symbol.get_bass().unwrap_or(chord_symbol.get_root()));
+        let root = get_pitch_class(chord_symbol.get_root());
+        let allowed: BTreeSet<i16> = notes.iter().map(|note| get_pitch_class(*note)).collect();
+        let tone_count = notes.len() - chord_symbol.get_bass().map_or(0, |_| 1);
+        let required: BTreeSet<i16> = allowed.iter().copied().filter(|pitch_class| tone_count < 4 || *pitch_class != (root + 7) % 12 || *pitch_class == bass).collect();
+        let open_strings: Vec<i16> = self.tuning.get_strings().iter().map(|note| note.get_pitch_number() + self.capo as i16).collect();
+        let mut shapes = BTreeSet::new();
+
+        for window_start in self.capo + 1..=self.fret_count.max(self.capo + 1) {
+            let window_end = (window_start as u16 + max_span as u16).saturating_sub(1).min(self.fret_count as u16) as u8;
+            let options: Vec<Vec<Option<u8>>> = open_strings.iter()
+                .map(|open| {
+                    let mut options = vec![None];
+
+                    if allowed.contains(&open.rem_euclid(12)) {
+                        options.push(Some(self.capo));
+                    }
+
+                    options.extend((window_start..=window_end).filter(|fret| allowed.contains(&(open + (*fret - self.capo) as i16).rem_euclid(12))).map(Some));
+                    options
+                })
+                .collect();
+
+            let mut frets = Vec::with_capacity(open_strings.len());
+            self.search_shapes(&options, &open_strings, &mut frets, &required, bass, &mut shapes);
+        }
+
+        let mut shapes: Vec<ChordShape> = shapes.into_iter().collect();
+
+        shapes.sort_by_key(|shape| (get_inner_muted_count(shape), shape.get_lowest_fret(self.capo).unwrap_or(0), shape.get_muted_count(), shape.get_span(self.capo)));
+        Ok(shapes)
+    }
+
+    fn search_shapes(&self, options: &[Vec<Option<u8>>], open_strings: &[i16], frets: &mut Vec<Option<u8>>, required: &BTreeSet<i16>, bass: i16, shapes: &mut BTreeSet<ChordShape>) {
+        if get_finger_count(frets, self.capo) > MAX_FINGERS {
+            return;
+        }
+
+        if frets.len() == options.len() {
+            let pitches: Vec<i16> = frets.iter()
+                .zip(open_strings)
+                .filter_map(|(fret, open)| fret.map(|fret| open + (fret - self.capo) as i16))
+                .collect();
+            let pitch_classes: BTreeSet<i16> = pitches.iter().map(|pitch| pitch.rem_euclid(12)).collect();
+            let is_bass_lowest = pitches.iter().min().is_some_and(|lowest| lowest.rem_euclid(12) == bass);
+
+            if pitches.len() >= options.len().min(3) && is_bass_lowest && required.is_subset(&pitch_classes) {
+                shapes.insert(ChordShape::new(frets.clone()));
+            }
+
+            return;
+        }
+
+        for option in &options[frets.len()] {
+            frets.push(*option);
+            self.search_shapes(options, open_strings, frets, required, bass, shapes);
+            frets.pop();
+        }
+    }
+}
+
+fn get_pitch_class(note: LetterNote) -> i16 {
+    note.get_pitch_number().rem_euclid(12)
+}
+
+/// Fingers needed for the stopped strings, with everything on the lowest stopped fret taken as a single barre
+fn get_finger_count(frets: &[Option<u8>], capo: u8) -> usize {
+    let fretted: Vec<u8> = frets.iter().flatten().copied().filter(|fret| *fret > capo).collect();
+
+    match fretted.iter().min() {
+        Some(lowest) => fretted.iter().filter(|fret| *fret > lowest).count() + 1,
+        None => 0,
+    }
+}
+
+/// Muted strings with played strings on both sides of them
+fn get_inner_muted_count(shape: &ChordShape) -> usize {
+    let frets = shape.get_frets();
+    let first = frets.iter().position(Option::is_some);
+    let last = frets.iter().rposition(Option::is_some);
+
+    match (first, last) {
+        (Some(first), Some(last)) => frets[first..=last].iter().filter(|fret| fret.is_none()).count(),
+        _ => 0,
+    }
+}
@@ -0,0 +1,42 @@
+use crate::fretted::FretPosition;
+use crate::rhythm::Duration;
+
+/// Positions struck together, which is a rest when there are none
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct TabEvent {
+    positions: Vec<FretPosition>,
+    duration: Duration,
+}
+
+impl TabEvent {
+    pub fn new(positions: Vec<FretPosition>, duration: Duration) -> TabEvent {
+        TabEvent { positions, duration }
+    }
+
+    pub fn new_rest(duration: Duration) -> TabEvent {
+        TabEvent {
+            positions: Vec::new(),
+            duration,
+        }
+    }
+
+    pub fn get_positions(&self) -> &[FretPosition] {
+        &self.positions
+    }
+
+    pub fn get_duration(&self) -> Duration {
+        self.duration
+    }
+
+    pub fn is_rest(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    pub fn set_positions(&mut self, positions: Vec<FretPosition>) {
+        self.positions = positions;
+    }
+
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = duration;
+    }
+}
@@ -0,0 +1,193 @@
+use std::fmt::Display;
+
+use crispii_errors::{CrispiiError, ImpossibleOperationError};
+
+use crate::fretted::{ascii_tab, FretPosition, TabEvent, Tuning};
+use crate::keys::Key;
+use crate::notes::LetterNote;
+use crate::rhythm::{Duration, Tempo, TimeSignature};
+use crate::scores::{Event, EventKind, Melody};
+
+const MAX_ASSIGNMENTS: usize = 512;
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Tablature {
+    tuning: Tuning,
+    time_signature: Option<TimeSignature>,
+    events: Vec<TabEvent>,
+}
+
+impl Display for Tablature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", ascii_tab::write_tablature(self))
+    }
+}
+
+impl Tablature {
+    /// Without a time signature no barlines are written
+    pub fn new(tuning: Tuning, time_signature: Option<TimeSignature>) -> Tablature {
+        Tablature {
+            tuning,
+            time_signature,
+            events: Vec::new(),
+        }
+    }
+
+    /// Reads ASCII tab with one line per string of the tuning, highest string first. Each column is taken as a thirty-second note, which is
+    /// how tab is written by this crate, apart from the later digits of a fret and columns of spaces. An 'r' marks a rest, and any other gap
+    /// is absorbed into the note before it
+    pub fn try_parse_ascii(text: &str, tuning: Tuning) -> Result<Tablature, Box<dyn CrispiiError>> {
+        ascii_tab::try_parse_tablature(text, tuning)
+    }
+
+    /// Picks a string and fret for every note, keeping chords compact and the hand moving as little as possible between events
+    pub fn try_from_melody(melody: &Melody, tuning: Tuning, max_fret: u8) -> Result<Tablature, Box<dyn CrispiiError>> {
+        let mut layers: Vec<Vec<(Vec<FretPosition>, f64, usize)>> = Vec::new();
+
+        for event in melody.get_events().iter().filter(|event| !event.is_rest()) {
+            let assignments = get_assignments(&tuning, &event.get_notes(), max_fret);
+
+            if assignments.is_empty() {
+                let notes: Vec<String> = event.get_notes().iter().map(|note| format!("{}{}", note.to_string().trim_end(), note.get_octave())).collect();
+                return Err(Box::new(ImpossibleOperationError::new(format!("{} cannot be played in {tuning} tuning within {max_fret} frets", notes.join(" ")).as_str())));
+            }
+
+            let layer = assignments.into_iter()
+                .map(|assignment| {
+                    let cost = get_cost(&assignment);
+
+                    match layers.last() {
+                        Some(previous) => previous.iter()
+                            .enumerate()
+                            .map(|(index, (previous_assignment, total, _))| (total + cost + get_movement(previous_assignment, &assignment), index))
+                            .min_by(|(cost, _), (other_cost, _)| cost.total_cmp(other_cost))
+                            .map(|(total, index)| (assignment.clone(), total, index))
+                            .expect("Earlier layers are never empty"),
+                        None => (assignment, cost, 0),
+                    }
+                })
+                .collect();
+
+            layers.push(layer);
+        }
+
+        let mut chosen = Vec::with_capacity(layers.len());
+        let mut index = layers.last()
+            .and_then(|layer| layer.iter().enumerate().min_by(|(_, (_, cost, _)), (_, (_, other_cost, _))| cost.total_cmp(other_cost)))
+            .map(|(index, _)| index)
+            .unwrap_or(0);
+
+        for layer in layers.iter().rev() {
+            chosen.push(layer[index].0.clone());
+            index = layer[index].2;
+        }
+
+        chosen.reverse();
+
+        let mut chosen = chosen.into_iter();
+        let events = melody.get_events().iter()
+            .map(|event| match event.is_rest() {
+                true => TabEvent::new_rest(event.get_duration()),
+                false => TabEvent::new(chosen.next().expect("There is an assignment for every sounding event"), event.get_duration()),
+            })
+            .collect();
+
+        Ok(Tablature {
+            tuning,
+            time_signature: Some(melody.get_time_signature()),
+            events,
+        })
+    }
+
+    /// Spells the sounding notes to suit the given key
+    pub fn try_to_melody(&self, key: Key) -> Result<Melody, Box<dyn CrispiiError>> {
+        let events = self.events.iter()
+            .map(|event| {
+                let notes = event.get_positions().iter().map(|position| self.tuning.try_get_note(*position, key)).collect::<Result<Vec<LetterNote>, Box<dyn CrispiiError>>>()?;
+                let kind = match notes.len() {
+                    0 => EventKind::Rest,
+                    1 => EventKind::Note(notes[0]),
+                    _ => EventKind::Chord(notes),
+                };
+
+                Ok(Event::new(kind, event.get_duration()))
+            })
+            .collect::<Result<Vec<Event>, Box<dyn CrispiiError>>>()?;
+
+        Ok(Melody::new(key, self.time_signature.unwrap_or_default(), Tempo::default(), events))
+    }
+
+    pub fn get_tuning(&self) -> &Tuning {
+        &self.tuning
+    }
+
+    pub fn get_time_signature(&self) -> Option<TimeSignature> {
+        self.time_signature
+    }
+
+    pub fn get_events(&self) -> &[TabEvent] {
+        &self.events
+    }
+
+    pub fn get_events_mut(&mut self) -> &mut Vec<TabEvent> {
+        &mut self.events
+    }
+
+    pub fn get_duration(&self) -> Duration {
+        self.events.iter().fold(Duration::zero(), |total, event| total + event.get_duration())
+    }
+
+    pub fn set_time_signature(&mut self, time_signature: Option<TimeSignature>) {
+        self.time_signature = time_signature;
+    }
+
+    pub fn add_event(&mut self, event: TabEvent) {
+        self.events.push(event);
+    }
+}
+
+/// Every way of giving each note its own string
+fn get_assignments(tuning: &Tuning, notes: &[LetterNote], max_fret: u8) -> Vec<Vec<FretPosition>> {
+    let mut assignments = vec![Vec::new()];
+
+    for note in notes {
+        let candidates = tuning.get_positions(*note, max_fret);
+
+        assignments = assignments.iter()
+            .flat_map(|assignment: &Vec<FretPosition>| {
+                candidates.iter()
+                    .filter(|candidate| assignment.iter().all(|position| position.get_string() != candidate.get_string()))
+                    .map(|candidate| {
+                        let mut assignment = assignment.clone();
+                        assignment.push(*candidate);
+                        assignment
+                    })
+            })
+            .take(MAX_ASSIGNMENTS)
+            .collect();
+    }
+
+    assignments
+}
+
+/// Where the hand sits, which open strings do not affect
+fn get_hand_position(assignment: &[FretPosition]) -> Option<f64> {
+    let fretted: Vec<f64> = assignment.iter().filter(|position| !position.is_open()).map(|position| position.get_fret() as f64).collect();
+
+    (!fretted.is_empty()).then(|| fretted.iter().sum::<f64>() / fretted.len() as f64)
+}
+
+/// Wide stretches cost the most, and higher positions a little
+fn get_cost(assignment: &[FretPosition]) -> f64 {
+    let fretted = assignment.iter().filter(|position| !position.is_open()).map(|position| position.get_fret());
+    let span = fretted.clone().max().unwrap_or(0) - fretted.min().unwrap_or(0);
+
+    span as f64 * 2.0 + get_hand_position(assignment).unwrap_or(0.0) * 0.2
+}
+
+fn get_movement(from: &[FretPosition], to: &[FretPosition]) -> f64 {
+    match (get_hand_position(from), get_hand_position(to)) {
+        (Some(from), Some(to)) => (from - to).abs(),
+        _ => 0.0,
+    }
+}
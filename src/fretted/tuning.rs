@@ -0,0 +1,138 @@
+use std::fmt::Display;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::fretted::FretPosition;
+use crate::keys::Key;
+use crate::notes::{LetterNote, Modifier, Octave};
+
+/// The open notes of a fretted instrument's strings, listed from string 1 (the highest) down but displayed from the lowest string up
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Tuning {
+    strings: Vec<LetterNote>,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Tuning::guitar()
+    }
+}
+
+impl Display for Tuning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<String> = self.strings.iter().rev().map(|note| format!("{}{}", note.get_letter(), note.get_modifier().get_symbol())).collect();
+
+        write!(f, "{}", names.join(" "))
+    }
+}
+
+impl Tuning {
+    pub fn try_new(strings: Vec<LetterNote>) -> Result<Tuning, Box<dyn CrispiiError>> {
+        if strings.is_empty() || strings.len() > u8::MAX as usize {
+            return Err(Box::new(InvalidArgumentError::new("strings", "Must have between 1 and 255 strings")));
+        }
+
+        Ok(Tuning { strings })
+    }
+
+    /// E A D G B E
+    pub fn guitar() -> Tuning {
+        Tuning {
+            strings: vec![
+                LetterNote::E(Modifier::Default, Octave::Four),
+                LetterNote::B(Modifier::Default, Octave::Three),
+                LetterNote::G(Modifier::Default, Octave::Three),
+                LetterNote::D(Modifier::Default, Octave::Three),
+                LetterNote::A(Modifier::Default, Octave::Two),
+                LetterNote::E(Modifier::Default, Octave::Two),
+            ],
+        }
+    }
+
+    /// D A D G B E
+    pub fn drop_d_guitar() -> Tuning {
+        let mut tuning = Tuning::guitar();
+        tuning.strings[5] = LetterNote::D(Modifier::Default, Octave::Two);
+        tuning
+    }
+
+    /// E A D G, an octave below the guitar's lowest four strings
+    pub fn bass() -> Tuning {
+        Tuning {
+            strings: vec![
+                LetterNote::G(Modifier::Default, Octave::Two),
+                LetterNote::D(Modifier::Default, Octave::Two),
+                LetterNote::A(Modifier::Default, Octave::One),
+                LetterNote::E(Modifier::Default, Octave::One),
+            ],
+        }
+    }
+
+    /// B E A D G
+    pub fn five_string_bass() -> Tuning {
+        let mut tuning = Tuning::bass();
+        tuning.strings.push(LetterNote::B(Modifier::Default, Octave::Zero));
+        tuning
+    }
+
+    /// G C E A, with the G string re-entrant (above middle C)
+    pub fn ukulele() -> Tuning {
+        Tuning {
+            strings: vec![
+                LetterNote::A(Modifier::Default, Octave::Four),
+                LetterNote::E(Modifier::Default, Octave::Four),
+                LetterNote::C(Modifier::Default, Octave::Four),
+                LetterNote::G(Modifier::Default, Octave::Four),
+            ],
+        }
+    }
+
+    /// G D A E, tuned like a violin with each pair of strings counted as one
+    pub fn mandolin() -> Tuning {
+        Tuning {
+            strings: vec![
+                LetterNote::E(Modifier::Default, Octave::Five),
+                LetterNote::A(Modifier::Default, Octave::Four),
+                LetterNote::D(Modifier::Default, Octave::Four),
+                LetterNote::G(Modifier::Default, Octave::Three),
+            ],
+        }
+    }
+
+    pub fn get_strings(&self) -> &[LetterNote] {
+        &self.strings
+    }
+
+    pub fn get_string_count(&self) -> u8 {
+        self.strings.len() as u8
+    }
+
+    pub fn try_get_open_string(&self, string: u8) -> Result<LetterNote, Box<dyn CrispiiError>> {
+        (string as usize).checked_sub(1)
+            .and_then(|index| self.strings.get(index))
+            .copied()
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("string", format!("Must be between 1 and {}", self.strings.len()).as_str())) })
+    }
+
+    /// The MIDI style pitch number (60 is middle C) sounding at a position
+    pub fn try_get_pitch_number(&self, position: FretPosition) -> Result<i16, Box<dyn CrispiiError>> {
+        Ok(self.try_get_open_string(position.get_string())?.get_pitch_number() + position.get_fret() as i16)
+    }
+
+    /// The note sounding at a position, spelt to suit the given key
+    pub fn try_get_note(&self, position: FretPosition, key: Key) -> Result<LetterNote, Box<dyn CrispiiError>> {
+        key.try_spell(self.try_get_pitch_number(position)?)
+    }
+
+    /// Every string and fret, up to and including the given fret, where the note can be played
+    pub fn get_positions(&self, note: LetterNote, max_fret: u8) -> Vec<FretPosition> {
+        self.strings.iter()
+            .enumerate()
+            .filter_map(|(index, open)| {
+                let fret = note.get_pitch_number() - open.get_pitch_number();
+
+                (0..=max_fret as i16).contains(&fret).then(|| FretPosition::new(index as u8 + 1, fret as u8))
+            })
+            .collect()
+    }
+}
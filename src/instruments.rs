@@ -0,0 +1,2 @@
+mod instrument;
+pub use instrument::Instrument;
@@ -0,0 +1,323 @@
+use std::fmt::Display;
+
+use crispii_errors::CrispiiError;
+
+use crate::keys::Key;
+use crate::notation::Clef;
+use crate::notes::{LetterNote, Modifier, Octave};
+use crate::scores::{Event, EventKind, Melody};
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum Instrument {
+    #[default]
+    Piano,
+    Piccolo,
+    Flute,
+    Oboe,
+    CorAnglais,
+    ClarinetInBFlat,
+    ClarinetInA,
+    BassClarinet,
+    Bassoon,
+    SopranoSaxophone,
+    AltoSaxophone,
+    TenorSaxophone,
+    BaritoneSaxophone,
+    TrumpetInBFlat,
+    HornInF,
+    Trombone,
+    Tuba,
+    Violin,
+    Viola,
+    Cello,
+    DoubleBass,
+    Guitar,
+    BassGuitar,
+    Glockenspiel,
+}
+
+impl Display for Instrument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instrument::Piano => write!(f, "Piano"),
+            Instrument::Piccolo => write!(f, "Piccolo"),
+            Instrument::Flute => write!(f, "Flute"),
+            Instrument::Oboe => write!(f, "Oboe"),
+            Instrument::CorAnglais => write!(f, "Cor Anglais"),
+            Instrument::ClarinetInBFlat => write!(f, "Clarinet in B Flat"),
+            Instrument::ClarinetInA => write!(f, "Clarinet in A"),
+            Instrument::BassClarinet => write!(f, "Bass Clarinet"),
+            Instrument::Bassoon => write!(f, "Bassoon"),
+            Instrument::SopranoSaxophone => write!(f, "Soprano Saxophone"),
+            Instrument::AltoSaxophone => write!(f, "Alto Saxophone"),
+            Instrument::TenorSaxophone => write!(f, "Tenor Saxophone"),
+            Instrument::BaritoneSaxophone => write!(f, "Baritone Saxophone"),
+            Instrument::TrumpetInBFlat => write!(f, "Trumpet in B Flat"),
+            Instrument::HornInF => write!(f, "Horn in F"),
+            Instrument::Trombone => write!(f, "Trombone"),
+            Instrument::Tuba => write!(f, "Tuba"),
+            Instrument::Violin => write!(f, "Violin"),
+            Instrument::Viola => write!(f, "Viola"),
+            Instrument::Cello => write!(f, "Cello"),
+            Instrument::DoubleBass => write!(f, "Double Bass"),
+            Instrument::Guitar => write!(f, "Guitar"),
+            Instrument::BassGuitar => write!(f, "Bass Guitar"),
+            Instrument::Glockenspiel => write!(f, "Glockenspiel"),
+        }
+    }
+}
+
+impl Instrument {
+    pub const ALL: [Instrument; 24] = [
+        Instrument::Piano,
+        Instrument::Piccolo,
+        Instrument::Flute,
+        Instrument::Oboe,
+        Instrument::CorAnglais,
+        Instrument::ClarinetInBFlat,
+        Instrument::ClarinetInA,
+        Instrument::BassClarinet,
+        Instrument::Bassoon,
+        Instrument::SopranoSaxophone,
+        Instrument::AltoSaxophone,
+        Instrument::TenorSaxophone,
+        Instrument::BaritoneSaxophone,
+        Instrument::TrumpetInBFlat,
+        Instrument::HornInF,
+        Instrument::Trombone,
+        Instrument::Tuba,
+        Instrument::Violin,
+        Instrument::Viola,
+        Instrument::Cello,
+        Instrument::DoubleBass,
+        Instrument::Guitar,
+        Instrument::BassGuitar,
+        Instrument::Glockenspiel,
+    ];
+
+    /// How far the sounding pitch lies from the written one, as (letters, semitones), so a B flat clarinet, which sounds a major second below
+    /// what is written, gives (-1, -2)
+    pub fn get_transposition(&self) -> (i8, i16) {
+        match self {
+            Instrument::Piano => (0, 0),
+            Instrument::Piccolo => (7, 12),
+            Instrument::Flute => (0, 0),
+            Instrument::Oboe => (0, 0),
+            Instrument::CorAnglais => (-4, -7),
+            Instrument::ClarinetInBFlat => (-1, -2),
+            Instrument::ClarinetInA => (-2, -3),
+            Instrument::BassClarinet => (-8, -14),
+            Instrument::Bassoon => (0, 0),
+            Instrument::SopranoSaxophone => (-1, -2),
+            Instrument::AltoSaxophone => (-5, -9),
+            Instrument::TenorSaxophone => (-8, -14),
+            Instrument::BaritoneSaxophone => (-12, -21),
+            Instrument::TrumpetInBFlat => (-1, -2),
+            Instrument::HornInF => (-4, -7),
+            Instrument::Trombone => (0, 0),
+            Instrument::Tuba => (0, 0),
+            Instrument::Violin => (0, 0),
+            Instrument::Viola => (0, 0),
+            Instrument::Cello => (0, 0),
+            Instrument::DoubleBass => (-7, -12),
+            Instrument::Guitar => (-7, -12),
+            Instrument::BassGuitar => (-7, -12),
+            Instrument::Glockenspiel => (14, 24),
+        }
+    }
+
+    pub fn is_transposing(&self) -> bool {
+        self.get_transposition() != (0, 0)
+    }
+
+    /// The lowest and highest notes as written in the part
+    pub fn get_written_range(&self) -> (LetterNote, LetterNote) {
+        match self {
+            Instrument::Piano => (LetterNote::A(Modifier::Default, Octave::Zero), LetterNote::C(Modifier::Default, Octave::Eight)),
+            Instrument::Piccolo => (LetterNote::D(Modifier::Default, Octave::Four), LetterNote::C(Modifier::Default, Octave::Seven)),
+            Instrument::Flute => (LetterNote::C(Modifier::Default, Octave::Four), LetterNote::D(Modifier::Default, Octave::Seven)),
+            Instrument::Oboe => (LetterNote::B(Modifier::Flat, Octave::Three), LetterNote::A(Modifier::Default, Octave::Six)),
+            Instrument::CorAnglais => (LetterNote::B(Modifier::Default, Octave::Three), LetterNote::G(Modifier::Default, Octave::Six)),
+            Instrument::ClarinetInBFlat => (LetterNote::E(Modifier::Default, Octave::Three), LetterNote::C(Modifier::Default, Octave::Seven)),
+            Instrument::ClarinetInA => (LetterNote::E(Modifier::Default, Octave::Three), LetterNote::C(Modifier::Default, Octave::Seven)),
+            Instrument::BassClarinet => (LetterNote::E(Modifier::Flat, Octave::Three), LetterNote::G(Modifier::Default, Octave::Six)),
+            Instrument::Bassoon => (LetterNote::B(Modifier::Flat, Octave::One), LetterNote::E(Modifier::Default, Octave::Five)),
+            Instrument::SopranoSaxophone => (LetterNote::B(Modifier::Flat, Octave::Three), LetterNote::F(Modifier::Default, Octave::Six)),
+            Instrument::AltoSaxophone => (LetterNote::B(Modifier::Flat, Octave::Three), LetterNote::F(Modifier::Default, Octave::Six)),
+            Instrument::TenorSaxophone => (LetterNote::B(Modifier::Flat, Octave::Three), LetterNote::F(Modifier::Default, Octave::Six)),
+            Instrument::BaritoneSaxophone => (LetterNote::B(Modifier::Flat, Octave::Three), LetterNote::F(Modifier::Default, Octave::Six)),
+            Instrument::TrumpetInBFlat => (LetterNote::F(Modifier::Sharp, Octave::Three), LetterNote::D(Modifier::Default, Octave::Six)),
+            Instrument::HornInF => (LetterNote::F(Modifier::Sharp, Octave::Two), LetterNote::C(Modifier::Default, Octave::Six)),
+            Instrument::Trombone => (LetterNote::E(Modifier::Default, Octave::Two), LetterNote::F(Modifier::Default, Octave::Five)),
+            Instrument::Tuba => (LetterNote::D(Modifier::Default, Octave::One), LetterNote::F(Modifier::Default, Octave::Four)),
+            Instrument::Violin => (LetterNote::G(Modifier::Default, Octave::Three), LetterNote::A(Modifier::Default, Octave::Seven)),
+            Instrument::Viola => (LetterNote::C(Modifier::Default, Octave::Three), LetterNote::E(Modifier::Default, Octave::Six)),
+            Instrument::Cello => (LetterNote::C(Modifier::Default, Octave::Two), LetterNote::C(Modifier::Default, Octave::Six)),
+            Instrument::DoubleBass => (LetterNote::E(Modifier::Default, Octave::Two), LetterNote::G(Modifier::Default, Octave::Five)),
+            Instrument::Guitar => (LetterNote::E(Modifier::Default, Octave::Three), LetterNote::B(Modifier::Default, Octave::Six)),
+            Instrument::BassGuitar => (LetterNote::E(Modifier::Default, Octave::Two), LetterNote::G(Modifier::Default, Octave::Five)),
+            Instrument::Glockenspiel => (LetterNote::G(Modifier::Default, Octave::Three), LetterNote::C(Modifier::Default, Octave::Six)),
+        }
+    }
+
+    /// The lowest and highest notes as they sound
+    pub fn get_sounding_range(&self) -> (LetterNote, LetterNote) {
+        let (lowest, highest) = self.get_written_range();
+
+        (
+            self.try_to_concert(lowest).expect("Every range is within the available octaves"),
+            self.try_to_concert(highest).expect("Every range is within the available octaves"),
+        )
+    }
+
+    /// The clefs parts are written in, most common first. Guitar parts are already written an octave above where they sound, so they use
+    /// the plain treble clef
+    pub fn get_clefs(&self) -> &'static [Clef] {
+        match self {
+            Instrument::Piano => &[Clef::Treble, Clef::Bass],
+            Instrument::Piccolo => &[Clef::Treble],
+            Instrument::Flute => &[Clef::Treble],
+            Instrument::Oboe => &[Clef::Treble],
+            Instrument::CorAnglais => &[Clef::Treble],
+            Instrument::ClarinetInBFlat => &[Clef::Treble],
+            Instrument::ClarinetInA => &[Clef::Treble],
+            Instrument::BassClarinet => &[Clef::Treble],
+            Instrument::Bassoon => &[Clef::Bass, Clef::Tenor],
+            Instrument::SopranoSaxophone => &[Clef::Treble],
+            Instrument::AltoSaxophone => &[Clef::Treble],
+            Instrument::TenorSaxophone => &[Clef::Treble],
+            Instrument::BaritoneSaxophone => &[Clef::Treble],
+            Instrument::TrumpetInBFlat => &[Clef::Treble],
+            Instrument::HornInF => &[Clef::Treble, Clef::Bass],
+            Instrument::Trombone => &[Clef::Bass, Clef::Tenor],
+            Instrument::Tuba => &[Clef::Bass],
+            Instrument::Violin => &[Clef::Treble],
+            Instrument::Viola => &[Clef::Alto, Clef::Treble],
+            Instrument::Cello => &[Clef::Bass, Clef::Tenor, Clef::Treble],
+            Instrument::DoubleBass => &[Clef::Bass],
+            Instrument::Guitar => &[Clef::Treble],
+            Instrument::BassGuitar => &[Clef::Bass],
+            Instrument::Glockenspiel => &[Clef::Treble],
+        }
+    }
+
+    pub fn get_clef(&self) -> Clef {
+        self.get_clefs()[0]
+    }
+
+    /// The General MIDI program number (counting from 0) for this instrument's sound
+    pub fn get_midi_program(&self) -> u8 {
+        match self {
+            Instrument::Piano => 0,
+            Instrument::Piccolo => 72,
+            Instrument::Flute => 73,
+            Instrument::Oboe => 68,
+            Instrument::CorAnglais => 69,
+            Instrument::ClarinetInBFlat => 71,
+            Instrument::ClarinetInA => 71,
+            Instrument::BassClarinet => 71,
+            Instrument::Bassoon => 70,
+            Instrument::SopranoSaxophone => 64,
+            Instrument::AltoSaxophone => 65,
+            Instrument::TenorSaxophone => 66,
+            Instrument::BaritoneSaxophone => 67,
+            Instrument::TrumpetInBFlat => 56,
+            Instrument::HornInF => 60,
+            Instrument::Trombone => 57,
+            Instrument::Tuba => 58,
+            Instrument::Violin => 40,
+            Instrument::Viola => 41,
+            Instrument::Cello => 42,
+            Instrument::DoubleBass => 43,
+            Instrument::Guitar => 24,
+            Instrument::BassGuitar => 33,
+            Instrument::Glockenspiel => 9,
+        }
+    }
+
+    /// The note to write in the part for a concert pitch
+    pub fn try_to_written(&self, concert: LetterNote) -> Result<LetterNote, Box<dyn CrispiiError>> {
+        let (letter_steps, semitones) = self.get_transposition();
+
+        concert.try_offset(-letter_steps, -semitones)
+    }
+
+    /// The concert pitch that sounds when a written note is played
+    pub fn try_to_concert(&self, written: LetterNote) -> Result<LetterNote, Box<dyn CrispiiError>> {
+        let (letter_steps, semitones) = self.get_transposition();
+
+        written.try_offset(letter_steps, semitones)
+    }
+
+    /// Whether a concert pitch can be played, whatever its spelling
+    pub fn is_in_range(&self, concert: LetterNote) -> bool {
+        let (lowest, highest) = self.get_sounding_range();
+
+        (lowest.get_pitch_number()..=highest.get_pitch_number()).contains(&concert.get_pitch_number())
+    }
+
+    pub fn is_in_written_range(&self, written: LetterNote) -> bool {
+        let (lowest, highest) = self.get_written_range();
+
+        (lowest.get_pitch_number()..=highest.get_pitch_number()).contains(&written.get_pitch_number())
+    }
+
+    /// The index of every event in a concert pitch melody holding a note this instrument cannot play, along with that note
+    pub fn find_out_of_range_notes(&self, concert: &Melody) -> Vec<(usize, LetterNote)> {
+        concert.get_events()
+            .iter()
+            .enumerate()
+            .flat_map(|(index, event)| event.get_notes().into_iter().map(move |note| (index, note)))
+            .filter(|(_, note)| !self.is_in_range(*note))
+            .collect()
+    }
+
+    /// Writes out a concert pitch melody as this instrument's part, moving the key signature, notes and chord symbols together. Keys that
+    /// would need more than six sharps or flats are respelt enharmonically, so concert B major becomes D flat major for a B flat instrument
+    /// rather than C sharp major
+    pub fn try_melody_to_written(&self, concert: &Melody) -> Result<Melody, Box<dyn CrispiiError>> {
+        let (letter_steps, semitones) = self.get_transposition();
+
+        try_transpose_melody(concert, -letter_steps, -semitones)
+    }
+
+    /// Turns this instrument's part back into concert pitch
+    pub fn try_melody_to_concert(&self, written: &Melody) -> Result<Melody, Box<dyn CrispiiError>> {
+        let (letter_steps, semitones) = self.get_transposition();
+
+        try_transpose_melody(written, letter_steps, semitones)
+    }
+}
+
+fn try_transpose_melody(melody: &Melody, letter_steps: i8, semitones: i16) -> Result<Melody, Box<dyn CrispiiError>> {
+    let key = melody.get_key();
+    let tonic = key.get_tonic().try_offset(letter_steps, semitones)?;
+    let transposed_key = Key::try_new(tonic, key.get_mode())?;
+    let (key, letter_steps) = match transposed_key.get_fifths().abs() > 6 {
+        true => {
+            let respelt = transposed_key.transpose(0);
+            let letter_shift = (respelt.get_tonic().get_letter_index() as i8 - tonic.get_letter_index() as i8 + 3).rem_euclid(7) - 3;
+
+            (respelt, letter_steps + letter_shift)
+        }
+        false => (transposed_key, letter_steps),
+    };
+
+    let events = melody.get_events()
+        .iter()
+        .map(|event| {
+            let kind = match event.get_kind() {
+                EventKind::Note(note) => EventKind::Note(note.try_offset(letter_steps, semitones)?),
+                EventKind::Chord(notes) => EventKind::Chord(notes.iter().map(|note| note.try_offset(letter_steps, semitones)).collect::<Result<Vec<LetterNote>, Box<dyn CrispiiError>>>()?),
+                EventKind::Rest => EventKind::Rest,
+            };
+            let mut transposed = event.clone();
+
+            transposed.set_kind(kind);
+            transposed.set_chord_symbol(event.get_chord_symbol().map(|chord_symbol| chord_symbol.try_transpose(letter_steps, semitones)).transpose()?);
+            Ok(transposed)
+        })
+        .collect::<Result<Vec<Event>, Box<dyn CrispiiError>>>()?;
+
+    Ok(Melody::new(key, melody.get_time_signature(), melody.get_tempo(), events))
+}
@@ -0,0 +1,5 @@
+mod mode;
+pub use mode::Mode;
+
+mod key;
+pub use key::Key;
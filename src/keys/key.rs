@@ -0,0 +1,216 @@
+use std::fmt::Display;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::keys::Mode;
+use crate::notes::{LetterNote, Modifier, NumberNote, Octave};
+
+const MAJOR_SEMITONES: [i16; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct Key {
+    tonic: LetterNote,
+    mode: Mode,
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.tonic.to_string().trim_end(), self.mode)
+    }
+}
+
+impl Key {
+    /// The octave of the tonic is ignored. Keys needing more than double sharps or flats in their key signature are rejected
+    pub fn try_new(tonic: LetterNote, mode: Mode) -> Result<Key, Box<dyn CrispiiError>> {
+        let tonic = LetterNote::from_letter_index(tonic.get_letter_index(), tonic.get_modifier(), Octave::default());
+        let key = Key { tonic, mode };
+        let fifths = key.get_fifths();
+
+        if !(-14..=14).contains(&fifths) {
+            return Err(Box::new(InvalidArgumentError::new("tonic", format!("{key} would need a key signature beyond double sharps or flats").as_str())));
+        }
+
+        Ok(key)
+    }
+
+    /// Tries to build the key whose signature has the given number of sharps (positive) or flats (negative)
+    pub fn try_from_fifths(fifths: i8, mode: Mode) -> Result<Key, Box<dyn CrispiiError>> {
+        if !(-14..=14).contains(&fifths) {
+            return Err(Box::new(InvalidArgumentError::new("fifths", "Must be between -14 and 14 (inclusive)")));
+        }
+
+        let tonic_fifths = fifths - mode.get_fifths_offset();
+        let letter_fifths = (tonic_fifths + 1).rem_euclid(7) - 1;
+        let modifier = Modifier::try_from_semitone_offset((tonic_fifths - letter_fifths) / 7)
+            .map_err(|_| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("fifths", format!("A {mode} key with {fifths} fifths would need a tonic beyond a double sharp or flat").as_str())) })?;
+
+        Key::try_new(LetterNote::from_letter_index(letter_index_from_fifths(letter_fifths), modifier, Octave::default()), mode)
+    }
+
+    pub fn get_tonic(&self) -> LetterNote {
+        self.tonic
+    }
+
+    pub fn get_mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// The number of sharps (positive) or flats (negative) in the key signature
+    pub fn get_fifths(&self) -> i8 {
+        letter_fifths(self.tonic.get_letter_index()) + 7 * self.tonic.get_modifier().get_semitone_offset() + self.mode.get_fifths_offset()
+    }
+
+    pub fn get_modifier_for_letter(&self, letter_index: u8) -> Modifier {
+        let modifier = (self.get_fifths() + 5 - letter_fifths(letter_index)).div_euclid(7);
+
+        Modifier::try_from_semitone_offset(modifier).expect("Keys beyond double sharps and flats have already been factored out")
+    }
+
+    /// Respells the given note's letter with the modifier this key signature gives it
+    pub fn apply_to(&self, note: LetterNote) -> LetterNote {
+        let letter_index = note.get_letter_index();
+
+        LetterNote::from_letter_index(letter_index, self.get_modifier_for_letter(letter_index), note.get_octave())
+    }
+
+    pub fn contains(&self, note: LetterNote) -> bool {
+        self.apply_to(note).get_modifier() == note.get_modifier()
+    }
+
+    pub fn get_scale(&self) -> [LetterNote; 7] {
+        let tonic_index = self.tonic.get_letter_index();
+
+        std::array::from_fn(|degree| {
+            let letter_index = tonic_index + degree as u8;
+            let octave = if letter_index > 6 { Octave::Five } else { Octave::Four };
+
+            LetterNote::from_letter_index(letter_index, self.get_modifier_for_letter(letter_index % 7), octave)
+        })
+    }
+
+    /// Describes a note as a degree of this key's tonic major scale (so the third of a minor key is a flat 3), with an octave counted from
+    /// the tonic in octave four
+    pub fn try_get_number_note(&self, note: LetterNote) -> Result<NumberNote, Box<dyn CrispiiError>> {
+        let letter_steps = get_letter_position(note) - get_letter_position(self.tonic);
+        let degree = letter_steps.rem_euclid(7) as u8;
+        let octave = Octave::try_from_number(Octave::Four.get_number() + letter_steps.div_euclid(7) as i8)?;
+        let major_pitch = self.tonic.get_pitch_number() + 12 * letter_steps.div_euclid(7) + MAJOR_SEMITONES[degree as usize];
+        let modifier = Modifier::try_from_semitone_offset((note.get_pitch_number() - major_pitch) as i8)
+            .map_err(|_| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("note", format!("{} is too far from the {self} scale to be a degree of it", note.to_string().trim_end()).as_str())) })?;
+
+        Ok(NumberNote::from_degree(degree + 1, modifier, octave))
+    }
+
+    /// The letter note a degree of this key's tonic major scale stands for, the reverse of `try_get_number_note`
+    pub fn try_get_letter_note(&self, number_note: NumberNote) -> Result<LetterNote, Box<dyn CrispiiError>> {
+        let degree = number_note.get_degree() as i16 - 1;
+        let octaves = number_note.get_octave().get_number() as i16 - Octave::Four.get_number() as i16;
+        let semitones = 12 * octaves + MAJOR_SEMITONES[degree as usize] + number_note.get_modifier().get_semitone_offset() as i16;
+
+        self.tonic.try_offset((7 * octaves + degree) as i8, semitones)
+    }
+
+    /// The key of the same mode a number of semitones higher (or lower), spelt with whichever enharmonic signature has the fewest sharps or
+    /// flats
+    pub fn transpose(&self, semitones: i8) -> Key {
+        let fifths = (self.get_fifths() as i16 + 7 * semitones as i16 + 5).rem_euclid(12) - 5;
+
+        Key::try_from_fifths(fifths as i8, self.mode).expect("Signatures within six sharps or flats always have a tonic")
+    }
+
+    /// Spells a MIDI style pitch number (60 is middle C) as the letter note that best fits this key. Notes outside the key get the spelling
+    /// closest to the tonic on the circle of fifths, which favours raised sixths and sevenths in minor keys and flattened degrees in major keys
+    pub fn try_spell(&self, pitch_number: i16) -> Result<LetterNote, Box<dyn CrispiiError>> {
+        let tonic_fifths = letter_fifths(self.tonic.get_letter_index()) as f64 + 7.0 * self.tonic.get_modifier().get_semitone_offset() as f64;
+        let centre = match self.mode {
+            Mode::Minor => tonic_fifths + 0.5,
+            _ => self.get_fifths() as f64 + 1.9,
+        };
+
+        (0..7u8)
+            .flat_map(|letter_index| [Modifier::Default, Modifier::Sharp, Modifier::Flat, Modifier::DoubleSharp, Modifier::DoubleFlat].map(|modifier| (letter_index, modifier)))
+            .filter_map(|(letter_index, modifier)| {
+                let natural = LetterNote::from_letter_index(letter_index, modifier, Octave::Four);
+                let octave_offset = pitch_number - natural.get_pitch_number();
+
+                if octave_offset.rem_euclid(12) != 0 {
+                    return None;
+                }
+
+                let octave = Octave::try_from_number((Octave::Four.get_number() as i16 + octave_offset / 12).try_into().ok()?).ok()?;
+                let note = LetterNote::from_letter_index(letter_index, modifier, octave);
+                let in_key = self.contains(note);
+                let distance = (letter_fifths(letter_index) as f64 + 7.0 * modifier.get_semitone_offset() as f64 - centre).abs();
+
+                Some((note, in_key, distance))
+            })
+            .min_by(|(_, in_key, distance), (_, other_in_key, other_distance)| other_in_key.cmp(in_key).then(distance.total_cmp(other_distance)))
+            .map(|(note, _, _)| note)
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("pitch_number", "Must be within the range of the available octaves")) })
+    }
+
+    /// Finds the major or minor key whose Krumhansl-Kessler profile best correlates with how much each pitch class (0 is C) is heard
+    pub fn estimate(pitch_class_weights: [f64; 12]) -> Key {
+        const MAJOR_PROFILE: [f64; 12] = [6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88];
+        const MINOR_PROFILE: [f64; 12] = [6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34, 3.17];
+
+        let mut best = (Key::default(), f64::MIN);
+
+        for (mode, profile, tonic_fifths_offset) in [(Mode::Major, MAJOR_PROFILE, 0), (Mode::Minor, MINOR_PROFILE, -3)] {
+            for tonic_pitch_class in 0..12 {
+                let rotated: [f64; 12] = std::array::from_fn(|pitch_class| profile[(pitch_class + 12 - tonic_pitch_class) % 12]);
+                let correlation = get_correlation(&pitch_class_weights, &rotated);
+                // Pick whichever of the enharmonic signatures has the fewest sharps or flats
+                let fifths = ((tonic_pitch_class as i8 * 7 + tonic_fifths_offset + 5).rem_euclid(12)) - 5;
+
+                if correlation > best.1 {
+                    best = (Key::try_from_fifths(fifths, mode).expect("Signatures within six sharps or flats always have a tonic"), correlation);
+                }
+            }
+        }
+
+        best.0
+    }
+}
+
+fn get_correlation(a: &[f64; 12], b: &[f64; 12]) -> f64 {
+    let mean_a = a.iter().sum::<f64>() / 12.0;
+    let mean_b = b.iter().sum::<f64>() / 12.0;
+    let covariance: f64 = a.iter().zip(b).map(|(x, y)| (x - mean_a) * (y - mean_b)).sum();
+    let variance_a: f64 = a.iter().map(|x| (x - mean_a).powi(2)).sum();
+    let variance_b: f64 = b.iter().map(|y| (y - mean_b).powi(2)).sum();
+
+    if variance_a == 0.0 || variance_b == 0.0 {
+        return 0.0;
+    }
+
+    covariance / (variance_a * variance_b).sqrt()
+}
+
+fn get_letter_position(note: LetterNote) -> i16 {
+    7 * note.get_octave().get_number() as i16 + note.get_letter_index() as i16
+}
+
+fn letter_fifths(letter_index: u8) -> i8 {
+    match letter_index % 7 {
+        0 => 0,
+        1 => 2,
+        2 => 4,
+        3 => -1,
+        4 => 1,
+        5 => 3,
+        _ => 5,
+    }
+}
+
+fn letter_index_from_fifths(fifths: i8) -> u8 {
+    match fifths {
+        -1 => 3,
+        0 => 0,
+        1 => 4,
+        2 => 1,
+        3 => 5,
+        4 => 2,
+        _ => 6,
+    }
+}
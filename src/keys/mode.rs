@@ -0,0 +1,62 @@
+use std::fmt::Display;
+
+use crate::notes::{Modifier, NumberNote, Octave};
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum Mode {
+    #[default]
+    Major,
+    Dorian,
+    Phrygian,
+    Lydian,
+    Mixolydian,
+    Minor,
+    Locrian,
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mode::Major => write!(f, "Major"),
+            Mode::Dorian => write!(f, "Dorian"),
+            Mode::Phrygian => write!(f, "Phrygian"),
+            Mode::Lydian => write!(f, "Lydian"),
+            Mode::Mixolydian => write!(f, "Mixolydian"),
+            Mode::Minor => write!(f, "Minor"),
+            Mode::Locrian => write!(f, "Locrian"),
+        }
+    }
+}
+
+impl Mode {
+    pub const ALL: [Mode; 7] = [Mode::Major, Mode::Dorian, Mode::Phrygian, Mode::Lydian, Mode::Mixolydian, Mode::Minor, Mode::Locrian];
+
+    /// How far round the circle of fifths this mode's key signature sits from the major key on the same tonic
+    pub fn get_fifths_offset(&self) -> i8 {
+        match self {
+            Mode::Major => 0,
+            Mode::Dorian => -2,
+            Mode::Phrygian => -4,
+            Mode::Lydian => 1,
+            Mode::Mixolydian => -1,
+            Mode::Minor => -3,
+            Mode::Locrian => -5,
+        }
+    }
+
+    pub fn get_number_notes(&self) -> [NumberNote; 7] {
+        let octave = Octave::default();
+        let natural = Modifier::Default;
+        let flat = Modifier::Flat;
+
+        match self {
+            Mode::Major => [NumberNote::One(natural, octave), NumberNote::Two(natural, octave), NumberNote::Three(natural, octave), NumberNote::Four(natural, octave), NumberNote::Five(natural, octave), NumberNote::Six(natural, octave), NumberNote::Seven(natural, octave)],
+            Mode::Dorian => [NumberNote::One(natural, octave), NumberNote::Two(natural, octave), NumberNote::Three(flat, octave), NumberNote::Four(natural, octave), NumberNote::Five(natural, octave), NumberNote::Six(natural, octave), NumberNote::Seven(flat, octave)],
+            Mode::Phrygian => [NumberNote::One(natural, octave), NumberNote::Two(flat, octave), NumberNote::Three(flat, octave), NumberNote::Four(natural, octave), NumberNote::Five(natural, octave), NumberNote::Six(flat, octave), NumberNote::Seven(flat, octave)],
+            Mode::Lydian => [NumberNote::One(natural, octave), NumberNote::Two(natural, octave), NumberNote::Three(natural, octave), NumberNote::Four(Modifier::Sharp, octave), NumberNote::Five(natural, octave), NumberNote::Six(natural, octave), NumberNote::Seven(natural, octave)],
+            Mode::Mixolydian => [NumberNote::One(natural, octave), NumberNote::Two(natural, octave), NumberNote::Three(natural, octave), NumberNote::Four(natural, octave), NumberNote::Five(natural, octave), NumberNote::Six(natural, octave), NumberNote::Seven(flat, octave)],
+            Mode::Minor => [NumberNote::One(natural, octave), NumberNote::Two(natural, octave), NumberNote::Three(flat, octave), NumberNote::Four(natural, octave), NumberNote::Five(natural, octave), NumberNote::Six(flat, octave), NumberNote::Seven(flat, octave)],
+            Mode::Locrian => [NumberNote::One(natural, octave), NumberNote::Two(flat, octave), NumberNote::Three(flat, octave), NumberNote::Four(natural, octave), NumberNote::Five(flat, octave), NumberNote::Six(flat, octave), NumberNote::Seven(flat, octave)],
+        }
+    }
+}
@@ -1 +1,18 @@
 pub mod notes;
+pub mod keys;
+pub mod rhythm;
+pub mod chords;
+pub mod scores;
+pub mod midi;
+pub mod musicxml;
+pub mod abc;
+pub mod lilypond;
+pub mod notation;
+pub mod chordpro;
+pub mod fretted;
+pub mod piano;
+pub mod instruments;
+pub mod sampling;
+pub mod training;
+pub mod audio;
+pub mod tunings;
@@ -0,0 +1,2 @@
+mod writer;
+pub use writer::{write_duration, write_melody, write_note, write_score};
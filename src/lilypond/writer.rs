@@ -0,0 +1,329 @@
+use crate::chords::{ChordQuality, ChordSymbol};
+use crate::keys::{Key, Mode};
+use crate::notes::{LetterNote, Modifier};
+use crate::rhythm::{Duration, NoteValue, Tempo};
+use crate::scores::{Event, EventKind, Melody, Score};
+
+const VERSION: &str = "2.24.0";
+
+/// The absolute (Dutch) LilyPond name of a note, where c' is middle C, so a C sharp in octave 5 is cis'' and a B double flat in octave 2 is beses,
+pub fn write_note(note: LetterNote) -> String {
+    let mut output = write_pitch_class(note);
+    let octave = note.get_octave().get_number();
+
+    if octave > 3 {
+        (3..octave).for_each(|_| output.push('\''));
+    } else {
+        (octave..3).for_each(|_| output.push(','));
+    }
+
+    output
+}
+
+/// A LilyPond duration such as 4 or 2., falling back to the next longer note value scaled down (8*2/3 for a triplet eighth) when no single
+/// note value fits
+pub fn write_duration(duration: Duration) -> String {
+    if let Some((note_value, dots)) = duration.get_note_value() {
+        return format!("{}{}", write_note_value(note_value), ".".repeat(dots as usize));
+    }
+
+    let note_value = NoteValue::ALL.iter().rev().copied().find(|note_value| note_value.get_duration() >= duration).unwrap_or(NoteValue::Breve);
+    let base = note_value.get_duration();
+    let factor = duration.multiply(base.get_denominator(), base.get_numerator());
+
+    format!("{}*{}/{}", write_note_value(note_value), factor.get_numerator(), factor.get_denominator())
+}
+
+pub fn write_melody(melody: &Melody) -> String {
+    write_score(&melody.to_score(""))
+}
+
+/// Writes a complete .ly file with a staff per staff of each part, chord names above any part carrying chord symbols and lyrics under any voice
+/// carrying them
+pub fn write_score(score: &Score) -> String {
+    let mut output = format!("\\version \"{VERSION}\"\n\n");
+    let title = score.get_title();
+    let composer = score.get_composer();
+
+    if title.is_some() || composer.is_some() {
+        output.push_str("\\header {\n");
+
+        if let Some(title) = title {
+            output.push_str(&format!("  title = \"{}\"\n", escape(title)));
+        }
+
+        if let Some(composer) = composer {
+            output.push_str(&format!("  composer = \"{}\"\n", escape(composer)));
+        }
+
+        output.push_str("}\n\n");
+    }
+
+    output.push_str("\\score {\n  <<\n");
+
+    for (part_index, part) in score.get_parts().iter().enumerate() {
+        if let Some(chord_names) = write_chord_names(score, part_index) {
+            output.push_str(&format!("    \\new ChordNames \\chordmode {{\n      {chord_names}\n    }}\n"));
+        }
+
+        let is_grouped = part.get_staves().len() > 1;
+        let mut lyrics = Vec::new();
+
+        if is_grouped {
+            output.push_str(&format!("    \\new PianoStaff \\with {{ instrumentName = \"{}\" }} <<\n", escape(part.get_name())));
+        }
+
+        for (staff_index, staff) in part.get_staves().iter().enumerate() {
+            let indent = if is_grouped { "      " } else { "    " };
+            let voice_count = staff.get_measures().iter().map(|measure| measure.get_voices().len()).max().unwrap_or(0).max(1);
+
+            if !is_grouped && !part.get_name().is_empty() {
+                output.push_str(&format!("{indent}\\new Staff \\with {{ instrumentName = \"{}\" }} <<\n", escape(part.get_name())));
+            } else {
+                output.push_str(&format!("{indent}\\new Staff <<\n"));
+            }
+
+            for voice_index in 0..voice_count {
+                let name = format!("part{}staff{}voice{}", part_index + 1, staff_index + 1, voice_index + 1);
+                let voice_command = match (voice_count, voice_index) {
+                    (1, _) => String::new(),
+                    (_, 0) => " \\voiceOne".to_string(),
+                    (_, 1) => " \\voiceTwo".to_string(),
+                    (_, 2) => " \\voiceThree".to_string(),
+                    _ => " \\voiceFour".to_string(),
+                };
+                let (music, voice_lyrics) = write_voice(score, part_index, staff_index, voice_index, part_index == 0 && staff_index == 0 && voice_index == 0);
+
+                output.push_str(&format!("{indent}  \\new Voice = \"{name}\" {{{voice_command}\n{music}{indent}  }}\n"));
+
+                if let Some(voice_lyrics) = voice_lyrics {
+                    lyrics.push((name, voice_lyrics));
+                }
+            }
+
+            output.push_str(&format!("{indent}>>\n"));
+        }
+
+        if is_grouped {
+            output.push_str("    >>\n");
+        }
+
+        for (name, voice_lyrics) in lyrics {
+            output.push_str(&format!("    \\new Lyrics \\lyricsto \"{name}\" {{\n      {voice_lyrics}\n    }}\n"));
+        }
+    }
+
+    output.push_str("  >>\n  \\layout { }\n  \\midi { }\n}\n");
+    output
+}
+
+/// Returns the music of one voice across every measure, and its lyrics if any of its events have one
+fn write_voice(score: &Score, part_index: usize, staff_index: usize, voice_index: usize, includes_tempo: bool) -> (String, Option<String>) {
+    let measures = score.get_parts()[part_index].get_staves()[staff_index].get_measures();
+    let mut music = String::new();
+    let mut syllables = Vec::new();
+    let mut has_lyrics = false;
+    let mut tied_from_previous = false;
+
+    for (measure_index, measure) in measures.iter().enumerate() {
+        let mut line = String::from("      ");
+
+        if measure_index == 0 || score.get_key_changes().iter().any(|(index, _)| *index == measure_index) {
+            line.push_str(&format!("{} ", write_key(score.get_key_at(measure_index))));
+        }
+
+        if measure_index == 0 || score.get_time_signature_changes().iter().any(|(index, _)| *index == measure_index) {
+            line.push_str(&format!("\\time {} ", score.get_time_signature_at(measure_index)));
+        }
+
+        if includes_tempo && (measure_index == 0 || score.get_tempo_changes().iter().any(|(index, _)| *index == measure_index)) {
+            line.push_str(&format!("{} ", write_tempo(score.get_tempo_at(measure_index))));
+        }
+
+        match measure.get_voices().get(voice_index) {
+            Some(voice) => {
+                for event in voice.get_events() {
+                    line.push_str(&write_event(event));
+                    line.push(' ');
+
+                    if !event.is_rest() && !tied_from_previous {
+                        match event.get_lyric() {
+                            Some(lyric) => {
+                                has_lyrics = true;
+                                syllables.push(match lyric.strip_suffix('-') {
+                                    Some(syllable) => format!("{} --", write_syllable(syllable)),
+                                    None => write_syllable(lyric),
+                                });
+                            }
+                            None => syllables.push("_".to_string()),
+                        }
+                    }
+
+                    tied_from_previous = event.is_tied() && !event.is_rest();
+                }
+            }
+            None => line.push_str(&format!("s{} ", write_duration(score.get_time_signature_at(measure_index).get_measure_duration()))),
+        }
+
+        line.push_str("|\n");
+        music.push_str(&line);
+    }
+
+    while syllables.last().is_some_and(|syllable| syllable == "_") {
+        syllables.pop();
+    }
+
+    (music, has_lyrics.then(|| syllables.join(" ")))
+}
+
+fn write_event(event: &Event) -> String {
+    let pieces: Vec<String> = match event.get_duration().try_split_into_note_values() {
+        Ok(note_values) => note_values.iter().map(|(note_value, dots)| write_duration(note_value.get_dotted_duration(*dots))).collect(),
+        Err(_) => vec![write_duration(event.get_duration())],
+    };
+    let pitch = match event.get_kind() {
+        EventKind::Note(note) => write_note(*note),
+        EventKind::Chord(notes) => format!("<{}>", notes.iter().map(|note| write_note(*note)).collect::<Vec<_>>().join(" ")),
+        EventKind::Rest => "r".to_string(),
+    };
+    let tie = if event.is_rest() { " " } else { " ~ " };
+    let mut output = pieces.iter().map(|duration| format!("{pitch}{duration}")).collect::<Vec<_>>().join(tie);
+
+    if event.is_tied() && !event.is_rest() {
+        output.push_str(" ~");
+    }
+
+    output
+}
+
+/// Lays out the part's chord symbols in time, holding each until the next one and filling any gaps with spacer rests
+fn write_chord_names(score: &Score, part_index: usize) -> Option<String> {
+    let part = &score.get_parts()[part_index];
+    let mut changes: Vec<(Duration, ChordSymbol)> = Vec::new();
+    let mut end = Duration::zero();
+
+    for staff in part.get_staves() {
+        for (measure_index, measure) in staff.get_measures().iter().enumerate() {
+            let measure_start = score.get_measure_start(measure_index);
+            end = end.max(measure_start + score.get_time_signature_at(measure_index).get_measure_duration());
+
+            for voice in measure.get_voices() {
+                let mut position = measure_start;
+
+                for event in voice.get_events() {
+                    if let Some(chord_symbol) = event.get_chord_symbol() {
+                        changes.push((position, chord_symbol));
+                    }
+
+                    position = position + event.get_duration();
+                }
+            }
+        }
+    }
+
+    if changes.is_empty() {
+        return None;
+    }
+
+    changes.sort_by_key(|(start, _)| *start);
+    changes.dedup_by_key(|(start, _)| *start);
+
+    let mut output = Vec::new();
+
+    if let Some((first, _)) = changes.first().filter(|(first, _)| !first.is_zero()) {
+        output.push(format!("s{}", write_duration(*first)));
+    }
+
+    for (index, (start, chord_symbol)) in changes.iter().enumerate() {
+        let next = changes.get(index + 1).map(|(next, _)| *next).unwrap_or(end);
+        let duration = next.try_subtract(*start).unwrap_or_else(|_| Duration::zero());
+
+        if !duration.is_zero() {
+            output.push(write_chord_symbol(chord_symbol, duration));
+        }
+    }
+
+    Some(output.join(" "))
+}
+
+fn write_chord_symbol(chord_symbol: &ChordSymbol, duration: Duration) -> String {
+    let quality = match chord_symbol.get_quality() {
+        ChordQuality::Major => "",
+        ChordQuality::Minor => ":m",
+        ChordQuality::Diminished => ":dim",
+        ChordQuality::Augmented => ":aug",
+        ChordQuality::SuspendedSecond => ":sus2",
+        ChordQuality::SuspendedFourth => ":sus4",
+        ChordQuality::Power => ":1.5",
+        ChordQuality::MajorSixth => ":6",
+        ChordQuality::MinorSixth => ":m6",
+        ChordQuality::DominantSeventh => ":7",
+        ChordQuality::MajorSeventh => ":maj7",
+        ChordQuality::MinorSeventh => ":m7",
+        ChordQuality::MinorMajorSeventh => ":m7+",
+        ChordQuality::HalfDiminishedSeventh => ":m7.5-",
+        ChordQuality::DiminishedSeventh => ":dim7",
+        ChordQuality::AugmentedSeventh => ":aug7",
+        ChordQuality::DominantNinth => ":9",
+        ChordQuality::MajorNinth => ":maj9",
+        ChordQuality::MinorNinth => ":m9",
+        ChordQuality::AddNine => ":5.9",
+    };
+    let bass = chord_symbol.get_bass().map(|bass| format!("/{}", write_pitch_class(bass))).unwrap_or_default();
+
+    format!("{}{}{quality}{bass}", write_pitch_class(chord_symbol.get_root()), write_duration(duration))
+}
+
+fn write_pitch_class(note: LetterNote) -> String {
+    let suffix = match note.get_modifier() {
+        Modifier::DoubleFlat => "eses",
+        Modifier::Flat => "es",
+        Modifier::Default => "",
+        Modifier::Sharp => "is",
+        Modifier::DoubleSharp => "isis",
+    };
+    format!("{}{suffix}", note.get_letter().to_ascii_lowercase())
+}
+
+fn write_note_value(note_value: NoteValue) -> &'static str {
+    match note_value {
+        NoteValue::Breve => "\\breve",
+        NoteValue::Whole => "1",
+        NoteValue::Half => "2",
+        NoteValue::Quarter => "4",
+        NoteValue::Eighth => "8",
+        NoteValue::Sixteenth => "16",
+        NoteValue::ThirtySecond => "32",
+        NoteValue::SixtyFourth => "64",
+    }
+}
+
+fn write_key(key: Key) -> String {
+    let mode = match key.get_mode() {
+        Mode::Major => "\\major",
+        Mode::Dorian => "\\dorian",
+        Mode::Phrygian => "\\phrygian",
+        Mode::Lydian => "\\lydian",
+        Mode::Mixolydian => "\\mixolydian",
+        Mode::Minor => "\\minor",
+        Mode::Locrian => "\\locrian",
+    };
+
+    format!("\\key {} {mode}", write_pitch_class(key.get_tonic()))
+}
+
+fn write_tempo(tempo: Tempo) -> String {
+    format!("\\tempo {} = {}", write_duration(tempo.get_beat()), tempo.get_beats_per_minute())
+}
+
+fn write_syllable(syllable: &str) -> String {
+    if syllable.chars().any(|character| character.is_whitespace() || "{}\"\\_~-".contains(character)) || syllable.chars().all(|character| character.is_ascii_digit()) {
+        format!("\"{}\"", escape(syllable))
+    } else {
+        syllable.to_string()
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,21 @@
+mod byte_reader;
+
+mod midi_format;
+pub use midi_format::MidiFormat;
+
+mod meta_event;
+pub use meta_event::MetaEvent;
+
+mod midi_message;
+pub use midi_message::MidiMessage;
+
+mod midi_event;
+pub use midi_event::MidiEvent;
+
+mod midi_track;
+pub use midi_track::MidiTrack;
+
+mod midi_file;
+pub use midi_file::MidiFile;
+
+mod score_conversion;
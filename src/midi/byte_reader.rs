@@ -0,0 +1,61 @@
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+pub(crate) struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> ByteReader<'a> {
+        ByteReader { bytes, position: 0 }
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.position >= self.bytes.len()
+    }
+
+    pub(crate) fn try_read_bytes(&mut self, count: usize) -> Result<&'a [u8], Box<dyn CrispiiError>> {
+        let end = self.position.checked_add(count).filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("bytes", format!("Unexpected end of data at byte {}", self.position).as_str())) })?;
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+
+        Ok(bytes)
+    }
+
+    pub(crate) fn try_peek_u8(&self) -> Result<u8, Box<dyn CrispiiError>> {
+        self.bytes.get(self.position).copied()
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("bytes", format!("Unexpected end of data at byte {}", self.position).as_str())) })
+    }
+
+    pub(crate) fn try_read_u8(&mut self) -> Result<u8, Box<dyn CrispiiError>> {
+        Ok(self.try_read_bytes(1)?[0])
+    }
+
+    pub(crate) fn try_read_u16(&mut self) -> Result<u16, Box<dyn CrispiiError>> {
+        let bytes = self.try_read_bytes(2)?;
+
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub(crate) fn try_read_u32(&mut self) -> Result<u32, Box<dyn CrispiiError>> {
+        let bytes = self.try_read_bytes(4)?;
+
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub(crate) fn try_read_variable_length(&mut self) -> Result<u32, Box<dyn CrispiiError>> {
+        let mut value = 0u32;
+
+        for _ in 0..4 {
+            let byte = self.try_read_u8()?;
+            value = (value << 7) | (byte & 0x7F) as u32;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(Box::new(InvalidArgumentError::new("bytes", format!("Variable length quantity longer than four bytes before byte {}", self.position).as_str())))
+    }
+}
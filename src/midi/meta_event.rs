@@ -0,0 +1,74 @@
+const MAX_MICROSECONDS_PER_QUARTER: u32 = 0xFF_FFFF;
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum MetaEvent {
+    Text(String),
+    Copyright(String),
+    TrackName(String),
+    InstrumentName(String),
+    Lyric(String),
+    Marker(String),
+    EndOfTrack,
+    /// Microseconds per quarter note, written as 24 bits so anything slower than 0xFFFFFF (about 3.6 quarter notes per minute) is
+    /// written as that
+    Tempo(u32),
+    /// Numerator, denominator, MIDI clocks per metronome click and notated thirty second notes per quarter note
+    TimeSignature(u8, u8, u8, u8),
+    /// Sharps (positive) or flats (negative), and whether the key is minor
+    KeySignature(i8, bool),
+    Other(u8, Vec<u8>),
+}
+
+impl MetaEvent {
+    pub fn get_kind(&self) -> u8 {
+        match self {
+            MetaEvent::Text(_) => 0x01,
+            MetaEvent::Copyright(_) => 0x02,
+            MetaEvent::TrackName(_) => 0x03,
+            MetaEvent::InstrumentName(_) => 0x04,
+            MetaEvent::Lyric(_) => 0x05,
+            MetaEvent::Marker(_) => 0x06,
+            MetaEvent::EndOfTrack => 0x2F,
+            MetaEvent::Tempo(_) => 0x51,
+            MetaEvent::TimeSignature(_, _, _, _) => 0x58,
+            MetaEvent::KeySignature(_, _) => 0x59,
+            MetaEvent::Other(kind, _) => *kind,
+        }
+    }
+
+    pub fn get_data(&self) -> Vec<u8> {
+        match self {
+            MetaEvent::Text(text) => text.as_bytes().to_vec(),
+            MetaEvent::Copyright(text) => text.as_bytes().to_vec(),
+            MetaEvent::TrackName(text) => text.as_bytes().to_vec(),
+            MetaEvent::InstrumentName(text) => text.as_bytes().to_vec(),
+            MetaEvent::Lyric(text) => text.as_bytes().to_vec(),
+            MetaEvent::Marker(text) => text.as_bytes().to_vec(),
+            MetaEvent::EndOfTrack => Vec::new(),
+            MetaEvent::Tempo(microseconds_per_quarter) => (*microseconds_per_quarter).min(MAX_MICROSECONDS_PER_QUARTER).to_be_bytes()[1..].to_vec(),
+            MetaEvent::TimeSignature(numerator, denominator, clocks_per_click, thirty_seconds_per_quarter) => {
+                vec![*numerator, denominator.trailing_zeros() as u8, *clocks_per_click, *thirty_seconds_per_quarter]
+            }
+            MetaEvent::KeySignature(fifths, minor) => vec![*fifths as u8, *minor as u8],
+            MetaEvent::Other(_, data) => data.clone(),
+        }
+    }
+
+    pub(crate) fn from_kind_and_data(kind: u8, data: &[u8]) -> MetaEvent {
+        let text = || String::from_utf8_lossy(data).into_owned();
+
+        match (kind, data.len()) {
+            (0x01, _) => MetaEvent::Text(text()),
+            (0x02, _) => MetaEvent::Copyright(text()),
+            (0x03, _) => MetaEvent::TrackName(text()),
+            (0x04, _) => MetaEvent::InstrumentName(text()),
+            (0x05, _) => MetaEvent::Lyric(text()),
+            (0x06, _) => MetaEvent::Marker(text()),
+            (0x2F, _) => MetaEvent::EndOfTrack,
+            (0x51, 3) => MetaEvent::Tempo(u32::from_be_bytes([0, data[0], data[1], data[2]])),
+            (0x58, 4) if data[1] < 8 => MetaEvent::TimeSignature(data[0], 1 << data[1], data[2], data[3]),
+            (0x59, 2) => MetaEvent::KeySignature(data[0] as i8, data[1] != 0),
+            _ => MetaEvent::Other(kind, data.to_vec()),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::midi::MidiMessage;
+
+/// A message positioned at an absolute tick from the start of its track
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct MidiEvent {
+    tick: u32,
+    message: MidiMessage,
+}
+
+impl MidiEvent {
+    pub fn new(tick: u32, message: MidiMessage) -> MidiEvent {
+        MidiEvent { tick, message }
+    }
+
+    pub fn get_tick(&self) -> u32 {
+        self.tick
+    }
+
+    pub fn get_message(&self) -> &MidiMessage {
+        &self.message
+    }
+}
@@ -0,0 +1,114 @@
+use std::path::Path;
+
+use crispii_errors::{CrispiiError, ImpossibleOperationError, InvalidArgumentError};
+
+use crate::midi::{MidiFormat, MidiTrack};
+use crate::midi::byte_reader::ByteReader;
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct MidiFile {
+    format: MidiFormat,
+    ticks_per_quarter: u16,
+    tracks: Vec<MidiTrack>,
+}
+
+impl Default for MidiFile {
+    fn default() -> Self {
+        Self {
+            format: MidiFormat::default(),
+            ticks_per_quarter: 480,
+            tracks: Vec::new(),
+        }
+    }
+}
+
+impl MidiFile {
+    pub fn try_new(format: MidiFormat, ticks_per_quarter: u16, tracks: Vec<MidiTrack>) -> Result<MidiFile, Box<dyn CrispiiError>> {
+        if ticks_per_quarter == 0 || ticks_per_quarter > 0x7FFF {
+            return Err(Box::new(InvalidArgumentError::new("ticks_per_quarter", "Must be between 1 and 32767 (inclusive)")));
+        }
+
+        if format == MidiFormat::SingleTrack && tracks.len() > 1 {
+            return Err(Box::new(InvalidArgumentError::new("tracks", "A single track MIDI file cannot hold more than one track")));
+        }
+
+        Ok(MidiFile { format, ticks_per_quarter, tracks })
+    }
+
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<MidiFile, Box<dyn CrispiiError>> {
+        let mut reader = ByteReader::new(bytes);
+
+        if reader.try_read_bytes(4)? != b"MThd" {
+            return Err(Box::new(InvalidArgumentError::new("bytes", "Standard MIDI Files must start with an MThd chunk")));
+        }
+
+        let header_length = reader.try_read_u32()? as usize;
+
+        if header_length < 6 {
+            return Err(Box::new(InvalidArgumentError::new("bytes", "The MThd chunk is too short")));
+        }
+
+        let format = MidiFormat::try_from_number(reader.try_read_u16()?)?;
+        let track_count = reader.try_read_u16()? as usize;
+        let division = reader.try_read_u16()?;
+        reader.try_read_bytes(header_length - 6)?;
+
+        if division & 0x8000 != 0 {
+            return Err(Box::new(InvalidArgumentError::new("bytes", "SMPTE time divisions are not supported")));
+        }
+
+        let mut tracks = Vec::new();
+
+        while !reader.is_finished() && tracks.len() < track_count {
+            let chunk_type = reader.try_read_bytes(4)?;
+            let chunk_length = reader.try_read_u32()? as usize;
+            let chunk = reader.try_read_bytes(chunk_length)?;
+
+            if chunk_type == b"MTrk" {
+                tracks.push(MidiTrack::try_from_bytes(chunk)?);
+            }
+        }
+
+        MidiFile::try_new(format, division, tracks)
+    }
+
+    pub fn try_read_from_path<P: AsRef<Path>>(path: P) -> Result<MidiFile, Box<dyn CrispiiError>> {
+        let bytes = std::fs::read(path)
+            .map_err(|error| -> Box<dyn CrispiiError> { Box::new(ImpossibleOperationError::new(format!("Could not read the MIDI file: {error}").as_str())) })?;
+
+        MidiFile::try_from_bytes(&bytes)
+    }
+
+    pub fn get_format(&self) -> MidiFormat {
+        self.format
+    }
+
+    pub fn get_ticks_per_quarter(&self) -> u16 {
+        self.ticks_per_quarter
+    }
+
+    pub fn get_tracks(&self) -> &[MidiTrack] {
+        &self.tracks
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.extend(b"MThd");
+        bytes.extend(6u32.to_be_bytes());
+        bytes.extend(self.format.get_number().to_be_bytes());
+        bytes.extend((self.tracks.len() as u16).to_be_bytes());
+        bytes.extend(self.ticks_per_quarter.to_be_bytes());
+
+        for track in &self.tracks {
+            track.write_to(&mut bytes);
+        }
+
+        bytes
+    }
+
+    pub fn try_write_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn CrispiiError>> {
+        std::fs::write(path, self.to_bytes())
+            .map_err(|error| -> Box<dyn CrispiiError> { Box::new(ImpossibleOperationError::new(format!("Could not write the MIDI file: {error}").as_str())) })
+    }
+}
@@ -0,0 +1,43 @@
+use std::fmt::Display;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum MidiFormat {
+    /// Format 0: a single track holding every channel
+    SingleTrack,
+    /// Format 1: simultaneous tracks, the first of which conventionally holds the tempo map
+    #[default]
+    MultiTrack,
+    /// Format 2: independent single-track sequences
+    Sequential,
+}
+
+impl Display for MidiFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MidiFormat::SingleTrack => write!(f, "Single Track"),
+            MidiFormat::MultiTrack => write!(f, "Multi Track"),
+            MidiFormat::Sequential => write!(f, "Sequential"),
+        }
+    }
+}
+
+impl MidiFormat {
+    pub fn get_number(&self) -> u16 {
+        match self {
+            MidiFormat::SingleTrack => 0,
+            MidiFormat::MultiTrack => 1,
+            MidiFormat::Sequential => 2,
+        }
+    }
+
+    pub fn try_from_number(number: u16) -> Result<MidiFormat, Box<dyn CrispiiError>> {
+        match number {
+            0 => Ok(MidiFormat::SingleTrack),
+            1 => Ok(MidiFormat::MultiTrack),
+            2 => Ok(MidiFormat::Sequential),
+            _ => Err(Box::new(InvalidArgumentError::new("number", "Standard MIDI File formats are 0, 1 or 2"))),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use crate::midi::MetaEvent;
+
+/// Channels are zero based, so General MIDI percussion is on channel 9
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum MidiMessage {
+    /// Channel, key and velocity
+    NoteOff(u8, u8, u8),
+    /// Channel, key and velocity
+    NoteOn(u8, u8, u8),
+    /// Channel, key and pressure
+    PolyphonicPressure(u8, u8, u8),
+    /// Channel, controller and value
+    ControlChange(u8, u8, u8),
+    /// Channel and program
+    ProgramChange(u8, u8),
+    /// Channel and pressure
+    ChannelPressure(u8, u8),
+    /// Channel and a 14 bit value centred on 8192
+    PitchBend(u8, u16),
+    SysEx(Vec<u8>),
+    Meta(MetaEvent),
+}
+
+impl MidiMessage {
+    pub(crate) fn get_sort_priority(&self) -> u8 {
+        match self {
+            MidiMessage::Meta(MetaEvent::EndOfTrack) => 4,
+            MidiMessage::Meta(_) | MidiMessage::SysEx(_) => 0,
+            MidiMessage::NoteOff(_, _, _) => 2,
+            MidiMessage::NoteOn(_, _, velocity) if *velocity == 0 => 2,
+            MidiMessage::NoteOn(_, _, _) => 3,
+            _ => 1,
+        }
+    }
+
+    pub(crate) fn write_to(&self, bytes: &mut Vec<u8>) {
+        match self {
+            MidiMessage::NoteOff(channel, key, velocity) => bytes.extend([0x80 | (channel & 0x0F), key & 0x7F, velocity & 0x7F]),
+            MidiMessage::NoteOn(channel, key, velocity) => bytes.extend([0x90 | (channel & 0x0F), key & 0x7F, velocity & 0x7F]),
+            MidiMessage::PolyphonicPressure(channel, key, pressure) => bytes.extend([0xA0 | (channel & 0x0F), key & 0x7F, pressure & 0x7F]),
+            MidiMessage::ControlChange(channel, controller, value) => bytes.extend([0xB0 | (channel & 0x0F), controller & 0x7F, value & 0x7F]),
+            MidiMessage::ProgramChange(channel, program) => bytes.extend([0xC0 | (channel & 0x0F), program & 0x7F]),
+            MidiMessage::ChannelPressure(channel, pressure) => bytes.extend([0xD0 | (channel & 0x0F), pressure & 0x7F]),
+            MidiMessage::PitchBend(channel, value) => bytes.extend([0xE0 | (channel & 0x0F), (value & 0x7F) as u8, ((value >> 7) & 0x7F) as u8]),
+            MidiMessage::SysEx(data) => {
+                bytes.push(0xF0);
+                write_variable_length(bytes, data.len() as u32);
+                bytes.extend(data);
+            }
+            MidiMessage::Meta(meta_event) => {
+                let data = meta_event.get_data();
+                bytes.extend([0xFF, meta_event.get_kind()]);
+                write_variable_length(bytes, data.len() as u32);
+                bytes.extend(data);
+            }
+        }
+    }
+}
+
+pub(crate) fn write_variable_length(bytes: &mut Vec<u8>, value: u32) {
+    let mut groups = vec![(value & 0x7F) as u8];
+    let mut remaining = value >> 7;
+
+    while remaining > 0 {
+        groups.push((remaining & 0x7F) as u8 | 0x80);
+        remaining >>= 7;
+    }
+
+    bytes.extend(groups.iter().rev());
+}
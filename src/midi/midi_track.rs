@@ -0,0 +1,122 @@
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::midi::{MetaEvent, MidiEvent, MidiMessage};
+use crate::midi::byte_reader::ByteReader;
+use crate::midi::midi_message::write_variable_length;
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct MidiTrack {
+    events: Vec<MidiEvent>,
+}
+
+impl MidiTrack {
+    pub fn new(events: Vec<MidiEvent>) -> MidiTrack {
+        MidiTrack { events }
+    }
+
+    /// Reads the contents of an MTrk chunk, following running status and stopping at the end of track event
+    pub(crate) fn try_from_bytes(bytes: &[u8]) -> Result<MidiTrack, Box<dyn CrispiiError>> {
+        let mut reader = ByteReader::new(bytes);
+        let mut track = MidiTrack::default();
+        let mut tick = 0u32;
+        let mut running_status: Option<u8> = None;
+
+        while !reader.is_finished() {
+            tick = tick.checked_add(reader.try_read_variable_length()?)
+                .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("bytes", "The track runs past the largest tick that can be stored")) })?;
+
+            let status = if reader.try_peek_u8()? & 0x80 != 0 {
+                reader.try_read_u8()?
+            } else {
+                running_status.ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("bytes", "Found running status data without a preceding status byte")) })?
+            };
+
+            let message = match status {
+                0xFF => {
+                    running_status = None;
+                    let kind = reader.try_read_u8()?;
+                    let length = reader.try_read_variable_length()? as usize;
+
+                    MidiMessage::Meta(MetaEvent::from_kind_and_data(kind, reader.try_read_bytes(length)?))
+                }
+                0xF0 | 0xF7 => {
+                    running_status = None;
+                    let length = reader.try_read_variable_length()? as usize;
+
+                    MidiMessage::SysEx(reader.try_read_bytes(length)?.to_vec())
+                }
+                0xF1..=0xFE => return Err(Box::new(InvalidArgumentError::new("bytes", format!("System message {status:#04X} is not allowed in a Standard MIDI File").as_str()))),
+                _ => {
+                    running_status = Some(status);
+                    let channel = status & 0x0F;
+
+                    match status & 0xF0 {
+                        0x80 => MidiMessage::NoteOff(channel, reader.try_read_u8()? & 0x7F, reader.try_read_u8()? & 0x7F),
+                        0x90 => MidiMessage::NoteOn(channel, reader.try_read_u8()? & 0x7F, reader.try_read_u8()? & 0x7F),
+                        0xA0 => MidiMessage::PolyphonicPressure(channel, reader.try_read_u8()? & 0x7F, reader.try_read_u8()? & 0x7F),
+                        0xB0 => MidiMessage::ControlChange(channel, reader.try_read_u8()? & 0x7F, reader.try_read_u8()? & 0x7F),
+                        0xC0 => MidiMessage::ProgramChange(channel, reader.try_read_u8()? & 0x7F),
+                        0xD0 => MidiMessage::ChannelPressure(channel, reader.try_read_u8()? & 0x7F),
+                        _ => {
+                            let low = (reader.try_read_u8()? & 0x7F) as u16;
+                            let high = (reader.try_read_u8()? & 0x7F) as u16;
+
+                            MidiMessage::PitchBend(channel, (high << 7) | low)
+                        }
+                    }
+                }
+            };
+
+            let is_end = message == MidiMessage::Meta(MetaEvent::EndOfTrack);
+            track.add_event(MidiEvent::new(tick, message));
+
+            if is_end {
+                break;
+            }
+        }
+
+        Ok(track)
+    }
+
+    pub fn get_events(&self) -> &[MidiEvent] {
+        &self.events
+    }
+
+    pub fn add_event(&mut self, event: MidiEvent) {
+        self.events.push(event);
+    }
+
+    pub fn get_name(&self) -> Option<&str> {
+        self.events.iter().find_map(|event| match event.get_message() {
+            MidiMessage::Meta(MetaEvent::TrackName(name)) => Some(name.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Orders events by tick, putting meta events first and note offs before note ons on the same tick
+    pub fn sort(&mut self) {
+        self.events.sort_by_key(|event| (event.get_tick(), event.get_message().get_sort_priority()));
+    }
+
+    pub(crate) fn write_to(&self, bytes: &mut Vec<u8>) {
+        let mut events = self.clone();
+        events.sort();
+
+        let end_tick = events.events.last().map(|event| event.get_tick()).unwrap_or(0);
+        events.events.retain(|event| *event.get_message() != MidiMessage::Meta(MetaEvent::EndOfTrack));
+        events.add_event(MidiEvent::new(end_tick, MidiMessage::Meta(MetaEvent::EndOfTrack)));
+
+        let mut data = Vec::new();
+        let mut previous_tick = 0;
+
+        for event in &events.events {
+            write_variable_length(&mut data, event.get_tick() - previous_tick);
+            event.get_message().write_to(&mut data);
+            previous_tick = event.get_tick();
+        }
+
+        bytes.extend(b"MTrk");
+        bytes.extend((data.len() as u32).to_be_bytes());
+        bytes.extend(data);
+    }
+}
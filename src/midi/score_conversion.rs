@@ -0,0 +1,371 @@
+use std::collections::BTreeMap;
+
+use crispii_errors::{CrispiiError, ImpossibleOperationError, InvalidArgumentError};
+
+use crate::keys::{Key, Mode};
+use crate::midi::{MetaEvent, MidiEvent, MidiFile, MidiFormat, MidiMessage, MidiTrack};
+use crate::rhythm::{Duration, Tempo, TimeSignature};
+use crate::scores::{Event, Measure, Melody, Part, Score, Staff, Voice};
+
+const DEFAULT_VELOCITY: u8 = 80;
+/// Imported files are turned down past this many measures, rather than laying out a score for a stray event hours after the music
+const MAX_MEASURE_COUNT: usize = 10_000;
+
+impl MidiFile {
+    /// Lays a score out with a channel per part (skipping the percussion channel). Multi track files get a leading tempo map track followed by a track per part
+    pub fn try_from_score(score: &Score, format: MidiFormat, ticks_per_quarter: u16) -> Result<MidiFile, Box<dyn CrispiiError>> {
+        if format == MidiFormat::Sequential {
+            return Err(Box::new(InvalidArgumentError::new("format", "Scores can only be written as single or multi track MIDI files")));
+        }
+
+        if score.get_parts().len() > 15 {
+            return Err(Box::new(ImpossibleOperationError::new("A score with more than 15 parts cannot be given a MIDI channel per part")));
+        }
+
+        let mut conductor = MidiTrack::default();
+
+        if let Some(title) = score.get_title() {
+            conductor.add_event(MidiEvent::new(0, MidiMessage::Meta(MetaEvent::TrackName(title.to_string()))));
+        }
+
+        if let Some(composer) = score.get_composer() {
+            conductor.add_event(MidiEvent::new(0, MidiMessage::Meta(MetaEvent::Text(composer.to_string()))));
+        }
+
+        let get_tick = |measure_index: usize| score.get_measure_start(measure_index).get_ticks(ticks_per_quarter);
+
+        add_change_events(&mut conductor, score.get_time_signature_changes(), TimeSignature::default(), |time_signature| {
+            MetaEvent::TimeSignature(time_signature.get_numerator(), time_signature.get_denominator(), 24, 8)
+        }, get_tick);
+        add_change_events(&mut conductor, score.get_key_changes(), Key::default(), |key| {
+            MetaEvent::KeySignature(get_midi_fifths(key), key.get_mode() == Mode::Minor)
+        }, get_tick);
+        add_change_events(&mut conductor, score.get_tempo_changes(), Default::default(), |tempo| {
+            MetaEvent::Tempo(tempo.get_microseconds_per_quarter())
+        }, get_tick);
+
+        let mut part_tracks: Vec<MidiTrack> = score.get_parts().iter().enumerate().map(|(part_index, part)| {
+            let channel = get_channel(part_index);
+            let mut track = MidiTrack::default();
+            track.add_event(MidiEvent::new(0, MidiMessage::Meta(MetaEvent::TrackName(part.get_name().to_string()))));
+
+            if let Some(program) = part.get_midi_program() {
+                track.add_event(MidiEvent::new(0, MidiMessage::ProgramChange(channel, program)));
+            }
+
+            track
+        }).collect();
+
+        for timed_note in score.get_timed_notes() {
+            let channel = get_channel(timed_note.get_part_index());
+            let key = timed_note.get_note().try_get_midi_number()?;
+            let start = timed_note.get_start().get_ticks(ticks_per_quarter);
+            let end = timed_note.get_end().get_ticks(ticks_per_quarter);
+            let track = &mut part_tracks[timed_note.get_part_index()];
+
+            track.add_event(MidiEvent::new(start, MidiMessage::NoteOn(channel, key, DEFAULT_VELOCITY)));
+            track.add_event(MidiEvent::new(end, MidiMessage::NoteOff(channel, key, 0)));
+        }
+
+        let tracks = match format {
+            MidiFormat::SingleTrack => {
+                let mut track = conductor;
+
+                for part_track in part_tracks {
+                    for event in part_track.get_events() {
+                        if let MidiMessage::Meta(MetaEvent::TrackName(name)) = event.get_message() {
+                            track.add_event(MidiEvent::new(event.get_tick(), MidiMessage::Meta(MetaEvent::InstrumentName(name.clone()))));
+                        } else {
+                            track.add_event(event.clone());
+                        }
+                    }
+                }
+
+                track.sort();
+                vec![track]
+            }
+            _ => {
+                let mut tracks = vec![conductor];
+                tracks.extend(part_tracks);
+                tracks.iter_mut().for_each(|track| track.sort());
+                tracks
+            }
+        };
+
+        MidiFile::try_new(format, ticks_per_quarter, tracks)
+    }
+
+    pub fn try_from_melody(melody: &Melody, format: MidiFormat, ticks_per_quarter: u16) -> Result<MidiFile, Box<dyn CrispiiError>> {
+        MidiFile::try_from_score(&melody.to_score("Melody"), format, ticks_per_quarter)
+    }
+
+    /// Pairs up note ons and offs into events snapped to the quantisation grid, and spells them using the declared key, else the file's
+    /// key signatures, else a key estimated from the notes. Notes on the same channel that overlap without sharing a start and end are
+    /// split across voices
+    pub fn try_to_score(&self, quantisation: Duration, key: Option<Key>) -> Result<Score, Box<dyn CrispiiError>> {
+        if self.get_format() == MidiFormat::Sequential && self.get_tracks().len() > 1 {
+            return Err(Box::new(ImpossibleOperationError::new("The tracks of a sequential MIDI file are independent, so each needs its own score")));
+        }
+
+        tracks_to_score(self.get_tracks(), self.get_format(), self.get_ticks_per_quarter(), quantisation, key)
+    }
+
+    /// Gives a score per track for sequential files, or a single score otherwise
+    pub fn try_to_scores(&self, quantisation: Duration, key: Option<Key>) -> Result<Vec<Score>, Box<dyn CrispiiError>> {
+        match self.get_format() {
+            MidiFormat::Sequential => self.get_tracks().iter()
+                .map(|track| tracks_to_score(std::slice::from_ref(track), self.get_format(), self.get_ticks_per_quarter(), quantisation, key))
+                .collect(),
+            _ => Ok(vec![self.try_to_score(quantisation, key)?]),
+        }
+    }
+}
+
+fn get_tick_overflow_error() -> Box<dyn CrispiiError> {
+    Box::new(InvalidArgumentError::new("tracks", "The notes run past the largest tick that can be stored"))
+}
+
+struct ImportedPart {
+    name: String,
+    program: Option<u8>,
+    notes: Vec<(u32, u32, u8)>,
+}
+
+fn tracks_to_score(tracks: &[MidiTrack], format: MidiFormat, ticks_per_quarter: u16, quantisation: Duration, declared_key: Option<Key>) -> Result<Score, Box<dyn CrispiiError>> {
+    if quantisation.is_zero() {
+        return Err(Box::new(InvalidArgumentError::new("quantisation", "Must not be zero")));
+    }
+
+    let grid = quantisation.get_ticks(ticks_per_quarter).max(1);
+    let try_quantise = |tick: u32| -> Result<u32, Box<dyn CrispiiError>> {
+        tick.checked_add(grid / 2).map(|tick| tick / grid).and_then(|steps| steps.checked_mul(grid)).ok_or_else(get_tick_overflow_error)
+    };
+
+    let mut score = Score::default();
+    let mut time_signatures = Vec::new();
+    let mut key_signatures = Vec::new();
+    let mut tempos = Vec::new();
+    let mut parts: BTreeMap<(usize, u8), ImportedPart> = BTreeMap::new();
+    let mut end_tick = 0;
+
+    for (track_index, track) in tracks.iter().enumerate() {
+        let mut open_notes: BTreeMap<(u8, u8), Vec<u32>> = BTreeMap::new();
+        let mut programs: BTreeMap<u8, u8> = BTreeMap::new();
+        let mut instrument_names = Vec::new();
+        let track_end = track.get_events().last().map(|event| event.get_tick()).unwrap_or(0);
+        end_tick = end_tick.max(track_end);
+
+        for event in track.get_events() {
+            let tick = event.get_tick();
+
+            match event.get_message() {
+                MidiMessage::NoteOn(channel, key, velocity) if *velocity > 0 => open_notes.entry((*channel, *key)).or_default().push(tick),
+                MidiMessage::NoteOn(channel, key, _) | MidiMessage::NoteOff(channel, key, _) => {
+                    let starts = open_notes.entry((*channel, *key)).or_default();
+
+                    if !starts.is_empty() {
+                        let start = starts.remove(0);
+                        add_imported_note(&mut parts, track_index, *channel, start, tick, *key);
+                    }
+                }
+                MidiMessage::ProgramChange(channel, program) => {
+                    programs.entry(*channel).or_insert(*program);
+                }
+                MidiMessage::Meta(MetaEvent::TimeSignature(numerator, denominator, _, _)) => {
+                    if let Ok(time_signature) = TimeSignature::try_new(*numerator, *denominator) {
+                        time_signatures.push((tick, time_signature));
+                    }
+                }
+                MidiMessage::Meta(MetaEvent::KeySignature(fifths, minor)) => {
+                    if let Ok(key) = Key::try_from_fifths(*fifths, if *minor { Mode::Minor } else { Mode::Major }) {
+                        key_signatures.push((tick, key));
+                    }
+                }
+                MidiMessage::Meta(MetaEvent::Tempo(microseconds_per_quarter)) => tempos.push((tick, Tempo::from_microseconds_per_quarter(*microseconds_per_quarter))),
+                MidiMessage::Meta(MetaEvent::InstrumentName(name)) => instrument_names.push(name.clone()),
+                _ => (),
+            }
+        }
+
+        for ((channel, key), starts) in open_notes {
+            for start in starts {
+                add_imported_note(&mut parts, track_index, channel, start, track_end, key);
+            }
+        }
+
+        let track_name = track.get_name().map(|name| name.to_string());
+        let channels: Vec<u8> = parts.keys().filter(|(index, _)| *index == track_index).map(|(_, channel)| *channel).collect();
+
+        if (channels.is_empty() || format == MidiFormat::SingleTrack) && score.get_title().is_none() {
+            score.set_title(track_name.clone());
+        }
+
+        for (position, channel) in channels.iter().enumerate() {
+            let part = parts.get_mut(&(track_index, *channel)).expect("The channel was taken from the parts");
+            part.program = programs.get(channel).copied();
+            part.name = match (&track_name, instrument_names.get(position)) {
+                (_, Some(instrument_name)) => instrument_name.clone(),
+                (Some(track_name), None) if channels.len() == 1 && format != MidiFormat::SingleTrack => track_name.clone(),
+                (Some(track_name), None) if format != MidiFormat::SingleTrack => format!("{track_name} (Channel {})", channel + 1),
+                _ => format!("Channel {}", channel + 1),
+            };
+        }
+    }
+
+    time_signatures.sort_by_key(|(tick, _)| *tick);
+    key_signatures.sort_by_key(|(tick, _)| *tick);
+    tempos.sort_by_key(|(tick, _)| *tick);
+
+    let mut last_note_end = 0;
+
+    for (_, end, _) in parts.values().flat_map(|part| part.notes.iter()) {
+        last_note_end = last_note_end.max(try_quantise(*end)?);
+    }
+
+    let end_tick = last_note_end.max(end_tick);
+    let mut measure_starts = Vec::new();
+    let mut time_signature = TimeSignature::default();
+    let mut pending_time_signatures = time_signatures.iter().peekable();
+    let mut measure_start = 0;
+
+    while measure_start < end_tick.max(1) {
+        while let Some((_, change)) = pending_time_signatures.next_if(|(tick, _)| *tick <= measure_start) {
+            time_signature = *change;
+            score.set_time_signature_at(measure_starts.len(), time_signature);
+        }
+
+        if measure_starts.len() == MAX_MEASURE_COUNT {
+            return Err(Box::new(InvalidArgumentError::new("tracks", format!("The file runs past {MAX_MEASURE_COUNT} measures").as_str())));
+        }
+
+        measure_starts.push(measure_start);
+        measure_start = measure_start.checked_add(time_signature.get_measure_duration().get_ticks(ticks_per_quarter).max(1)).ok_or_else(get_tick_overflow_error)?;
+    }
+
+    let get_measure_index = |tick: u32| measure_starts.partition_point(|start| *start <= tick).saturating_sub(1);
+
+    for (tick, tempo) in tempos {
+        score.set_tempo_at(get_measure_index(tick), tempo);
+    }
+
+    match declared_key {
+        Some(key) => score.set_key_at(0, key),
+        None if !key_signatures.is_empty() => {
+            for (tick, key) in key_signatures {
+                score.set_key_at(get_measure_index(tick), key);
+            }
+        }
+        None => {
+            let mut pitch_class_weights = [0.0; 12];
+
+            for (start, end, key) in parts.values().flat_map(|part| part.notes.iter()) {
+                pitch_class_weights[*key as usize % 12] += (end - start) as f64;
+            }
+
+            score.set_key_at(0, Key::estimate(pitch_class_weights));
+        }
+    }
+
+    for part in parts.into_values() {
+        let mut clusters: BTreeMap<(u32, u32), Vec<u8>> = BTreeMap::new();
+
+        for (start, end, key) in part.notes {
+            let start = try_quantise(start)?;
+            let end = try_quantise(end)?.max(start.checked_add(grid).ok_or_else(get_tick_overflow_error)?);
+            let keys = clusters.entry((start, end)).or_default();
+
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+
+        let mut voices: Vec<(u32, Vec<Event>)> = Vec::new();
+
+        for ((start, end), mut keys) in clusters {
+            keys.sort();
+            let key = score.get_key_at(get_measure_index(start));
+            let notes = keys.iter().map(|midi_number| key.try_spell(*midi_number as i16)).collect::<Result<Vec<_>, _>>()?;
+            let duration = Duration::from_ticks(end - start, ticks_per_quarter);
+            let event = match notes.len() {
+                1 => Event::new_note(notes[0], duration),
+                _ => Event::new_chord(notes, duration),
+            };
+
+            let voice_index = match voices.iter().position(|(voice_end, _)| *voice_end <= start) {
+                Some(voice_index) => voice_index,
+                None => {
+                    voices.push((0, Vec::new()));
+                    voices.len() - 1
+                }
+            };
+            let (voice_end, events) = &mut voices[voice_index];
+
+            if *voice_end < start {
+                events.push(Event::new_rest(Duration::from_ticks(start - *voice_end, ticks_per_quarter)));
+            }
+
+            events.push(event);
+            *voice_end = end;
+        }
+
+        let measure_count = measure_starts.len();
+        let total_end = match measure_starts.last() {
+            Some(start) => start.checked_add(score.get_time_signature_at(measure_count - 1).get_measure_duration().get_ticks(ticks_per_quarter)).ok_or_else(get_tick_overflow_error)?,
+            None => 0,
+        };
+        let split_voices: Vec<Vec<Voice>> = voices.into_iter().map(|(voice_end, mut events)| {
+            if voice_end < total_end {
+                events.push(Event::new_rest(Duration::from_ticks(total_end - voice_end, ticks_per_quarter)));
+            }
+
+            Voice::new(events).split_into_measures(|measure_index| score.get_time_signature_at(measure_index).get_measure_duration())
+        }).collect();
+
+        let measures = (0..measure_count).map(|measure_index| {
+            Measure::new(split_voices.iter().filter_map(|voice| voice.get(measure_index).cloned()).collect())
+        }).collect();
+
+        let mut imported = Part::new(&part.name, vec![Staff::new(measures)]);
+        imported.set_midi_program(part.program);
+        score.add_part(imported);
+    }
+
+    Ok(score)
+}
+
+fn add_imported_note(parts: &mut BTreeMap<(usize, u8), ImportedPart>, track_index: usize, channel: u8, start: u32, end: u32, key: u8) {
+    parts.entry((track_index, channel))
+        .or_insert_with(|| ImportedPart { name: String::new(), program: None, notes: Vec::new() })
+        .notes.push((start, end, key));
+}
+
+fn get_channel(part_index: usize) -> u8 {
+    if part_index < 9 {
+        part_index as u8
+    } else {
+        part_index as u8 + 1
+    }
+}
+
+/// MIDI key signatures only go up to seven sharps or flats, so theoretical keys are swapped for their enharmonic equivalents
+fn get_midi_fifths(key: &Key) -> i8 {
+    match key.get_fifths() {
+        fifths if fifths > 7 => fifths - 12,
+        fifths if fifths < -7 => fifths + 12,
+        fifths => fifths,
+    }
+}
+
+fn add_change_events<T, F, G>(track: &mut MidiTrack, changes: &[(usize, T)], default: T, to_meta_event: F, get_tick: G)
+where
+    F: Fn(&T) -> MetaEvent,
+    G: Fn(usize) -> u32,
+{
+    if changes.first().is_none_or(|(measure_index, _)| *measure_index > 0) {
+        track.add_event(MidiEvent::new(0, MidiMessage::Meta(to_meta_event(&default))));
+    }
+
+    for (measure_index, change) in changes {
+        track.add_event(MidiEvent::new(get_tick(*measure_index), MidiMessage::Meta(to_meta_event(change))));
+    }
+}
@@ -0,0 +1,7 @@
+mod xml_element;
+
+mod writer;
+pub use writer::try_write_score;
+
+mod reader;
+pub use reader::try_read_score;
@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::chords::{ChordQuality, ChordSymbol};
+use crate::keys::{Key, Mode};
+use crate::musicxml::xml_element::XmlElement;
+use crate::notes::{LetterNote, Modifier, Octave};
+use crate::rhythm::{Duration, NoteValue, Tempo, TimeSignature};
+use crate::scores::{Event, EventKind, Measure, Part, Score, Staff, Voice};
+
+/// Reads a partwise MusicXML document. Key and time signatures are taken from the first part, and grace notes are skipped
+pub fn try_read_score(xml: &str) -> Result<Score, Box<dyn CrispiiError>> {
+    let root = XmlElement::try_parse(xml)?;
+
+    if root.get_name() != "score-partwise" {
+        return Err(Box::new(InvalidArgumentError::new("xml", format!("Expected a <score-partwise> document but found <{}>", root.get_name()).as_str())));
+    }
+
+    let mut score = Score::default();
+    score.set_title(root.get_child("work").and_then(|work| work.get_child_text("work-title"))
+        .or_else(|| root.get_child_text("movement-title"))
+        .map(|title| title.to_string()));
+    score.set_composer(root.get_child("identification")
+        .and_then(|identification| identification.get_children_named("creator").find(|creator| creator.get_attribute("type") == Some("composer")))
+        .map(|creator| creator.get_text().to_string()));
+
+    let score_parts: HashMap<&str, &XmlElement> = root.get_child("part-list")
+        .map(|part_list| part_list.get_children_named("score-part").filter_map(|score_part| Some((score_part.get_attribute("id")?, score_part))).collect())
+        .unwrap_or_default();
+
+    for (part_index, part_element) in root.get_children_named("part").enumerate() {
+        let score_part = part_element.get_attribute("id").and_then(|id| score_parts.get(id));
+        let name = score_part.and_then(|score_part| score_part.get_child_text("part-name")).unwrap_or("");
+        let mut part = Part::new(name, Vec::new());
+        part.set_midi_program(score_part
+            .and_then(|score_part| score_part.get_child("midi-instrument"))
+            .and_then(|instrument| instrument.get_child_text("midi-program"))
+            .and_then(|program| program.parse::<u8>().ok())
+            .map(|program| program.saturating_sub(1)));
+
+        let mut whole_note_divisions = 4u32;
+        let mut staves: Vec<Vec<Measure>> = Vec::new();
+        let mut voice_ids: Vec<Vec<String>> = Vec::new();
+
+        for (measure_index, measure_element) in part_element.get_children_named("measure").enumerate() {
+            let mut position = 0u32;
+            let mut buckets: Vec<(usize, String, u32, Vec<Event>)> = Vec::new();
+            let mut last_bucket = None;
+            let mut pending_chord_symbol = None;
+
+            for element in measure_element.get_children() {
+                match element.get_name() {
+                    "attributes" => {
+                        if let Some(value) = element.get_child_text("divisions").and_then(|value| value.parse::<u32>().ok()) {
+                            whole_note_divisions = value.max(1).checked_mul(4)
+                                .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("xml", format!("{value} divisions per quarter note is too many").as_str())) })?;
+                        }
+
+                        if let Some(count) = element.get_child_text("staves").and_then(|count| count.parse::<usize>().ok()) {
+                            ensure_staves(&mut staves, &mut voice_ids, count);
+                        }
+
+                        if part_index == 0 {
+                            if let Some(key) = element.get_child("key").and_then(read_key) {
+                                score.set_key_at(measure_index, key);
+                            }
+
+                            if let Some(time_signature) = element.get_child("time").and_then(read_time_signature) {
+                                score.set_time_signature_at(measure_index, time_signature);
+                            }
+                        }
+                    }
+                    "direction" | "sound" => {
+                        if let Some(tempo) = read_tempo(element) {
+                            score.set_tempo_at(measure_index, tempo);
+                        }
+                    }
+                    "harmony" => pending_chord_symbol = read_chord_symbol(element),
+                    "backup" => position = position.saturating_sub(read_duration(element)),
+                    "forward" => position = try_advance(position, read_duration(element))?,
+                    "note" => {
+                        if element.get_child("grace").is_some() || element.get_child("cue").is_some() {
+                            continue;
+                        }
+
+                        let pitch = match element.get_child("pitch") {
+                            Some(pitch) => Some(read_pitch(pitch)?),
+                            None => None,
+                        };
+                        let tied = element.get_children_named("tie").any(|tie| tie.get_attribute("type") == Some("start"));
+
+                        if element.get_child("chord").is_some() {
+                            if let (Some(pitch), Some(bucket_index)) = (pitch, last_bucket) {
+                                let bucket: &mut (usize, String, u32, Vec<Event>) = &mut buckets[bucket_index];
+
+                                if let Some(event) = bucket.3.last_mut() {
+                                    let mut notes = event.get_notes();
+                                    notes.push(pitch);
+                                    event.set_kind(EventKind::Chord(notes));
+                                    event.set_tied(event.is_tied() || tied);
+                                }
+                            }
+
+                            continue;
+                        }
+
+                        let duration_divisions = read_duration(element);
+                        let staff_index = element.get_child_text("staff").and_then(|staff| staff.parse::<usize>().ok()).unwrap_or(1).max(1) - 1;
+                        let voice_id = element.get_child_text("voice").unwrap_or("1").to_string();
+                        ensure_staves(&mut staves, &mut voice_ids, staff_index + 1);
+
+                        let bucket_index = match buckets.iter().position(|(staff, voice, _, _)| *staff == staff_index && *voice == voice_id) {
+                            Some(bucket_index) => bucket_index,
+                            None => {
+                                buckets.push((staff_index, voice_id.clone(), 0, Vec::new()));
+                                buckets.len() - 1
+                            }
+                        };
+                        let bucket = &mut buckets[bucket_index];
+
+                        if bucket.2 < position {
+                            bucket.3.push(Event::new_rest(Duration::try_new(position - bucket.2, whole_note_divisions)?));
+                        }
+
+                        let duration = Duration::try_new(duration_divisions, whole_note_divisions)?;
+                        let mut event = match pitch {
+                            Some(pitch) => Event::new_note(pitch, duration),
+                            None => Event::new_rest(duration),
+                        };
+                        event.set_tied(tied && pitch.is_some());
+                        event.set_lyric(element.get_child("lyric").and_then(read_lyric));
+                        event.set_chord_symbol(pending_chord_symbol.take());
+
+                        bucket.3.push(event);
+                        position = try_advance(position, duration_divisions)?;
+                        bucket.2 = position;
+                        last_bucket = Some(bucket_index);
+                    }
+                    _ => (),
+                }
+            }
+
+            ensure_staves(&mut staves, &mut voice_ids, 1);
+            let measure_duration = score.get_time_signature_at(measure_index).get_measure_duration();
+
+            for (staff_index, measures) in staves.iter_mut().enumerate() {
+                let mut voices: Vec<Option<Voice>> = Vec::new();
+
+                for (_, voice_id, _, events) in buckets.iter().filter(|(staff, _, _, _)| *staff == staff_index) {
+                    let ids = &mut voice_ids[staff_index];
+                    let voice_index = match ids.iter().position(|id| id == voice_id) {
+                        Some(voice_index) => voice_index,
+                        None => {
+                            ids.push(voice_id.clone());
+                            ids.len() - 1
+                        }
+                    };
+
+                    if voices.len() <= voice_index {
+                        voices.resize(voice_index + 1, None);
+                    }
+
+                    voices[voice_index] = Some(Voice::new(events.clone()));
+                }
+
+                if voices.is_empty() {
+                    voices.push(None);
+                }
+
+                measures.push(Measure::new(voices.into_iter().map(|voice| voice.unwrap_or_else(|| Voice::new(vec![Event::new_rest(measure_duration)]))).collect()));
+            }
+        }
+
+        for measures in staves {
+            part.add_staff(Staff::new(measures));
+        }
+
+        score.add_part(part);
+    }
+
+    Ok(score)
+}
+
+fn ensure_staves(staves: &mut Vec<Vec<Measure>>, voice_ids: &mut Vec<Vec<String>>, count: usize) {
+    while staves.len() < count {
+        let measure_count = staves.first().map(|measures| measures.len()).unwrap_or(0);
+        staves.push(vec![Measure::default(); measure_count]);
+        voice_ids.push(Vec::new());
+    }
+}
+
+fn try_advance(position: u32, duration: u32) -> Result<u32, Box<dyn CrispiiError>> {
+    position.checked_add(duration).ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("xml", "A measure runs past the largest position that can be stored")) })
+}
+
+fn read_duration(element: &XmlElement) -> u32 {
+    element.get_child_text("duration").and_then(|duration| duration.parse::<f64>().ok()).map(|duration| duration.round().max(0.0) as u32).unwrap_or(0)
+}
+
+fn read_alter(text: Option<&str>) -> Result<Modifier, Box<dyn CrispiiError>> {
+    let alter = text.and_then(|alter| alter.parse::<f64>().ok()).unwrap_or(0.0);
+
+    Modifier::try_from_semitone_offset(alter.round() as i8)
+}
+
+fn read_pitch(pitch: &XmlElement) -> Result<LetterNote, Box<dyn CrispiiError>> {
+    let step = pitch.get_child_text("step").and_then(|step| step.chars().next())
+        .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("xml", "Found a <pitch> without a <step>")) })?;
+    let octave = pitch.get_child_text("octave").and_then(|octave| octave.parse::<i8>().ok())
+        .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("xml", "Found a <pitch> without a valid <octave>")) })?;
+
+    LetterNote::try_from_letter(step, read_alter(pitch.get_child_text("alter"))?, Octave::try_from_number(octave)?)
+}
+
+fn read_key(key: &XmlElement) -> Option<Key> {
+    let fifths = key.get_child_text("fifths")?.parse::<i8>().ok()?;
+    let mode = match key.get_child_text("mode").unwrap_or("major") {
+        "minor" | "aeolian" => Mode::Minor,
+        "dorian" => Mode::Dorian,
+        "phrygian" => Mode::Phrygian,
+        "lydian" => Mode::Lydian,
+        "mixolydian" => Mode::Mixolydian,
+        "locrian" => Mode::Locrian,
+        _ => Mode::Major,
+    };
+
+    Key::try_from_fifths(fifths, mode).ok()
+}
+
+fn read_time_signature(time: &XmlElement) -> Option<TimeSignature> {
+    // Additive signatures such as 3+2 are summed
+    let beats = time.get_child_text("beats")?.split('+').map(|beats| beats.trim().parse::<u8>().ok()).sum::<Option<u8>>()?;
+    let beat_type = time.get_child_text("beat-type")?.parse::<u8>().ok()?;
+
+    TimeSignature::try_new(beats, beat_type).ok()
+}
+
+fn read_tempo(element: &XmlElement) -> Option<Tempo> {
+    let metronome = element.get_children_named("direction-type").find_map(|direction_type| direction_type.get_child("metronome"));
+
+    if let Some(metronome) = metronome {
+        let note_value = metronome.get_child_text("beat-unit").and_then(read_note_value);
+        let per_minute = metronome.get_child_text("per-minute").and_then(|per_minute| per_minute.parse::<f64>().ok());
+
+        if let (Some(note_value), Some(per_minute)) = (note_value, per_minute) {
+            let dots = metronome.get_children_named("beat-unit-dot").count() as u8;
+
+            if let Ok(tempo) = Tempo::try_new(note_value.get_dotted_duration(dots), per_minute.round() as u16) {
+                return Some(tempo);
+            }
+        }
+    }
+
+    let sound = if element.get_name() == "sound" { Some(element) } else { element.get_child("sound") };
+    let quarter_notes_per_minute = sound?.get_attribute("tempo")?.parse::<f64>().ok()?;
+
+    Tempo::try_new(NoteValue::Quarter.get_duration(), quarter_notes_per_minute.round() as u16).ok()
+}
+
+fn read_note_value(name: &str) -> Option<NoteValue> {
+    match name {
+        "breve" => Some(NoteValue::Breve),
+        "whole" => Some(NoteValue::Whole),
+        "half" => Some(NoteValue::Half),
+        "quarter" => Some(NoteValue::Quarter),
+        "eighth" => Some(NoteValue::Eighth),
+        "16th" => Some(NoteValue::Sixteenth),
+        "32nd" => Some(NoteValue::ThirtySecond),
+        "64th" => Some(NoteValue::SixtyFourth),
+        _ => None,
+    }
+}
+
+fn read_lyric(lyric: &XmlElement) -> Option<String> {
+    let text = lyric.get_child_text("text")?;
+
+    match lyric.get_child_text("syllabic") {
+        Some("begin") | Some("middle") => Some(format!("{text}-")),
+        _ => Some(text.to_string()),
+    }
+}
+
+fn read_chord_symbol(harmony: &XmlElement) -> Option<ChordSymbol> {
+    let root = harmony.get_child("root")?;
+    let root = LetterNote::try_from_letter(root.get_child_text("root-step")?.chars().next()?, read_alter(root.get_child_text("root-alter")).ok()?, Octave::Four).ok()?;
+    let added_ninth = harmony.get_children_named("degree").any(|degree| degree.get_child_text("degree-value") == Some("9") && degree.get_child_text("degree-type") == Some("add"));
+    let quality = match harmony.get_child_text("kind")? {
+        "major" if added_ninth => ChordQuality::AddNine,
+        kind => get_quality(kind)?,
+    };
+    let bass = match harmony.get_child("bass") {
+        Some(bass) => Some(LetterNote::try_from_letter(bass.get_child_text("bass-step")?.chars().next()?, read_alter(bass.get_child_text("bass-alter")).ok()?, Octave::Three).ok()?),
+        None => None,
+    };
+
+    Some(ChordSymbol::new(root, quality, bass))
+}
+
+fn get_quality(kind: &str) -> Option<ChordQuality> {
+    match kind {
+        "major" => Some(ChordQuality::Major),
+        "minor" => Some(ChordQuality::Minor),
+        "diminished" => Some(ChordQuality::Diminished),
+        "augmented" => Some(ChordQuality::Augmented),
+        "suspended-second" => Some(ChordQuality::SuspendedSecond),
+        "suspended-fourth" => Some(ChordQuality::SuspendedFourth),
+        "power" => Some(ChordQuality::Power),
+        "major-sixth" => Some(ChordQuality::MajorSixth),
+        "minor-sixth" => Some(ChordQuality::MinorSixth),
+        "dominant" => Some(ChordQuality::DominantSeventh),
+        "major-seventh" => Some(ChordQuality::MajorSeventh),
+        "minor-seventh" => Some(ChordQuality::MinorSeventh),
+        "major-minor" => Some(ChordQuality::MinorMajorSeventh),
+        "half-diminished" => Some(ChordQuality::HalfDiminishedSeventh),
+        "diminished-seventh" => Some(ChordQuality::DiminishedSeventh),
+        "augmented-seventh" => Some(ChordQuality::AugmentedSeventh),
+        "dominant-ninth" => Some(ChordQuality::DominantNinth),
+        "major-ninth" => Some(ChordQuality::MajorNinth),
+        "minor-ninth" => Some(ChordQuality::MinorNinth),
+        _ => None,
+    }
+}
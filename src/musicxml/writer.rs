@@ -0,0 +1,390 @@
+use std::collections::HashMap;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::chords::{ChordQuality, ChordSymbol};
+use crate::keys::Mode;
+use crate::musicxml::xml_element::XmlElement;
+use crate::notes::LetterNote;
+use crate::rhythm::{Duration, NoteValue};
+use crate::scores::{Event, Score};
+
+/// Writes a partwise MusicXML document. Durations that cannot be written as a single note are split into tied notes
+pub fn try_write_score(score: &Score) -> Result<String, Box<dyn CrispiiError>> {
+    let divisions = try_get_divisions(score)?;
+    let mut root = XmlElement::new("score-partwise").with_attribute("version", "4.0");
+
+    if let Some(title) = score.get_title() {
+        root.add_child(XmlElement::new("work").with_child(XmlElement::new_with_text("work-title", title)));
+    }
+
+    let mut identification = XmlElement::new("identification");
+
+    if let Some(composer) = score.get_composer() {
+        identification.add_child(XmlElement::new_with_text("creator", composer).with_attribute("type", "composer"));
+    }
+
+    root.add_child(identification.with_child(XmlElement::new("encoding").with_child(XmlElement::new_with_text("software", "crispii_music"))));
+
+    let mut part_list = XmlElement::new("part-list");
+
+    for (part_index, part) in score.get_parts().iter().enumerate() {
+        let id = format!("P{}", part_index + 1);
+        let mut score_part = XmlElement::new("score-part").with_attribute("id", &id).with_child(XmlElement::new_with_text("part-name", part.get_name()));
+
+        if let Some(program) = part.get_midi_program() {
+            score_part.add_child(XmlElement::new("midi-instrument").with_attribute("id", &format!("{id}-I1"))
+                .with_child(XmlElement::new_with_text("midi-program", &(program as u16 + 1).to_string())));
+        }
+
+        part_list.add_child(score_part);
+    }
+
+    root.add_child(part_list);
+
+    for (part_index, part) in score.get_parts().iter().enumerate() {
+        let mut part_element = XmlElement::new("part").with_attribute("id", &format!("P{}", part_index + 1));
+        let mut tied_into: HashMap<(usize, usize), bool> = HashMap::new();
+        let mut hyphenated_into: HashMap<(usize, usize), bool> = HashMap::new();
+
+        for measure_index in 0..score.get_measure_count() {
+            let mut measure_element = XmlElement::new("measure").with_attribute("number", &(measure_index + 1).to_string());
+            let mut attributes = XmlElement::new("attributes");
+
+            if measure_index == 0 {
+                attributes.add_child(XmlElement::new_with_text("divisions", &divisions.to_string()));
+            }
+
+            if measure_index == 0 || score.get_key_changes().iter().any(|(index, _)| *index == measure_index) {
+                let key = score.get_key_at(measure_index);
+                attributes.add_child(XmlElement::new("key")
+                    .with_child(XmlElement::new_with_text("fifths", &key.get_fifths().to_string()))
+                    .with_child(XmlElement::new_with_text("mode", get_mode_name(key.get_mode()))));
+            }
+
+            if measure_index == 0 || score.get_time_signature_changes().iter().any(|(index, _)| *index == measure_index) {
+                let time_signature = score.get_time_signature_at(measure_index);
+                attributes.add_child(XmlElement::new("time")
+                    .with_child(XmlElement::new_with_text("beats", &time_signature.get_numerator().to_string()))
+                    .with_child(XmlElement::new_with_text("beat-type", &time_signature.get_denominator().to_string())));
+            }
+
+            if measure_index == 0 && part.get_staves().len() > 1 {
+                attributes.add_child(XmlElement::new_with_text("staves", &part.get_staves().len().to_string()));
+            }
+
+            if !attributes.get_children().is_empty() {
+                measure_element.add_child(attributes);
+            }
+
+            if part_index == 0 && (measure_index == 0 || score.get_tempo_changes().iter().any(|(index, _)| *index == measure_index)) {
+                let tempo = score.get_tempo_at(measure_index);
+                let mut metronome = XmlElement::new("metronome");
+
+                if let Some((note_value, dots)) = tempo.get_beat().get_note_value() {
+                    metronome.add_child(XmlElement::new_with_text("beat-unit", get_type_name(note_value)));
+                    (0..dots).for_each(|_| metronome.add_child(XmlElement::new("beat-unit-dot")));
+                    metronome.add_child(XmlElement::new_with_text("per-minute", &tempo.get_beats_per_minute().to_string()));
+                }
+
+                let mut direction_type = XmlElement::new("direction-type");
+
+                if !metronome.get_children().is_empty() {
+                    direction_type.add_child(metronome);
+                }
+
+                let mut direction = XmlElement::new("direction").with_attribute("placement", "above");
+
+                if !direction_type.get_children().is_empty() {
+                    direction.add_child(direction_type);
+                }
+
+                measure_element.add_child(direction.with_child(XmlElement::new("sound").with_attribute("tempo", &format_number(tempo.get_quarter_notes_per_minute()))));
+            }
+
+            let measure_duration = score.get_time_signature_at(measure_index).get_measure_duration();
+            let mut position = Duration::zero();
+
+            for (staff_index, staff) in part.get_staves().iter().enumerate() {
+                let voices = staff.get_measures().get(measure_index).map(|measure| measure.get_voices()).unwrap_or_default();
+
+                if voices.is_empty() {
+                    if !position.is_zero() {
+                        measure_element.add_child(XmlElement::new("backup").with_child(XmlElement::new_with_text("duration", &to_divisions(position, divisions))));
+                    }
+
+                    let mut rest = XmlElement::new("note")
+                        .with_child(XmlElement::new("rest").with_attribute("measure", "yes"))
+                        .with_child(XmlElement::new_with_text("duration", &to_divisions(measure_duration, divisions)))
+                        .with_child(XmlElement::new_with_text("voice", &get_voice_number(staff_index, 0)));
+
+                    if part.get_staves().len() > 1 {
+                        rest.add_child(XmlElement::new_with_text("staff", &(staff_index + 1).to_string()));
+                    }
+
+                    measure_element.add_child(rest);
+                    position = measure_duration;
+                    continue;
+                }
+
+                for (voice_index, voice) in voices.iter().enumerate() {
+                    if !position.is_zero() {
+                        measure_element.add_child(XmlElement::new("backup").with_child(XmlElement::new_with_text("duration", &to_divisions(position, divisions))));
+                    }
+
+                    position = Duration::zero();
+
+                    for event in voice.get_events() {
+                        let tied_from_previous = tied_into.get(&(staff_index, voice_index)).copied().unwrap_or(false);
+                        let hyphenated_from_previous = hyphenated_into.get(&(staff_index, voice_index)).copied().unwrap_or(false);
+                        let staff_number = (part.get_staves().len() > 1).then_some(staff_index + 1);
+
+                        for element in get_event_elements(event, tied_from_previous, hyphenated_from_previous, divisions, &get_voice_number(staff_index, voice_index), staff_number)? {
+                            measure_element.add_child(element);
+                        }
+
+                        tied_into.insert((staff_index, voice_index), event.is_tied() && !event.is_rest());
+
+                        if let Some(lyric) = event.get_lyric() {
+                            hyphenated_into.insert((staff_index, voice_index), lyric.ends_with('-'));
+                        }
+                        position = position + event.get_duration();
+                    }
+                }
+            }
+
+            part_element.add_child(measure_element);
+        }
+
+        root.add_child(part_element);
+    }
+
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n");
+    output.push_str("<!DOCTYPE score-partwise PUBLIC \"-//Recordare//DTD MusicXML 4.0 Partwise//EN\" \"http://www.musicxml.org/dtds/partwise.dtd\">\n");
+    root.write_to(&mut output, 0);
+
+    Ok(output)
+}
+
+fn get_event_elements(event: &Event, tied_from_previous: bool, hyphenated_from_previous: bool, divisions: u32, voice_number: &str, staff_number: Option<usize>) -> Result<Vec<XmlElement>, Box<dyn CrispiiError>> {
+    let mut elements = Vec::new();
+
+    if let Some(chord_symbol) = event.get_chord_symbol() {
+        elements.push(get_harmony_element(&chord_symbol));
+    }
+
+    let pieces: Vec<(Duration, Option<(NoteValue, u8)>)> = match event.get_duration().try_split_into_note_values() {
+        Ok(note_values) => note_values.into_iter().map(|(note_value, dots)| (note_value.get_dotted_duration(dots), Some((note_value, dots)))).collect(),
+        Err(_) => vec![(event.get_duration(), None)],
+    };
+    let notes = event.get_notes();
+
+    for (piece_index, (duration, note_value)) in pieces.iter().enumerate() {
+        let tie_stop = !event.is_rest() && (piece_index > 0 || tied_from_previous);
+        let tie_start = !event.is_rest() && (piece_index + 1 < pieces.len() || event.is_tied());
+        let pitches: Vec<Option<LetterNote>> = if notes.is_empty() { vec![None] } else { notes.iter().map(|note| Some(*note)).collect() };
+
+        for (note_index, pitch) in pitches.iter().enumerate() {
+            let mut note = XmlElement::new("note");
+
+            if note_index > 0 {
+                note.add_child(XmlElement::new("chord"));
+            }
+
+            match pitch {
+                Some(pitch) => {
+                    let mut pitch_element = XmlElement::new("pitch").with_child(XmlElement::new_with_text("step", &pitch.get_letter().to_string()));
+                    let alter = pitch.get_modifier().get_semitone_offset();
+
+                    if alter != 0 {
+                        pitch_element.add_child(XmlElement::new_with_text("alter", &alter.to_string()));
+                    }
+
+                    note.add_child(pitch_element.with_child(XmlElement::new_with_text("octave", &pitch.get_octave().to_string())));
+                }
+                None => note.add_child(XmlElement::new("rest")),
+            }
+
+            note.add_child(XmlElement::new_with_text("duration", &to_divisions(*duration, divisions)));
+
+            if tie_stop {
+                note.add_child(XmlElement::new("tie").with_attribute("type", "stop"));
+            }
+
+            if tie_start {
+                note.add_child(XmlElement::new("tie").with_attribute("type", "start"));
+            }
+
+            note.add_child(XmlElement::new_with_text("voice", voice_number));
+
+            if let Some((note_value, dots)) = note_value {
+                note.add_child(XmlElement::new_with_text("type", get_type_name(*note_value)));
+                (0..*dots).for_each(|_| note.add_child(XmlElement::new("dot")));
+            }
+
+            if let Some(staff_number) = staff_number {
+                note.add_child(XmlElement::new_with_text("staff", &staff_number.to_string()));
+            }
+
+            if tie_stop || tie_start {
+                let mut notations = XmlElement::new("notations");
+
+                if tie_stop {
+                    notations.add_child(XmlElement::new("tied").with_attribute("type", "stop"));
+                }
+
+                if tie_start {
+                    notations.add_child(XmlElement::new("tied").with_attribute("type", "start"));
+                }
+
+                note.add_child(notations);
+            }
+
+            if let (0, 0, Some(lyric)) = (piece_index, note_index, event.get_lyric()) {
+                let (text, syllabic) = match (hyphenated_from_previous, lyric.strip_suffix('-')) {
+                    (false, Some(text)) => (text, "begin"),
+                    (true, Some(text)) => (text, "middle"),
+                    (true, None) => (lyric, "end"),
+                    (false, None) => (lyric, "single"),
+                };
+
+                note.add_child(XmlElement::new("lyric").with_attribute("number", "1")
+                    .with_child(XmlElement::new_with_text("syllabic", syllabic))
+                    .with_child(XmlElement::new_with_text("text", text)));
+            }
+
+            elements.push(note);
+        }
+    }
+
+    Ok(elements)
+}
+
+fn get_harmony_element(chord_symbol: &ChordSymbol) -> XmlElement {
+    let root = chord_symbol.get_root();
+    let mut root_element = XmlElement::new("root").with_child(XmlElement::new_with_text("root-step", &root.get_letter().to_string()));
+
+    if root.get_modifier().get_semitone_offset() != 0 {
+        root_element.add_child(XmlElement::new_with_text("root-alter", &root.get_modifier().get_semitone_offset().to_string()));
+    }
+
+    let mut harmony = XmlElement::new("harmony")
+        .with_child(root_element)
+        .with_child(XmlElement::new_with_text("kind", get_kind(chord_symbol.get_quality())).with_attribute("text", chord_symbol.get_quality().get_symbol()));
+
+    if let Some(bass) = chord_symbol.get_bass() {
+        let mut bass_element = XmlElement::new("bass").with_child(XmlElement::new_with_text("bass-step", &bass.get_letter().to_string()));
+
+        if bass.get_modifier().get_semitone_offset() != 0 {
+            bass_element.add_child(XmlElement::new_with_text("bass-alter", &bass.get_modifier().get_semitone_offset().to_string()));
+        }
+
+        harmony.add_child(bass_element);
+    }
+
+    if chord_symbol.get_quality() == ChordQuality::AddNine {
+        harmony.add_child(XmlElement::new("degree")
+            .with_child(XmlElement::new_with_text("degree-value", "9"))
+            .with_child(XmlElement::new_with_text("degree-alter", "0"))
+            .with_child(XmlElement::new_with_text("degree-type", "add")));
+    }
+
+    harmony
+}
+
+/// The number of divisions per quarter note needed for every duration in the score, including the whole measure rests of empty measures,
+/// to be a whole number of divisions, failing if that does not fit in 32 bits
+fn try_get_divisions(score: &Score) -> Result<u32, Box<dyn CrispiiError>> {
+    let measure_durations = (0..score.get_measure_count()).map(|measure_index| score.get_time_signature_at(measure_index).get_measure_duration());
+    let event_durations = score.get_parts().iter()
+        .flat_map(|part| part.get_staves())
+        .flat_map(|staff| staff.get_measures())
+        .flat_map(|measure| measure.get_voices())
+        .flat_map(|voice| voice.get_events())
+        .map(|event| event.get_duration());
+    let mut divisions = 1u32;
+
+    for duration in measure_durations.chain(event_durations) {
+        let quarter_notes = duration.get_numerator() as u64 * 4;
+        let needed = (duration.get_denominator() as u64 / greatest_common_divisor(quarter_notes, duration.get_denominator() as u64).max(1)) as u32;
+        divisions = (divisions / greatest_common_divisor(divisions as u64, needed as u64) as u32).checked_mul(needed)
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("score", "The durations need more divisions per quarter note than can be stored")) })?;
+    }
+
+    Ok(divisions)
+}
+
+fn greatest_common_divisor(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+fn to_divisions(duration: Duration, divisions: u32) -> String {
+    (duration.get_numerator() as u64 * 4 * divisions as u64 / duration.get_denominator() as u64).to_string()
+}
+
+fn get_voice_number(staff_index: usize, voice_index: usize) -> String {
+    (staff_index * 4 + voice_index + 1).to_string()
+}
+
+fn format_number(number: f64) -> String {
+    if number.fract() == 0.0 {
+        format!("{number:.0}")
+    } else {
+        format!("{number:.2}")
+    }
+}
+
+fn get_mode_name(mode: Mode) -> &'static str {
+    match mode {
+        Mode::Major => "major",
+        Mode::Dorian => "dorian",
+        Mode::Phrygian => "phrygian",
+        Mode::Lydian => "lydian",
+        Mode::Mixolydian => "mixolydian",
+        Mode::Minor => "minor",
+        Mode::Locrian => "locrian",
+    }
+}
+
+fn get_type_name(note_value: NoteValue) -> &'static str {
+    match note_value {
+        NoteValue::Breve => "breve",
+        NoteValue::Whole => "whole",
+        NoteValue::Half => "half",
+        NoteValue::Quarter => "quarter",
+        NoteValue::Eighth => "eighth",
+        NoteValue::Sixteenth => "16th",
+        NoteValue::ThirtySecond => "32nd",
+        NoteValue::SixtyFourth => "64th",
+    }
+}
+
+fn get_kind(quality: ChordQuality) -> &'static str {
+    match quality {
+        ChordQuality::Major => "major",
+        ChordQuality::Minor => "minor",
+        ChordQuality::Diminished => "diminished",
+        ChordQuality::Augmented => "augmented",
+        ChordQuality::SuspendedSecond => "suspended-second",
+        ChordQuality::SuspendedFourth => "suspended-fourth",
+        ChordQuality::Power => "power",
+        ChordQuality::MajorSixth => "major-sixth",
+        ChordQuality::MinorSixth => "minor-sixth",
+        ChordQuality::DominantSeventh => "dominant",
+        ChordQuality::MajorSeventh => "major-seventh",
+        ChordQuality::MinorSeventh => "minor-seventh",
+        ChordQuality::MinorMajorSeventh => "major-minor",
+        ChordQuality::HalfDiminishedSeventh => "half-diminished",
+        ChordQuality::DiminishedSeventh => "diminished-seventh",
+        ChordQuality::AugmentedSeventh => "augmented-seventh",
+        ChordQuality::DominantNinth => "dominant-ninth",
+        ChordQuality::MajorNinth => "major-ninth",
+        ChordQuality::MinorNinth => "minor-ninth",
+        // MusicXML has no add nine kind, so it is written as a major triad with an added degree
+        ChordQuality::AddNine => "major",
+    }
+}
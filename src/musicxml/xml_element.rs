@@ -0,0 +1,296 @@
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+/// MusicXML rarely nests more than ten elements deep, so anything far past that is refused before it can exhaust the stack
+const MAX_DEPTH: usize = 256;
+
+/// A minimal XML tree, with the text directly inside each element joined together rather than interleaved with its children
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub(crate) struct XmlElement {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<XmlElement>,
+    text: String,
+}
+
+impl XmlElement {
+    pub(crate) fn new(name: &str) -> XmlElement {
+        XmlElement {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn new_with_text(name: &str, text: &str) -> XmlElement {
+        XmlElement {
+            name: name.to_string(),
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn with_attribute(mut self, name: &str, value: &str) -> XmlElement {
+        self.attributes.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub(crate) fn with_child(mut self, child: XmlElement) -> XmlElement {
+        self.children.push(child);
+        self
+    }
+
+    pub(crate) fn add_child(&mut self, child: XmlElement) {
+        self.children.push(child);
+    }
+
+    pub(crate) fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn get_text(&self) -> &str {
+        self.text.trim()
+    }
+
+    pub(crate) fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(attribute, _)| attribute == name).map(|(_, value)| value.as_str())
+    }
+
+    pub(crate) fn get_children(&self) -> &[XmlElement] {
+        &self.children
+    }
+
+    pub(crate) fn get_child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    pub(crate) fn get_children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlElement> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    pub(crate) fn get_child_text(&self, name: &str) -> Option<&str> {
+        self.get_child(name).map(|child| child.get_text())
+    }
+
+    pub(crate) fn try_parse(text: &str) -> Result<XmlElement, Box<dyn CrispiiError>> {
+        let mut parser = Parser { text, position: 0 };
+        parser.skip_prolog()?;
+        parser.try_parse_element(0)
+    }
+
+    pub(crate) fn write_to(&self, output: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+        output.push_str(&indent);
+        output.push('<');
+        output.push_str(&self.name);
+
+        for (name, value) in &self.attributes {
+            output.push_str(&format!(" {name}=\"{}\"", escape(value)));
+        }
+
+        if self.children.is_empty() && self.text.is_empty() {
+            output.push_str("/>\n");
+            return;
+        }
+
+        output.push('>');
+
+        if self.children.is_empty() {
+            output.push_str(&escape(&self.text));
+        } else {
+            output.push('\n');
+
+            for child in &self.children {
+                child.write_to(output, depth + 1);
+            }
+
+            output.push_str(&indent);
+        }
+
+        output.push_str(&format!("</{}>\n", self.name));
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;")
+}
+
+fn unescape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..end];
+        let replacement = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X"))
+                .map(|hex| u32::from_str_radix(hex, 16).ok())
+                .unwrap_or_else(|| entity.strip_prefix('#').and_then(|decimal| decimal.parse().ok()))
+                .and_then(char::from_u32),
+        };
+
+        match replacement {
+            Some(character) => {
+                output.push(character);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                output.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.text[self.position..]
+    }
+
+    fn error(&self, explanation: &str) -> Box<dyn CrispiiError> {
+        Box::new(InvalidArgumentError::new("xml", format!("{explanation} at byte {}", self.position).as_str()))
+    }
+
+    fn skip_whitespace(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.position = self.text.len() - trimmed.len();
+    }
+
+    fn try_skip_past(&mut self, terminator: &str) -> Result<(), Box<dyn CrispiiError>> {
+        let end = self.rest().find(terminator).ok_or_else(|| self.error(format!("Missing '{terminator}'").as_str()))?;
+        self.position += end + terminator.len();
+
+        Ok(())
+    }
+
+    fn skip_prolog(&mut self) -> Result<(), Box<dyn CrispiiError>> {
+        if self.rest().starts_with('\u{feff}') {
+            self.position += '\u{feff}'.len_utf8();
+        }
+
+        loop {
+            self.skip_whitespace();
+
+            if self.rest().starts_with("<?") {
+                self.try_skip_past("?>")?;
+            } else if self.rest().starts_with("<!--") {
+                self.try_skip_past("-->")?;
+            } else if self.rest().starts_with("<!DOCTYPE") {
+                let bracket = self.rest().find('[');
+                let close = self.rest().find('>').ok_or_else(|| self.error("Unterminated DOCTYPE"))?;
+
+                match bracket {
+                    Some(bracket) if bracket < close => self.try_skip_past("]>")?,
+                    _ => self.position += close + 1,
+                }
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn try_parse_name(&mut self) -> Result<String, Box<dyn CrispiiError>> {
+        let length = self.rest().find(|character: char| character.is_whitespace() || "/>=".contains(character)).unwrap_or(self.rest().len());
+
+        if length == 0 {
+            return Err(self.error("Expected a name"));
+        }
+
+        let name = self.rest()[..length].to_string();
+        self.position += length;
+
+        Ok(name)
+    }
+
+    fn try_parse_element(&mut self, depth: usize) -> Result<XmlElement, Box<dyn CrispiiError>> {
+        if !self.rest().starts_with('<') {
+            return Err(self.error("Expected an element"));
+        }
+
+        if depth > MAX_DEPTH {
+            return Err(self.error(format!("Elements are nested more than {MAX_DEPTH} deep").as_str()));
+        }
+
+        self.position += 1;
+        let mut element = XmlElement::new(&self.try_parse_name()?);
+
+        loop {
+            self.skip_whitespace();
+
+            if self.rest().starts_with("/>") {
+                self.position += 2;
+                return Ok(element);
+            }
+
+            if self.rest().starts_with('>') {
+                self.position += 1;
+                break;
+            }
+
+            let name = self.try_parse_name()?;
+            self.skip_whitespace();
+
+            if !self.rest().starts_with('=') {
+                return Err(self.error(format!("Expected '=' after the '{name}' attribute").as_str()));
+            }
+
+            self.position += 1;
+            self.skip_whitespace();
+
+            let quote = self.rest().chars().next().filter(|quote| *quote == '"' || *quote == '\'').ok_or_else(|| self.error("Expected a quoted attribute value"))?;
+            self.position += 1;
+            let end = self.rest().find(quote).ok_or_else(|| self.error("Unterminated attribute value"))?;
+            let value = unescape(&self.rest()[..end]);
+            self.position += end + 1;
+            element.attributes.push((name, value));
+        }
+
+        loop {
+            let text_end = self.rest().find('<').ok_or_else(|| self.error(format!("Unterminated <{}> element", element.name).as_str()))?;
+            element.text.push_str(&unescape(&self.rest()[..text_end]));
+            self.position += text_end;
+
+            if self.rest().starts_with("</") {
+                self.position += 2;
+                let name = self.try_parse_name()?;
+
+                if name != element.name {
+                    return Err(self.error(format!("Expected </{}> but found </{name}>", element.name).as_str()));
+                }
+
+                self.skip_whitespace();
+                self.try_skip_past(">")?;
+
+                return Ok(element);
+            } else if self.rest().starts_with("<!--") {
+                self.try_skip_past("-->")?;
+            } else if self.rest().starts_with("<![CDATA[") {
+                self.position += "<![CDATA[".len();
+                let end = self.rest().find("]]>").ok_or_else(|| self.error("Unterminated CDATA section"))?;
+                element.text.push_str(&self.rest()[..end]);
+                self.position += end + 3;
+            } else if self.rest().starts_with("<?") {
+                self.try_skip_past("?>")?;
+            } else {
+                let child = self.try_parse_element(depth + 1)?;
+                element.children.push(child);
+            }
+        }
+    }
+}
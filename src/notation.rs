@@ -0,0 +1,8 @@
+mod clef;
+pub use clef::Clef;
+
+mod staff_position;
+pub use staff_position::StaffPosition;
+
+mod svg_staff;
+pub use svg_staff::SvgStaff;
@@ -0,0 +1,95 @@
+use std::fmt::Display;
+
+use crispii_errors::CrispiiError;
+
+use crate::notation::StaffPosition;
+use crate::notes::{LetterNote, Modifier, Octave};
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum Clef {
+    #[default]
+    Treble,
+    Bass,
+    Alto,
+    Tenor,
+    Percussion,
+    TrebleOctaveDown,
+}
+
+impl Display for Clef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Clef::Treble => write!(f, "Treble"),
+            Clef::Bass => write!(f, "Bass"),
+            Clef::Alto => write!(f, "Alto"),
+            Clef::Tenor => write!(f, "Tenor"),
+            Clef::Percussion => write!(f, "Percussion"),
+            Clef::TrebleOctaveDown => write!(f, "Treble 8vb"),
+        }
+    }
+}
+
+impl Clef {
+    pub const ALL: [Clef; 6] = [Clef::Treble, Clef::Bass, Clef::Alto, Clef::Tenor, Clef::Percussion, Clef::TrebleOctaveDown];
+
+    /// The note sitting on the bottom line. Percussion staves have no pitch of their own, so they are read as if in the treble clef
+    pub fn get_bottom_line_note(&self) -> LetterNote {
+        match self {
+            Clef::Treble | Clef::Percussion => LetterNote::E(Modifier::Default, Octave::Four),
+            Clef::Bass => LetterNote::G(Modifier::Default, Octave::Two),
+            Clef::Alto => LetterNote::F(Modifier::Default, Octave::Three),
+            Clef::Tenor => LetterNote::D(Modifier::Default, Octave::Three),
+            Clef::TrebleOctaveDown => LetterNote::E(Modifier::Default, Octave::Three),
+        }
+    }
+
+    /// How many octaves the sounding pitch lies away from the written one, as for guitar music written in the treble 8vb clef
+    pub fn get_octave_transposition(&self) -> i8 {
+        match self {
+            Clef::TrebleOctaveDown => -1,
+            _ => 0,
+        }
+    }
+
+    /// Where a (sounding) note sits on the staff. Only the letter and octave count, so C, C# and Cb share a position
+    pub fn get_staff_position(&self, note: LetterNote) -> StaffPosition {
+        StaffPosition::new(get_diatonic_step(note) - get_diatonic_step(self.get_bottom_line_note()))
+    }
+
+    /// The (sounding) note at a staff position, spelt with the given modifier
+    pub fn try_get_note(&self, position: StaffPosition, modifier: Modifier) -> Result<LetterNote, Box<dyn CrispiiError>> {
+        let step = get_diatonic_step(self.get_bottom_line_note()) + position.get_steps();
+        let octave = Octave::try_from_number(step.div_euclid(7).clamp(-128, 127) as i8)?;
+
+        Ok(LetterNote::from_letter_index(step.rem_euclid(7) as u8, modifier, octave))
+    }
+
+    pub fn get_ledger_line_count(&self, note: LetterNote) -> u8 {
+        self.get_staff_position(note).get_ledger_line_count()
+    }
+
+    /// Where each accidental of a key signature sits, in the order they are added (F C G D A E B for sharps, the reverse for flats)
+    pub(crate) fn get_key_signature_positions(&self, sharps: bool) -> [i16; 7] {
+        let treble_sharps = [8, 5, 9, 6, 3, 7, 4];
+        let treble_flats = [4, 7, 3, 6, 2, 5, 1];
+
+        match (self, sharps) {
+            (Clef::Tenor, true) => [2, 6, 3, 7, 4, 8, 5],
+            (_, true) => treble_sharps.map(|position| position - self.get_key_signature_shift()),
+            (_, false) => treble_flats.map(|position| position - self.get_key_signature_shift()),
+        }
+    }
+
+    fn get_key_signature_shift(&self) -> i16 {
+        match self {
+            Clef::Treble | Clef::Percussion | Clef::TrebleOctaveDown => 0,
+            Clef::Bass => 2,
+            Clef::Alto => 1,
+            Clef::Tenor => -1,
+        }
+    }
+}
+
+fn get_diatonic_step(note: LetterNote) -> i16 {
+    note.get_octave().get_number() as i16 * 7 + note.get_letter_index() as i16
+}
@@ -0,0 +1,75 @@
+use std::fmt::Display;
+
+/// A line or space counted in steps from the bottom line of the staff, so even values are lines, 8 is the top line and anything below 0 or
+/// above 8 needs ledger lines
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct StaffPosition {
+    steps: i16,
+}
+
+impl Display for StaffPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ledger_lines = self.get_ledger_line_count();
+
+        match (self.is_line(), self.steps) {
+            (true, 0..=8) => write!(f, "Line {}", self.get_number()),
+            (true, ..0) => write!(f, "Ledger Line {ledger_lines} Below"),
+            (true, _) => write!(f, "Ledger Line {ledger_lines} Above"),
+            (false, 0..=8) => write!(f, "Space {}", self.get_number()),
+            (false, -1) => write!(f, "Space Below Line 1"),
+            (false, 9) => write!(f, "Space Above Line 5"),
+            (false, ..0) => write!(f, "Space Below Ledger Line {ledger_lines}"),
+            (false, _) => write!(f, "Space Above Ledger Line {ledger_lines}"),
+        }
+    }
+}
+
+impl StaffPosition {
+    pub fn new(steps: i16) -> StaffPosition {
+        StaffPosition { steps }
+    }
+
+    /// Line 1 is the bottom line of the staff, and space 1 sits between lines 1 and 2
+    pub fn from_line(line: i16) -> StaffPosition {
+        StaffPosition { steps: (line - 1) * 2 }
+    }
+
+    pub fn from_space(space: i16) -> StaffPosition {
+        StaffPosition { steps: (space - 1) * 2 + 1 }
+    }
+
+    pub fn get_steps(&self) -> i16 {
+        self.steps
+    }
+
+    pub fn is_line(&self) -> bool {
+        self.steps % 2 == 0
+    }
+
+    pub fn is_space(&self) -> bool {
+        !self.is_line()
+    }
+
+    /// The line or space number counted from the bottom, continuing past the staff so that the first ledger line above is line 6 and the first
+    /// below is line 0
+    pub fn get_number(&self) -> i16 {
+        self.steps.div_euclid(2) + 1
+    }
+
+    /// How many ledger lines must be drawn to reach this position
+    pub fn get_ledger_line_count(&self) -> u8 {
+        match self.steps {
+            ..0 => (-self.steps / 2) as u8,
+            0..=8 => 0,
+            _ => ((self.steps - 8) / 2) as u8,
+        }
+    }
+
+    pub fn is_on_staff(&self) -> bool {
+        (0..=8).contains(&self.steps)
+    }
+
+    pub fn offset(&self, steps: i16) -> StaffPosition {
+        StaffPosition { steps: self.steps + steps }
+    }
+}
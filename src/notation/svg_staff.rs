@@ -0,0 +1,438 @@
+use std::collections::HashMap;
+
+use crate::keys::Key;
+use crate::notation::Clef;
+use crate::notes::{LetterNote, Modifier};
+use crate::rhythm::{Duration, NoteValue, TimeSignature};
+use crate::scores::{Event, EventKind, Melody, Voice};
+
+const SPACE: f64 = 10.0;
+const HALF_SPACE: f64 = SPACE / 2.0;
+const MARGIN: f64 = 10.0;
+const STEM_LENGTH: f64 = 3.5 * SPACE;
+const GLYPH_FONT: &str = "font-family=\"Bravura, 'Noto Music', 'Segoe UI Symbol', serif\" font-size=\"40\"";
+const TIME_SIGNATURE_FONT: &str = "font-family=\"serif\" font-size=\"20\" font-weight=\"bold\" text-anchor=\"middle\" dominant-baseline=\"central\"";
+const SHARP_ORDER: [u8; 7] = [3, 0, 4, 1, 5, 2, 6];
+const FLAT_ORDER: [u8; 7] = [6, 2, 5, 1, 4, 0, 3];
+
+/// A single staff of events rendered to a standalone SVG image
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct SvgStaff {
+    clef: Clef,
+    key: Key,
+    time_signature: Option<TimeSignature>,
+    events: Vec<Event>,
+}
+
+impl SvgStaff {
+    pub fn new(clef: Clef) -> SvgStaff {
+        SvgStaff {
+            clef,
+            ..Default::default()
+        }
+    }
+
+    pub fn from_melody(melody: &Melody, clef: Clef) -> SvgStaff {
+        SvgStaff {
+            clef,
+            key: melody.get_key(),
+            time_signature: Some(melody.get_time_signature()),
+            events: melody.get_events().to_vec(),
+        }
+    }
+
+    pub fn get_clef(&self) -> Clef {
+        self.clef
+    }
+
+    pub fn get_key(&self) -> Key {
+        self.key
+    }
+
+    pub fn get_time_signature(&self) -> Option<TimeSignature> {
+        self.time_signature
+    }
+
+    pub fn get_events(&self) -> &[Event] {
+        &self.events
+    }
+
+    pub fn set_clef(&mut self, clef: Clef) {
+        self.clef = clef;
+    }
+
+    pub fn set_key(&mut self, key: Key) {
+        self.key = key;
+    }
+
+    /// Without a time signature no barlines are drawn
+    pub fn set_time_signature(&mut self, time_signature: Option<TimeSignature>) {
+        self.time_signature = time_signature;
+    }
+
+    pub fn add_event(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    pub fn add_note(&mut self, note: LetterNote, duration: Duration) {
+        self.events.push(Event::new_note(note, duration));
+    }
+
+    pub fn add_rest(&mut self, duration: Duration) {
+        self.events.push(Event::new_rest(duration));
+    }
+
+    pub fn to_svg(&self) -> String {
+        let measures = match self.time_signature {
+            Some(time_signature) => Voice::new(self.events.clone()).split_into_measures(|_| time_signature.get_measure_duration()),
+            None => vec![Voice::new(self.events.clone())],
+        };
+        let positions: Vec<i16> = self.events.iter().flat_map(Event::get_notes).map(|note| self.clef.get_staff_position(note).get_steps()).collect();
+        let highest = positions.iter().copied().max().unwrap_or(8).max(8) + 7;
+        let lowest = positions.iter().copied().min().unwrap_or(0).min(0) - 7;
+        let mut canvas = Canvas {
+            top_line_y: MARGIN + (highest - 8) as f64 * HALF_SPACE,
+            elements: Vec::new(),
+        };
+        let mut x = MARGIN;
+
+        x = self.draw_clef(&mut canvas, x);
+        x = self.draw_key_signature(&mut canvas, x);
+
+        if let Some(time_signature) = self.time_signature {
+            canvas.text(x + 12.0, canvas.y(6), &time_signature.get_numerator().to_string(), TIME_SIGNATURE_FONT);
+            canvas.text(x + 12.0, canvas.y(2), &time_signature.get_denominator().to_string(), TIME_SIGNATURE_FONT);
+            x += 30.0;
+        }
+
+        x += 10.0;
+
+        let mut tie_from: Option<(f64, Vec<(LetterNote, bool)>)> = None;
+
+        for (measure_index, measure) in measures.iter().enumerate() {
+            let mut accidentals: HashMap<i16, Modifier> = HashMap::new();
+
+            for event in measure.get_events() {
+                let pieces = match event.get_duration().try_split_into_note_values() {
+                    Ok(pieces) => pieces,
+                    Err(_) => vec![(get_nearest_note_value(event.get_duration()), 0)],
+                };
+
+                for (piece_index, (note_value, dots)) in pieces.iter().enumerate() {
+                    let is_tied = !event.is_rest() && (event.is_tied() || piece_index + 1 < pieces.len());
+
+                    x = match event.get_kind() {
+                        EventKind::Rest => {
+                            tie_from = None;
+                            draw_rest(&mut canvas, x, *note_value, *dots)
+                        }
+                        _ => {
+                            let notes = event.get_notes();
+                            let tied_notes = tie_from.take().filter(|(_, tied_notes)| tied_notes.iter().any(|(note, _)| notes.contains(note)));
+                            let (end, heads) = self.draw_notes(&mut canvas, x, &notes, (*note_value, *dots), &mut accidentals, tied_notes);
+
+                            if is_tied {
+                                tie_from = Some(heads);
+                            }
+
+                            end
+                        }
+                    };
+                }
+            }
+
+            if self.time_signature.is_some() && measure_index + 1 < measures.len() {
+                canvas.line(x - 8.0, canvas.y(8), x - 8.0, canvas.y(0), 1.0);
+                x += 6.0;
+            }
+        }
+
+        x += 4.0;
+
+        if self.time_signature.is_some() {
+            canvas.line(x - 12.0, canvas.y(8), x - 12.0, canvas.y(0), 1.0);
+            canvas.rect(x - 8.0, canvas.y(8), 4.0, 4.0 * SPACE);
+        }
+
+        let width = x + MARGIN;
+        let height = canvas.top_line_y + (8 - lowest) as f64 * HALF_SPACE + MARGIN;
+        let mut output = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.0}\" height=\"{height:.0}\" viewBox=\"0 0 {width:.0} {height:.0}\">\n");
+
+        for line in 0..5 {
+            let y = canvas.y(line * 2);
+            output.push_str(&format!("  <line x1=\"{MARGIN:.1}\" y1=\"{y:.1}\" x2=\"{:.1}\" y2=\"{y:.1}\" stroke=\"black\" stroke-width=\"1\"/>\n", x - 4.0));
+        }
+
+        for element in canvas.elements {
+            output.push_str(&format!("  {element}\n"));
+        }
+
+        output.push_str("</svg>\n");
+        output
+    }
+
+    fn draw_clef(&self, canvas: &mut Canvas, x: f64) -> f64 {
+        let (glyph, position) = match self.clef {
+            Clef::Treble | Clef::TrebleOctaveDown => ("\u{1D11E}", 2),
+            Clef::Bass => ("\u{1D122}", 6),
+            Clef::Alto => ("\u{1D121}", 4),
+            Clef::Tenor => ("\u{1D121}", 6),
+            Clef::Percussion => {
+                canvas.rect(x + 8.0, canvas.y(6), 4.0, 2.0 * SPACE);
+                canvas.rect(x + 16.0, canvas.y(6), 4.0, 2.0 * SPACE);
+                return x + 36.0;
+            }
+        };
+
+        canvas.text(x + 2.0, canvas.y(position), glyph, GLYPH_FONT);
+
+        if self.clef == Clef::TrebleOctaveDown {
+            canvas.text(x + 14.0, canvas.y(-4), "8", "font-family=\"serif\" font-size=\"11\" font-style=\"italic\" text-anchor=\"middle\"");
+        }
+
+        x + 36.0
+    }
+
+    fn draw_key_signature(&self, canvas: &mut Canvas, mut x: f64) -> f64 {
+        if self.clef == Clef::Percussion {
+            return x;
+        }
+
+        let sharps = self.key.get_fifths() > 0;
+        let order = if sharps { SHARP_ORDER } else { FLAT_ORDER };
+
+        for (letter_index, position) in order.iter().zip(self.clef.get_key_signature_positions(sharps)) {
+            let modifier = self.key.get_modifier_for_letter(*letter_index);
+
+            if modifier != Modifier::Default {
+                x += get_accidental_width(modifier);
+                draw_accidental(canvas, x - get_accidental_width(modifier) / 2.0, canvas.y(position), modifier);
+            }
+        }
+
+        x
+    }
+
+    /// Draws the heads, accidentals, stem and flags of a note or chord, returning where the next event starts and where each head is for any
+    /// tie out of it
+    fn draw_notes(
+        &self,
+        canvas: &mut Canvas,
+        x: f64,
+        notes: &[LetterNote],
+        (note_value, dots): (NoteValue, u8),
+        accidentals: &mut HashMap<i16, Modifier>,
+        tied_notes: Option<(f64, Vec<(LetterNote, bool)>)>,
+    ) -> (f64, (f64, Vec<(LetterNote, bool)>)) {
+        let positions: Vec<i16> = notes.iter().map(|note| self.clef.get_staff_position(*note).get_steps()).collect();
+        let highest = positions.iter().copied().max().unwrap_or(4);
+        let lowest = positions.iter().copied().min().unwrap_or(4);
+        let stem_up = highest + lowest < 8;
+        let mut shown = Vec::new();
+
+        for (note, position) in notes.iter().zip(&positions) {
+            let step = note.get_octave().get_number() as i16 * 7 + note.get_letter_index() as i16;
+            let current = accidentals.get(&step).copied().unwrap_or_else(|| self.key.get_modifier_for_letter(note.get_letter_index()));
+            let is_tied_over = tied_notes.as_ref().is_some_and(|(_, tied)| tied.iter().any(|(tied_note, _)| tied_note == note));
+
+            if note.get_modifier() != current && !is_tied_over {
+                shown.push((*position, note.get_modifier()));
+            }
+
+            accidentals.insert(step, note.get_modifier());
+        }
+
+        let accidental_space: f64 = shown.iter().map(|(_, modifier)| get_accidental_width(*modifier)).sum();
+        let x = x + accidental_space;
+        let mut accidental_x = x - 8.0;
+
+        for (position, modifier) in shown.iter().rev() {
+            draw_accidental(canvas, accidental_x - get_accidental_width(*modifier) / 2.0, canvas.y(*position), *modifier);
+            accidental_x -= get_accidental_width(*modifier);
+        }
+
+        for position in &positions {
+            for ledger in (*position..0).filter(|ledger| ledger % 2 == 0).chain((10..=*position).filter(|ledger| ledger % 2 == 0)) {
+                canvas.line(x - 9.0, canvas.y(ledger), x + 9.0, canvas.y(ledger), 1.2);
+            }
+
+            let y = canvas.y(*position);
+            let hollow = matches!(note_value, NoteValue::Breve | NoteValue::Whole | NoteValue::Half);
+
+            canvas.elements.push(format!(
+                "<ellipse cx=\"{x:.1}\" cy=\"{y:.1}\" rx=\"6\" ry=\"4.2\" transform=\"rotate(-20 {x:.1} {y:.1})\" fill=\"{}\" stroke=\"black\" stroke-width=\"1.5\"/>",
+                if hollow { "white" } else { "black" }
+            ));
+
+            for dot in 0..dots {
+                let dot_y = if position % 2 == 0 { canvas.y(position + 1) } else { y };
+                canvas.elements.push(format!("<circle cx=\"{:.1}\" cy=\"{dot_y:.1}\" r=\"1.8\" fill=\"black\"/>", x + 11.0 + dot as f64 * 5.0));
+            }
+        }
+
+        if note_value == NoteValue::Breve {
+            canvas.line(x - 8.0, canvas.y(highest) - 6.0, x - 8.0, canvas.y(lowest) + 6.0, 1.2);
+            canvas.line(x + 8.0, canvas.y(highest) - 6.0, x + 8.0, canvas.y(lowest) + 6.0, 1.2);
+        }
+
+        if !matches!(note_value, NoteValue::Breve | NoteValue::Whole) {
+            let (stem_x, start_y, end_y) = match stem_up {
+                true => (x + 5.6, canvas.y(lowest), canvas.y(highest) - STEM_LENGTH),
+                false => (x - 5.6, canvas.y(highest), canvas.y(lowest) + STEM_LENGTH),
+            };
+            let direction = if stem_up { 1.0 } else { -1.0 };
+
+            canvas.line(stem_x, start_y, stem_x, end_y, 1.2);
+
+            for flag in 0..get_flag_count(note_value) {
+                let flag_y = end_y + direction * flag as f64 * 7.0;
+                canvas.elements.push(format!(
+                    "<path d=\"M {stem_x:.1} {flag_y:.1} c 0 {:.1} 10 {:.1} 7 {:.1}\" fill=\"none\" stroke=\"black\" stroke-width=\"2\"/>",
+                    direction * 7.0,
+                    direction * 9.0,
+                    direction * 20.0
+                ));
+            }
+        }
+
+        if let Some((tie_x, tied)) = tied_notes {
+            for (note, tie_below) in tied {
+                if notes.contains(&note) {
+                    let y = canvas.y(self.clef.get_staff_position(note).get_steps()) + if tie_below { 6.0 } else { -6.0 };
+                    let bend = if tie_below { 6.0 } else { -6.0 };
+                    canvas.elements.push(format!(
+                        "<path d=\"M {:.1} {y:.1} Q {:.1} {:.1} {:.1} {y:.1}\" fill=\"none\" stroke=\"black\" stroke-width=\"1.2\"/>",
+                        tie_x + 4.0,
+                        (tie_x + x) / 2.0,
+                        y + bend,
+                        x - 4.0
+                    ));
+                }
+            }
+        }
+
+        let heads = notes.iter().map(|note| (*note, stem_up)).collect();
+
+        (x + get_advance(note_value, dots), (x, heads))
+    }
+}
+
+struct Canvas {
+    top_line_y: f64,
+    elements: Vec<String>,
+}
+
+impl Canvas {
+    fn y(&self, position: i16) -> f64 {
+        self.top_line_y + (8 - position) as f64 * HALF_SPACE
+    }
+
+    fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, width: f64) {
+        self.elements.push(format!("<line x1=\"{x1:.1}\" y1=\"{y1:.1}\" x2=\"{x2:.1}\" y2=\"{y2:.1}\" stroke=\"black\" stroke-width=\"{width}\"/>"));
+    }
+
+    fn rect(&mut self, x: f64, y: f64, width: f64, height: f64) {
+        self.elements.push(format!("<rect x=\"{x:.1}\" y=\"{y:.1}\" width=\"{width:.1}\" height=\"{height:.1}\" fill=\"black\"/>"));
+    }
+
+    fn text(&mut self, x: f64, y: f64, text: &str, attributes: &str) {
+        self.elements.push(format!("<text x=\"{x:.1}\" y=\"{y:.1}\" {attributes}>{text}</text>"));
+    }
+}
+
+fn draw_rest(canvas: &mut Canvas, x: f64, note_value: NoteValue, dots: u8) -> f64 {
+    match note_value {
+        NoteValue::Breve => canvas.rect(x - 3.0, canvas.y(6), 6.0, SPACE),
+        NoteValue::Whole => canvas.rect(x - 6.0, canvas.y(6), 12.0, HALF_SPACE),
+        NoteValue::Half => canvas.rect(x - 6.0, canvas.y(4) - HALF_SPACE, 12.0, HALF_SPACE),
+        _ => {
+            let glyph = match note_value {
+                NoteValue::Quarter => "\u{1D13D}",
+                NoteValue::Eighth => "\u{1D13E}",
+                NoteValue::Sixteenth => "\u{1D13F}",
+                NoteValue::ThirtySecond => "\u{1D140}",
+                _ => "\u{1D141}",
+            };
+            canvas.text(x, canvas.y(4), glyph, &format!("{GLYPH_FONT} text-anchor=\"middle\""));
+        }
+    }
+
+    for dot in 0..dots {
+        canvas.elements.push(format!("<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"1.8\" fill=\"black\"/>", x + 10.0 + dot as f64 * 5.0, canvas.y(5)));
+    }
+
+    x + get_advance(note_value, dots)
+}
+
+fn draw_accidental(canvas: &mut Canvas, x: f64, y: f64, modifier: Modifier) {
+    match modifier {
+        Modifier::Sharp => {
+            canvas.line(x - 1.8, y - 11.0, x - 1.8, y + 12.0, 1.0);
+            canvas.line(x + 1.8, y - 12.0, x + 1.8, y + 11.0, 1.0);
+            canvas.line(x - 4.5, y - 2.0, x + 4.5, y - 5.0, 2.5);
+            canvas.line(x - 4.5, y + 5.0, x + 4.5, y + 2.0, 2.5);
+        }
+        Modifier::Flat => draw_flat(canvas, x, y),
+        Modifier::Default => {
+            canvas.line(x - 3.0, y - 12.0, x - 3.0, y + 5.0, 1.0);
+            canvas.line(x + 3.0, y - 5.0, x + 3.0, y + 12.0, 1.0);
+            canvas.line(x - 3.0, y - 2.0, x + 3.0, y - 4.0, 2.5);
+            canvas.line(x - 3.0, y + 4.0, x + 3.0, y + 2.0, 2.5);
+        }
+        Modifier::DoubleSharp => {
+            canvas.line(x - 3.5, y - 3.5, x + 3.5, y + 3.5, 1.5);
+            canvas.line(x - 3.5, y + 3.5, x + 3.5, y - 3.5, 1.5);
+            for (corner_x, corner_y) in [(-3.5, -3.5), (3.5, -3.5), (-3.5, 3.5), (3.5, 3.5)] {
+                canvas.rect(x + corner_x - 1.5, y + corner_y - 1.5, 3.0, 3.0);
+            }
+        }
+        Modifier::DoubleFlat => {
+            draw_flat(canvas, x - 3.5, y);
+            draw_flat(canvas, x + 3.5, y);
+        }
+    }
+}
+
+fn draw_flat(canvas: &mut Canvas, x: f64, y: f64) {
+    canvas.line(x - 2.5, y - 15.0, x - 2.5, y + 5.0, 1.2);
+    canvas.elements.push(format!(
+        "<path d=\"M {:.1} {:.1} C {:.1} {:.1} {:.1} {:.1} {:.1} {:.1}\" fill=\"none\" stroke=\"black\" stroke-width=\"1.8\"/>",
+        x - 2.5,
+        y + 5.0,
+        x + 7.0,
+        y - 1.0,
+        x + 5.0,
+        y - 7.0,
+        x - 2.5,
+        y - 1.0
+    ));
+}
+
+fn get_accidental_width(modifier: Modifier) -> f64 {
+    match modifier {
+        Modifier::DoubleFlat => 17.0,
+        Modifier::Sharp => 12.0,
+        _ => 10.0,
+    }
+}
+
+fn get_flag_count(note_value: NoteValue) -> u8 {
+    match note_value {
+        NoteValue::Eighth => 1,
+        NoteValue::Sixteenth => 2,
+        NoteValue::ThirtySecond => 3,
+        NoteValue::SixtyFourth => 4,
+        _ => 0,
+    }
+}
+
+/// Longer notes take more room, though not proportionally more
+fn get_advance(note_value: NoteValue, dots: u8) -> f64 {
+    let quarters = note_value.get_duration().get_quarter_notes();
+
+    18.0 + 22.0 * quarters.sqrt() + dots as f64 * 5.0
+}
+
+fn get_nearest_note_value(duration: Duration) -> NoteValue {
+    NoteValue::ALL.iter().rev().copied().find(|note_value| note_value.get_duration() >= duration).unwrap_or(NoteValue::Breve)
+}
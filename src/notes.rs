@@ -9,3 +9,9 @@ pub use letter_note::LetterNote;
 
 mod number_note;
 pub use number_note::NumberNote;
+
+mod microtonal_modifier;
+pub use microtonal_modifier::MicrotonalModifier;
+
+mod microtonal_note;
+pub use microtonal_note::MicrotonalNote;
@@ -59,6 +59,73 @@ impl Distribution<LetterNote> for StandardUniform {
 }
 
 impl LetterNote {
+    pub(crate) fn from_letter_index(letter_index: u8, modifier: Modifier, octave: Octave) -> LetterNote {
+        match letter_index % 7 {
+            0 => LetterNote::C(modifier, octave),
+            1 => LetterNote::D(modifier, octave),
+            2 => LetterNote::E(modifier, octave),
+            3 => LetterNote::F(modifier, octave),
+            4 => LetterNote::G(modifier, octave),
+            5 => LetterNote::A(modifier, octave),
+            _ => LetterNote::B(modifier, octave),
+        }
+    }
+
+    pub(crate) fn try_from_letter(letter: char, modifier: Modifier, octave: Octave) -> Result<LetterNote, Box<dyn CrispiiError>> {
+        match letter.to_ascii_uppercase() {
+            'C' => Ok(LetterNote::C(modifier, octave)),
+            'D' => Ok(LetterNote::D(modifier, octave)),
+            'E' => Ok(LetterNote::E(modifier, octave)),
+            'F' => Ok(LetterNote::F(modifier, octave)),
+            'G' => Ok(LetterNote::G(modifier, octave)),
+            'A' => Ok(LetterNote::A(modifier, octave)),
+            'B' => Ok(LetterNote::B(modifier, octave)),
+            _ => Err(Box::new(InvalidArgumentError::new("letter", format!("'{letter}' is not a note letter").as_str()))),
+        }
+    }
+
+    pub(crate) fn with_modifier(self, modifier: Modifier) -> LetterNote {
+        LetterNote::from_letter_index(self.get_letter_index(), modifier, self.get_octave())
+    }
+
+    pub(crate) fn with_octave(self, octave: Octave) -> LetterNote {
+        LetterNote::from_letter_index(self.get_letter_index(), self.get_modifier(), octave)
+    }
+
+    /// Moves the note up (or down) by a number of letters, choosing whichever modifier makes it land the given number of semitones away
+    pub(crate) fn try_offset(&self, letter_steps: i8, semitones: i16) -> Result<LetterNote, Box<dyn CrispiiError>> {
+        let letter_position = self.get_letter_index() as i16 + letter_steps as i16;
+        let octave = Octave::try_from_number((self.get_octave().get_number() as i16 + letter_position.div_euclid(7)).clamp(-128, 127) as i8)?;
+        let natural = LetterNote::from_letter_index(letter_position.rem_euclid(7) as u8, Modifier::Default, octave);
+        let modifier = Modifier::try_from_semitone_offset((self.get_pitch_number() + semitones - natural.get_pitch_number()).clamp(-128, 127) as i8)?;
+
+        Ok(natural.with_modifier(modifier))
+    }
+
+    pub fn get_letter(&self) -> char {
+        match self {
+            LetterNote::C(_, _) => 'C',
+            LetterNote::D(_, _) => 'D',
+            LetterNote::E(_, _) => 'E',
+            LetterNote::F(_, _) => 'F',
+            LetterNote::G(_, _) => 'G',
+            LetterNote::A(_, _) => 'A',
+            LetterNote::B(_, _) => 'B',
+        }
+    }
+
+    pub(crate) fn get_letter_index(&self) -> u8 {
+        match self {
+            LetterNote::C(_, _) => 0,
+            LetterNote::D(_, _) => 1,
+            LetterNote::E(_, _) => 2,
+            LetterNote::F(_, _) => 3,
+            LetterNote::G(_, _) => 4,
+            LetterNote::A(_, _) => 5,
+            LetterNote::B(_, _) => 6,
+        }
+    }
+
     pub fn get_modifier(&self) -> Modifier {
         match self {
             LetterNote::C(modifier, _) => *modifier,
@@ -83,6 +150,57 @@ impl LetterNote {
         }
     }
 
+    /// The MIDI note number this note would have, without being limited to the 0-127 MIDI range (so Cb-1 is -1)
+    pub fn get_pitch_number(&self) -> i16 {
+        let letter_semitones = match self {
+            LetterNote::C(_, _) => 0,
+            LetterNote::D(_, _) => 2,
+            LetterNote::E(_, _) => 4,
+            LetterNote::F(_, _) => 5,
+            LetterNote::G(_, _) => 7,
+            LetterNote::A(_, _) => 9,
+            LetterNote::B(_, _) => 11,
+        };
+
+        (self.get_octave().get_number() as i16 + 1) * 12 + letter_semitones + self.get_modifier().get_semitone_offset() as i16
+    }
+
+    /// The note's place on the line of fifths with C at 0, G at 1 and F at -1, so enharmonic spellings such as G# (8) and Ab (-4) stay
+    /// apart. The octave is ignored
+    pub fn get_fifths(&self) -> i16 {
+        let letter_fifths = match self {
+            LetterNote::C(_, _) => 0,
+            LetterNote::D(_, _) => 2,
+            LetterNote::E(_, _) => 4,
+            LetterNote::F(_, _) => -1,
+            LetterNote::G(_, _) => 1,
+            LetterNote::A(_, _) => 3,
+            LetterNote::B(_, _) => 5,
+        };
+
+        letter_fifths + 7 * self.get_modifier().get_semitone_offset() as i16
+    }
+
+    /// The frequency in hertz in twelve tone equal temperament with A4 at 440 Hz
+    pub fn get_frequency(&self) -> f64 {
+        self.get_frequency_with_reference(440.0)
+    }
+
+    /// The frequency in hertz in twelve tone equal temperament with A4 at the given reference pitch
+    pub fn get_frequency_with_reference(&self, a4_frequency: f64) -> f64 {
+        a4_frequency * 2f64.powf((self.get_pitch_number() - 69) as f64 / 12.0)
+    }
+
+    pub fn try_get_midi_number(&self) -> Result<u8, Box<dyn CrispiiError>> {
+        let pitch_number = self.get_pitch_number();
+
+        if !(0..=127).contains(&pitch_number) {
+            return Err(Box::new(ImpossibleOperationError::new(format!("{} in octave {} is outside of the MIDI note range", self.to_string().trim_end(), self.get_octave()).as_str())));
+        }
+
+        Ok(pitch_number as u8)
+    }
+
     pub fn try_sharpen(self) -> Result<Self, Box<dyn CrispiiError>> {
         let modifier = self.get_modifier().try_sharpen()?;
 
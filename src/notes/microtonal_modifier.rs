@@ -0,0 +1,145 @@
+use std::fmt::Display;
+
+use crispii_errors::{CrispiiError, ImpossibleOperationError, InvalidArgumentError};
+
+use crate::notes::Modifier;
+
+/// A modifier that can also raise or lower a note by quarter tones, as used for Arabic maqam and Turkish makam
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum MicrotonalModifier {
+    DoubleFlat,
+    SesquiFlat,
+    Flat,
+    HalfFlat,
+    #[default]
+    Default,
+    HalfSharp,
+    Sharp,
+    SesquiSharp,
+    DoubleSharp,
+}
+
+impl Display for MicrotonalModifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MicrotonalModifier::DoubleFlat => write!(f, "Double Flat"),
+            MicrotonalModifier::SesquiFlat => write!(f, "Sesquiflat"),
+            MicrotonalModifier::Flat => write!(f, "Flat"),
+            MicrotonalModifier::HalfFlat => write!(f, "Half Flat"),
+            MicrotonalModifier::Default => write!(f, ""),
+            MicrotonalModifier::HalfSharp => write!(f, "Half Sharp"),
+            MicrotonalModifier::Sharp => write!(f, "Sharp"),
+            MicrotonalModifier::SesquiSharp => write!(f, "Sesquisharp"),
+            MicrotonalModifier::DoubleSharp => write!(f, "Double Sharp"),
+        }
+    }
+}
+
+impl MicrotonalModifier {
+    pub const ALL: [MicrotonalModifier; 9] = [
+        MicrotonalModifier::DoubleFlat,
+        MicrotonalModifier::SesquiFlat,
+        MicrotonalModifier::Flat,
+        MicrotonalModifier::HalfFlat,
+        MicrotonalModifier::Default,
+        MicrotonalModifier::HalfSharp,
+        MicrotonalModifier::Sharp,
+        MicrotonalModifier::SesquiSharp,
+        MicrotonalModifier::DoubleSharp,
+    ];
+
+    pub fn from_modifier(modifier: Modifier) -> MicrotonalModifier {
+        match modifier {
+            Modifier::DoubleFlat => MicrotonalModifier::DoubleFlat,
+            Modifier::Flat => MicrotonalModifier::Flat,
+            Modifier::Default => MicrotonalModifier::Default,
+            Modifier::Sharp => MicrotonalModifier::Sharp,
+            Modifier::DoubleSharp => MicrotonalModifier::DoubleSharp,
+        }
+    }
+
+    /// The plain modifier this stands for, failing for the quarter tone modifiers
+    pub fn try_to_modifier(&self) -> Result<Modifier, Box<dyn CrispiiError>> {
+        match self {
+            MicrotonalModifier::DoubleFlat => Ok(Modifier::DoubleFlat),
+            MicrotonalModifier::Flat => Ok(Modifier::Flat),
+            MicrotonalModifier::Default => Ok(Modifier::Default),
+            MicrotonalModifier::Sharp => Ok(Modifier::Sharp),
+            MicrotonalModifier::DoubleSharp => Ok(Modifier::DoubleSharp),
+            _ => Err(Box::new(ImpossibleOperationError::new(format!("A {self} Modifier is not a whole number of semitones").as_str()))),
+        }
+    }
+
+    pub fn get_quarter_tone_offset(&self) -> i8 {
+        match self {
+            MicrotonalModifier::DoubleFlat => -4,
+            MicrotonalModifier::SesquiFlat => -3,
+            MicrotonalModifier::Flat => -2,
+            MicrotonalModifier::HalfFlat => -1,
+            MicrotonalModifier::Default => 0,
+            MicrotonalModifier::HalfSharp => 1,
+            MicrotonalModifier::Sharp => 2,
+            MicrotonalModifier::SesquiSharp => 3,
+            MicrotonalModifier::DoubleSharp => 4,
+        }
+    }
+
+    pub fn try_from_quarter_tone_offset(offset: i8) -> Result<MicrotonalModifier, Box<dyn CrispiiError>> {
+        match offset {
+            -4 => Ok(MicrotonalModifier::DoubleFlat),
+            -3 => Ok(MicrotonalModifier::SesquiFlat),
+            -2 => Ok(MicrotonalModifier::Flat),
+            -1 => Ok(MicrotonalModifier::HalfFlat),
+            0 => Ok(MicrotonalModifier::Default),
+            1 => Ok(MicrotonalModifier::HalfSharp),
+            2 => Ok(MicrotonalModifier::Sharp),
+            3 => Ok(MicrotonalModifier::SesquiSharp),
+            4 => Ok(MicrotonalModifier::DoubleSharp),
+            _ => Err(Box::new(InvalidArgumentError::new("offset", "Must be between -4 and 4 (inclusive)"))),
+        }
+    }
+
+    /// ASCII symbols, using '+' for a quarter tone sharp and 'd' (a flat turned round) for a quarter tone flat
+    pub fn get_symbol(&self) -> &'static str {
+        match self {
+            MicrotonalModifier::DoubleFlat => "bb",
+            MicrotonalModifier::SesquiFlat => "db",
+            MicrotonalModifier::Flat => "b",
+            MicrotonalModifier::HalfFlat => "d",
+            MicrotonalModifier::Default => "",
+            MicrotonalModifier::HalfSharp => "+",
+            MicrotonalModifier::Sharp => "#",
+            MicrotonalModifier::SesquiSharp => "#+",
+            MicrotonalModifier::DoubleSharp => "##",
+        }
+    }
+
+    /// Reads a leading run of modifier symbols, adding up sharps and flats (#, x, b, ♯, ♭, 𝄪, 𝄫) and quarter tones (+, d, 𝄲, 𝄳), and
+    /// returns the modifier and the number of bytes it took up
+    pub(crate) fn parse_symbol_prefix(text: &str) -> (MicrotonalModifier, usize) {
+        let mut offset = 0i8;
+        let mut length = 0;
+
+        for character in text.chars() {
+            let character_offset = match character {
+                '+' | '𝄲' => 1,
+                '#' | '♯' => 2,
+                'x' | '𝄪' => 4,
+                'd' | '𝄳' => -1,
+                'b' | '♭' => -2,
+                '𝄫' => -4,
+                _ => break,
+            };
+
+            match MicrotonalModifier::try_from_quarter_tone_offset(offset + character_offset) {
+                Ok(_) => {
+                    offset += character_offset;
+                    length += character.len_utf8();
+                }
+                Err(_) => break,
+            }
+        }
+
+        (MicrotonalModifier::try_from_quarter_tone_offset(offset).expect("Offsets beyond doubles are never accepted"), length)
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt::Display;
+
+use crispii_errors::{CrispiiError, ImpossibleOperationError, InvalidArgumentError};
+
+use crate::notes::{LetterNote, MicrotonalModifier, Modifier, Octave};
+
+/// A spelt note that can sit between the semitones, with a quarter tone modifier and a further deviation in cents
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MicrotonalNote {
+    natural: LetterNote,
+    modifier: MicrotonalModifier,
+    cents: f64,
+}
+
+impl Default for MicrotonalNote {
+    fn default() -> Self {
+        MicrotonalNote::from_letter_note(LetterNote::default())
+    }
+}
+
+impl Display for MicrotonalNote {
+    /// Such as "E Half Flat" or "B Flat -14 Cents". The octave is left out, as it is for letter notes
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.natural.get_letter())?;
+
+        if self.modifier != MicrotonalModifier::Default {
+            write!(f, " {}", self.modifier)?;
+        }
+
+        if self.cents != 0.0 {
+            write!(f, " {:+} Cents", self.cents)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl MicrotonalNote {
+    /// Takes the letter and octave of the given note, ignoring its modifier. The cents deviation must be between -100 and 100
+    pub fn try_new(note: LetterNote, modifier: MicrotonalModifier, cents: f64) -> Result<MicrotonalNote, Box<dyn CrispiiError>> {
+        if !(-100.0..=100.0).contains(&cents) {
+            return Err(Box::new(InvalidArgumentError::new("cents", "Must be between -100 and 100 (inclusive)")));
+        }
+
+        Ok(MicrotonalNote { natural: note.with_modifier(Modifier::Default), modifier, cents })
+    }
+
+    pub fn from_letter_note(note: LetterNote) -> MicrotonalNote {
+        MicrotonalNote { natural: note.with_modifier(Modifier::Default), modifier: MicrotonalModifier::from_modifier(note.get_modifier()), cents: 0.0 }
+    }
+
+    /// Reads notes written like "Ed4", "C#+5", "Bb3 -14c" or "F𝄲4+20c": a capital letter, any modifier symbols, the octave and an
+    /// optional signed cents deviation ending in 'c'
+    pub fn try_parse(text: &str) -> Result<MicrotonalNote, Box<dyn CrispiiError>> {
+        let text = text.trim();
+        let letter = text.chars().next()
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", "Notes must start with a letter")) })?;
+
+        if !letter.is_ascii_uppercase() {
+            return Err(Box::new(InvalidArgumentError::new("text", format!("Note letters must be capitals, not '{letter}'").as_str())));
+        }
+
+        let natural = LetterNote::try_from_letter(letter, Modifier::Default, Octave::default())?;
+        let rest = &text[letter.len_utf8()..];
+        let (modifier, length) = MicrotonalModifier::parse_symbol_prefix(rest);
+        let rest = &rest[length..];
+
+        let sign_length = match rest.starts_with('-') {
+            true => 1,
+            false => 0,
+        };
+        let octave_length = sign_length + rest[sign_length..].chars().take_while(char::is_ascii_digit).count();
+        let octave_number: i8 = rest[..octave_length].parse()
+            .map_err(|_| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("'{text}' is missing an octave number after the letter and modifier").as_str())) })?;
+        let octave = Octave::try_from_number(octave_number)?;
+
+        let cents_text = rest[octave_length..].trim();
+        let cents = match cents_text.strip_suffix('c') {
+            _ if cents_text.is_empty() => 0.0,
+            Some(number) if number.starts_with(['+', '-']) => number.parse()
+                .map_err(|_| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("'{cents_text}' is not a cents deviation").as_str())) })?,
+            _ => return Err(Box::new(InvalidArgumentError::new("text", format!("Expected a signed cents deviation such as '+14c', not '{cents_text}'").as_str()))),
+        };
+
+        MicrotonalNote::try_new(natural.with_octave(octave), modifier, cents)
+    }
+
+    pub fn get_letter(&self) -> char {
+        self.natural.get_letter()
+    }
+
+    pub fn get_octave(&self) -> Octave {
+        self.natural.get_octave()
+    }
+
+    pub fn get_modifier(&self) -> MicrotonalModifier {
+        self.modifier
+    }
+
+    pub fn get_cents(&self) -> f64 {
+        self.cents
+    }
+
+    /// The note in the form `try_parse` reads, such as "Ed4" or "Bb3 -14c"
+    pub fn get_symbol(&self) -> String {
+        let symbol = format!("{}{}{}", self.natural.get_letter(), self.modifier.get_symbol(), self.natural.get_octave());
+
+        match self.cents == 0.0 {
+            true => symbol,
+            false => format!("{symbol} {:+}c", self.cents),
+        }
+    }
+
+    /// The MIDI style pitch number this note would have, with a fractional part for quarter tones and cents (so E half flat 4 is 63.5)
+    pub fn get_pitch(&self) -> f64 {
+        self.natural.get_pitch_number() as f64 + self.modifier.get_quarter_tone_offset() as f64 / 2.0 + self.cents / 100.0
+    }
+
+    /// The frequency in hertz, measuring quarter tones and cents from twelve tone equal temperament with A4 at 440 Hz
+    pub fn get_frequency(&self) -> f64 {
+        self.get_frequency_with_reference(440.0)
+    }
+
+    /// The frequency in hertz, measuring quarter tones and cents from twelve tone equal temperament with A4 at the given reference pitch
+    pub fn get_frequency_with_reference(&self, a4_frequency: f64) -> f64 {
+        a4_frequency * 2f64.powf((self.get_pitch() - 69.0) / 12.0)
+    }
+
+    /// The plain letter note, failing if the note is a quarter tone or has a cents deviation
+    pub fn try_to_letter_note(&self) -> Result<LetterNote, Box<dyn CrispiiError>> {
+        if self.cents != 0.0 {
+            return Err(Box::new(ImpossibleOperationError::new(format!("{self} has a cents deviation, so is not a plain letter note").as_str())));
+        }
+
+        Ok(self.natural.with_modifier(self.modifier.try_to_modifier()?))
+    }
+}
@@ -1,7 +1,7 @@
 use std::fmt::Display;
 use rand::distr::{Distribution, StandardUniform};
 
-use crispii_errors::{CrispiiError, ImpossibleOperationError};
+use crispii_errors::{CrispiiError, ImpossibleOperationError, InvalidArgumentError};
 
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
 pub enum Modifier {
@@ -57,4 +57,61 @@ impl Modifier {
             Modifier::DoubleSharp => Ok(Modifier::Sharp),
         }
     }
+
+    pub fn get_semitone_offset(&self) -> i8 {
+        match self {
+            Modifier::DoubleFlat => -2,
+            Modifier::Flat => -1,
+            Modifier::Default => 0,
+            Modifier::Sharp => 1,
+            Modifier::DoubleSharp => 2,
+        }
+    }
+
+    pub fn try_from_semitone_offset(offset: i8) -> Result<Modifier, Box<dyn CrispiiError>> {
+        match offset {
+            -2 => Ok(Modifier::DoubleFlat),
+            -1 => Ok(Modifier::Flat),
+            0 => Ok(Modifier::Default),
+            1 => Ok(Modifier::Sharp),
+            2 => Ok(Modifier::DoubleSharp),
+            _ => Err(Box::new(InvalidArgumentError::new("offset", "Must be between -2 and 2 (inclusive)"))),
+        }
+    }
+
+    pub fn get_symbol(&self) -> &'static str {
+        match self {
+            Modifier::DoubleFlat => "bb",
+            Modifier::Flat => "b",
+            Modifier::Default => "",
+            Modifier::Sharp => "#",
+            Modifier::DoubleSharp => "##",
+        }
+    }
+
+    /// Reads a leading run of sharp or flat symbols (#, x, b, ♯, ♭, 𝄪, 𝄫), returning the modifier and the number of bytes it took up
+    pub(crate) fn parse_symbol_prefix(text: &str) -> (Modifier, usize) {
+        let mut offset = 0i8;
+        let mut length = 0;
+
+        for character in text.chars() {
+            let character_offset = match character {
+                '#' | '♯' => 1,
+                'x' | '𝄪' => 2,
+                'b' | '♭' => -1,
+                '𝄫' => -2,
+                _ => break,
+            };
+
+            match Modifier::try_from_semitone_offset(offset + character_offset) {
+                Ok(_) => {
+                    offset += character_offset;
+                    length += character.len_utf8();
+                }
+                Err(_) => break,
+            }
+        }
+
+        (Modifier::try_from_semitone_offset(offset).expect("Offsets beyond doubles are never accepted"), length)
+    }
 }
@@ -51,6 +51,31 @@ impl Distribution<NumberNote> for StandardUniform {
 }
 
 impl NumberNote {
+    /// Degree 1 is the tonic. Degrees are taken modulo 7, so 8 is the tonic again
+    pub(crate) fn from_degree(degree: u8, modifier: Modifier, octave: Octave) -> NumberNote {
+        match (degree + 6) % 7 {
+            0 => NumberNote::One(modifier, octave),
+            1 => NumberNote::Two(modifier, octave),
+            2 => NumberNote::Three(modifier, octave),
+            3 => NumberNote::Four(modifier, octave),
+            4 => NumberNote::Five(modifier, octave),
+            5 => NumberNote::Six(modifier, octave),
+            _ => NumberNote::Seven(modifier, octave),
+        }
+    }
+
+    pub fn get_degree(&self) -> u8 {
+        match self {
+            NumberNote::One(_, _) => 1,
+            NumberNote::Two(_, _) => 2,
+            NumberNote::Three(_, _) => 3,
+            NumberNote::Four(_, _) => 4,
+            NumberNote::Five(_, _) => 5,
+            NumberNote::Six(_, _) => 6,
+            NumberNote::Seven(_, _) => 7,
+        }
+    }
+
     pub fn get_modifier(&self) -> Modifier {
         match self {
             NumberNote::One(modifier, _) => *modifier,
@@ -1,6 +1,8 @@
 use std::fmt::Display;
 use rand::distr::{Distribution, StandardUniform};
 
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
 pub enum Octave {
     MinusOne,
@@ -52,3 +54,38 @@ impl Distribution<Octave> for StandardUniform {
         }
     }
 }
+
+impl Octave {
+    pub fn get_number(&self) -> i8 {
+        match self {
+            Octave::MinusOne => -1,
+            Octave::Zero => 0,
+            Octave::One => 1,
+            Octave::Two => 2,
+            Octave::Three => 3,
+            Octave::Four => 4,
+            Octave::Five => 5,
+            Octave::Six => 6,
+            Octave::Seven => 7,
+            Octave::Eight => 8,
+            Octave::Nine => 9,
+        }
+    }
+
+    pub fn try_from_number(number: i8) -> Result<Octave, Box<dyn CrispiiError>> {
+        match number {
+            -1 => Ok(Octave::MinusOne),
+            0 => Ok(Octave::Zero),
+            1 => Ok(Octave::One),
+            2 => Ok(Octave::Two),
+            3 => Ok(Octave::Three),
+            4 => Ok(Octave::Four),
+            5 => Ok(Octave::Five),
+            6 => Ok(Octave::Six),
+            7 => Ok(Octave::Seven),
+            8 => Ok(Octave::Eight),
+            9 => Ok(Octave::Nine),
+            _ => Err(Box::new(InvalidArgumentError::new("number", "Must be between -1 and 9 (inclusive)"))),
+        }
+    }
+}
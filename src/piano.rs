@@ -0,0 +1,8 @@
+mod key_colour;
+pub use key_colour::KeyColour;
+
+mod key_geometry;
+pub use key_geometry::KeyGeometry;
+
+mod piano_keyboard;
+pub use piano_keyboard::PianoKeyboard;
@@ -0,0 +1,33 @@
+use std::fmt::Display;
+
+use crate::notes::LetterNote;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum KeyColour {
+    #[default]
+    White,
+    Black,
+}
+
+impl Display for KeyColour {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyColour::White => write!(f, "White"),
+            KeyColour::Black => write!(f, "Black"),
+        }
+    }
+}
+
+impl KeyColour {
+    /// Goes by the key that sounds rather than the spelling, so E# and Cb are white keys while C## is black
+    pub fn from_note(note: LetterNote) -> KeyColour {
+        KeyColour::from_pitch_number(note.get_pitch_number())
+    }
+
+    pub fn from_pitch_number(pitch_number: i16) -> KeyColour {
+        match pitch_number.rem_euclid(12) {
+            1 | 3 | 6 | 8 | 10 => KeyColour::Black,
+            _ => KeyColour::White,
+        }
+    }
+}
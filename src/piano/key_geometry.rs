@@ -0,0 +1,45 @@
+use std::fmt::Display;
+
+use crate::piano::KeyColour;
+
+/// Where a key sits on the keyboard, measured in white key widths from the keyboard's left edge. The height is a fraction of a white key's
+/// length
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default)]
+pub struct KeyGeometry {
+    colour: KeyColour,
+    x: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Display for KeyGeometry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} key at {:.2} ({:.2} wide, {:.2} long)", self.colour, self.x, self.width, self.height)
+    }
+}
+
+impl KeyGeometry {
+    pub fn new(colour: KeyColour, x: f64, width: f64, height: f64) -> KeyGeometry {
+        KeyGeometry { colour, x, width, height }
+    }
+
+    pub fn get_colour(&self) -> KeyColour {
+        self.colour
+    }
+
+    pub fn get_x(&self) -> f64 {
+        self.x
+    }
+
+    pub fn get_width(&self) -> f64 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> f64 {
+        self.height
+    }
+
+    pub fn get_centre(&self) -> f64 {
+        self.x + self.width / 2.0
+    }
+}
@@ -0,0 +1,132 @@
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::keys::Key;
+use crate::notes::LetterNote;
+use crate::piano::{KeyColour, KeyGeometry};
+
+const BLACK_KEY_WIDTH: f64 = 0.6;
+const BLACK_KEY_HEIGHT: f64 = 0.63;
+/// The white key at (or just left of) each pitch class, counted from C
+const WHITE_KEY_INDEX: [i16; 12] = [0, 0, 1, 1, 2, 3, 3, 4, 4, 5, 5, 6];
+/// Black key centres, in white key widths from the left edge of C. C# and D# lean away from each other, as do F# and A#, which is how real
+/// keyboards space them
+const BLACK_KEY_CENTRE: [f64; 12] = [0.0, 0.9, 0.0, 2.1, 0.0, 0.0, 3.88, 0.0, 5.0, 0.0, 6.12, 0.0];
+
+/// A run of piano keys between two MIDI numbers (inclusive)
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct PianoKeyboard {
+    lowest: u8,
+    highest: u8,
+}
+
+impl Default for PianoKeyboard {
+    fn default() -> Self {
+        PianoKeyboard::with_88_keys()
+    }
+}
+
+impl PianoKeyboard {
+    pub fn try_new(lowest: LetterNote, highest: LetterNote) -> Result<PianoKeyboard, Box<dyn CrispiiError>> {
+        let lowest = lowest.try_get_midi_number()?;
+        let highest = highest.try_get_midi_number()?;
+
+        if lowest > highest {
+            return Err(Box::new(InvalidArgumentError::new("highest", "Must not be below the lowest key")));
+        }
+
+        Ok(PianoKeyboard { lowest, highest })
+    }
+
+    /// C2 to C7
+    pub fn with_61_keys() -> PianoKeyboard {
+        PianoKeyboard { lowest: 36, highest: 96 }
+    }
+
+    /// E1 to G7
+    pub fn with_76_keys() -> PianoKeyboard {
+        PianoKeyboard { lowest: 28, highest: 103 }
+    }
+
+    /// A0 to C8
+    pub fn with_88_keys() -> PianoKeyboard {
+        PianoKeyboard { lowest: 21, highest: 108 }
+    }
+
+    pub fn get_lowest_midi_number(&self) -> u8 {
+        self.lowest
+    }
+
+    pub fn get_highest_midi_number(&self) -> u8 {
+        self.highest
+    }
+
+    pub fn get_key_count(&self) -> usize {
+        (self.highest - self.lowest) as usize + 1
+    }
+
+    pub fn get_white_key_count(&self) -> usize {
+        (self.lowest..=self.highest).filter(|midi_number| KeyColour::from_pitch_number(*midi_number as i16) == KeyColour::White).count()
+    }
+
+    /// The width of the whole keyboard in white key widths
+    pub fn get_width(&self) -> f64 {
+        let right = self.get_geometry(self.highest as i16);
+
+        right.get_x() + right.get_width()
+    }
+
+    /// Whether the key that sounds this note is on the keyboard, whatever its spelling
+    pub fn contains(&self, note: LetterNote) -> bool {
+        (self.lowest as i16..=self.highest as i16).contains(&note.get_pitch_number())
+    }
+
+    /// Counts keys of either colour from 0 at the lowest key
+    pub fn try_get_key_index(&self, note: LetterNote) -> Result<usize, Box<dyn CrispiiError>> {
+        match self.contains(note) {
+            true => Ok((note.get_pitch_number() - self.lowest as i16) as usize),
+            false => Err(Box::new(InvalidArgumentError::new("note", format!("{}{} is not on this keyboard", note.to_string().trim_end(), note.get_octave()).as_str()))),
+        }
+    }
+
+    /// The note played by a key, spelt to suit the given key signature
+    pub fn try_get_note(&self, key_index: usize, key: Key) -> Result<LetterNote, Box<dyn CrispiiError>> {
+        if key_index >= self.get_key_count() {
+            return Err(Box::new(InvalidArgumentError::new("key_index", format!("Must be below {}", self.get_key_count()).as_str())));
+        }
+
+        key.try_spell(self.lowest as i16 + key_index as i16)
+    }
+
+    /// Every note on the keyboard from the bottom up, spelt to suit the given key signature
+    pub fn get_notes(&self, key: Key) -> Vec<LetterNote> {
+        (self.lowest as i16..=self.highest as i16).filter_map(|pitch_number| key.try_spell(pitch_number).ok()).collect()
+    }
+
+    pub fn try_get_geometry(&self, note: LetterNote) -> Result<KeyGeometry, Box<dyn CrispiiError>> {
+        self.try_get_key_index(note)?;
+
+        Ok(self.get_geometry(note.get_pitch_number()))
+    }
+
+    fn get_geometry(&self, pitch_number: i16) -> KeyGeometry {
+        let origin = get_left_edge(self.lowest as i16);
+        let colour = KeyColour::from_pitch_number(pitch_number);
+        let (width, height) = match colour {
+            KeyColour::White => (1.0, 1.0),
+            KeyColour::Black => (BLACK_KEY_WIDTH, BLACK_KEY_HEIGHT),
+        };
+
+        KeyGeometry::new(colour, get_left_edge(pitch_number) - origin, width, height)
+    }
+}
+
+/// The left edge of a key, in white key widths from C-1
+fn get_left_edge(pitch_number: i16) -> f64 {
+    let octave = pitch_number.div_euclid(12);
+    let pitch_class = pitch_number.rem_euclid(12) as usize;
+
+    match KeyColour::from_pitch_number(pitch_number) {
+        KeyColour::White => (octave * 7 + WHITE_KEY_INDEX[pitch_class]) as f64,
+        KeyColour::Black => octave as f64 * 7.0 + BLACK_KEY_CENTRE[pitch_class] - BLACK_KEY_WIDTH / 2.0,
+    }
+}
@@ -0,0 +1,11 @@
+mod note_value;
+pub use note_value::NoteValue;
+
+mod duration;
+pub use duration::Duration;
+
+mod time_signature;
+pub use time_signature::TimeSignature;
+
+mod tempo;
+pub use tempo::Tempo;
@@ -0,0 +1,172 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::Add;
+
+use crispii_errors::{CrispiiError, ImpossibleOperationError, InvalidArgumentError};
+
+use crate::rhythm::NoteValue;
+
+/// A length of time measured in whole notes, so a quarter note is 1/4 and a dotted half note is 3/4
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Duration {
+    numerator: u32,
+    denominator: u32,
+}
+
+impl Default for Duration {
+    fn default() -> Self {
+        NoteValue::default().get_duration()
+    }
+}
+
+impl Display for Duration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+impl Ord for Duration {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numerator as u64 * other.denominator as u64).cmp(&(other.numerator as u64 * self.denominator as u64))
+    }
+}
+
+impl PartialOrd for Duration {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Duration {
+    type Output = Duration;
+
+    /// Panics if the sum's numerator or denominator does not fit in 32 bits once reduced. `try_add` returns an error instead
+    fn add(self, rhs: Self) -> Self::Output {
+        self.try_add(rhs).unwrap_or_else(|error| panic!("{error}"))
+    }
+}
+
+impl Duration {
+    pub fn try_new(numerator: u32, denominator: u32) -> Result<Duration, Box<dyn CrispiiError>> {
+        if denominator == 0 {
+            return Err(Box::new(InvalidArgumentError::new("denominator", "Must not be zero")));
+        }
+
+        Ok(Duration::reduce(numerator as u64, denominator as u64))
+    }
+
+    pub fn zero() -> Duration {
+        Duration { numerator: 0, denominator: 1 }
+    }
+
+    pub fn from_ticks(ticks: u32, ticks_per_quarter: u16) -> Duration {
+        Duration::reduce(ticks as u64, ticks_per_quarter.max(1) as u64 * 4)
+    }
+
+    pub fn get_numerator(&self) -> u32 {
+        self.numerator
+    }
+
+    pub fn get_denominator(&self) -> u32 {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    /// Rounds to the nearest tick
+    pub fn get_ticks(&self, ticks_per_quarter: u16) -> u32 {
+        let numerator = self.numerator as u64 * ticks_per_quarter as u64 * 4;
+        let denominator = self.denominator as u64;
+
+        ((numerator + denominator / 2) / denominator) as u32
+    }
+
+    pub fn get_quarter_notes(&self) -> f64 {
+        self.numerator as f64 * 4.0 / self.denominator as f64
+    }
+
+    /// Fails if the sum's numerator or denominator does not fit in 32 bits once reduced, as with 1/65537 + 1/65539
+    pub fn try_add(self, other: Duration) -> Result<Duration, Box<dyn CrispiiError>> {
+        let numerator = self.numerator as u64 * other.denominator as u64 + other.numerator as u64 * self.denominator as u64;
+        let denominator = self.denominator as u64 * other.denominator as u64;
+
+        Duration::try_reduce(numerator, denominator)
+    }
+
+    pub fn try_subtract(self, other: Duration) -> Result<Duration, Box<dyn CrispiiError>> {
+        if other > self {
+            return Err(Box::new(ImpossibleOperationError::new(format!("Cannot subtract {other} from the shorter duration {self}").as_str())));
+        }
+
+        let numerator = self.numerator as u64 * other.denominator as u64 - other.numerator as u64 * self.denominator as u64;
+        let denominator = self.denominator as u64 * other.denominator as u64;
+
+        Duration::try_reduce(numerator, denominator)
+    }
+
+    /// Panics if the product's numerator or denominator does not fit in 32 bits once reduced. `try_multiply` returns an error instead
+    pub fn multiply(self, numerator: u32, denominator: u32) -> Duration {
+        self.try_multiply(numerator, denominator).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Scales by numerator/denominator (a denominator of 0 counts as 1), failing if the result does not fit in 32 bits once reduced
+    pub fn try_multiply(self, numerator: u32, denominator: u32) -> Result<Duration, Box<dyn CrispiiError>> {
+        Duration::try_reduce(self.numerator as u64 * numerator as u64, self.denominator as u64 * denominator.max(1) as u64)
+    }
+
+    /// Returns the note value and number of dots if this duration can be written as a single (possibly dotted) note
+    pub fn get_note_value(&self) -> Option<(NoteValue, u8)> {
+        NoteValue::ALL.iter().find_map(|note_value| {
+            (0..=3).find(|dots| note_value.get_dotted_duration(*dots) == *self).map(|dots| (*note_value, dots))
+        })
+    }
+
+    /// Breaks this duration into the fewest (possibly dotted) notes that, tied together, add up to it
+    pub fn try_split_into_note_values(&self) -> Result<Vec<(NoteValue, u8)>, Box<dyn CrispiiError>> {
+        let smallest = NoteValue::SixtyFourth.get_duration();
+
+        if self.is_zero() || !self.denominator.is_power_of_two() || self.denominator > smallest.denominator {
+            return Err(Box::new(ImpossibleOperationError::new(format!("A duration of {self} cannot be written with tied note values").as_str())));
+        }
+
+        let mut remaining = *self;
+        let mut note_values = Vec::new();
+
+        while !remaining.is_zero() {
+            let (note_value, dots) = NoteValue::ALL.iter()
+                .flat_map(|note_value| (0..=2).rev().map(move |dots| (*note_value, dots)))
+                .find(|(note_value, dots)| note_value.get_dotted_duration(*dots) <= remaining)
+                .expect("Durations smaller than a sixty fourth note have already been factored out");
+
+            remaining = remaining.try_subtract(note_value.get_dotted_duration(dots))?;
+            note_values.push((note_value, dots));
+        }
+
+        Ok(note_values)
+    }
+
+    /// Only for fractions whose parts already fit in 32 bits, which reducing can never make larger
+    fn reduce(numerator: u64, denominator: u64) -> Duration {
+        Duration::try_reduce(numerator, denominator).expect("Parts that fit in 32 bits still fit once reduced")
+    }
+
+    fn try_reduce(numerator: u64, denominator: u64) -> Result<Duration, Box<dyn CrispiiError>> {
+        let divisor = greatest_common_divisor(numerator, denominator).max(1);
+        let (numerator, denominator) = (numerator / divisor, denominator / divisor);
+
+        match (u32::try_from(numerator), u32::try_from(denominator)) {
+            (Ok(numerator), Ok(denominator)) => Ok(Duration { numerator, denominator }),
+            _ => Err(Box::new(ImpossibleOperationError::new(format!("{numerator}/{denominator} is too fine a duration to be stored in 32 bit parts").as_str()))),
+        }
+    }
+}
+
+fn greatest_common_divisor(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
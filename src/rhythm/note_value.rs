@@ -0,0 +1,64 @@
+use std::fmt::Display;
+
+use crate::rhythm::Duration;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum NoteValue {
+    Breve,
+    Whole,
+    Half,
+    #[default]
+    Quarter,
+    Eighth,
+    Sixteenth,
+    ThirtySecond,
+    SixtyFourth,
+}
+
+impl Display for NoteValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NoteValue::Breve => write!(f, "Breve"),
+            NoteValue::Whole => write!(f, "Whole"),
+            NoteValue::Half => write!(f, "Half"),
+            NoteValue::Quarter => write!(f, "Quarter"),
+            NoteValue::Eighth => write!(f, "Eighth"),
+            NoteValue::Sixteenth => write!(f, "Sixteenth"),
+            NoteValue::ThirtySecond => write!(f, "Thirty Second"),
+            NoteValue::SixtyFourth => write!(f, "Sixty Fourth"),
+        }
+    }
+}
+
+impl NoteValue {
+    pub const ALL: [NoteValue; 8] = [
+        NoteValue::Breve,
+        NoteValue::Whole,
+        NoteValue::Half,
+        NoteValue::Quarter,
+        NoteValue::Eighth,
+        NoteValue::Sixteenth,
+        NoteValue::ThirtySecond,
+        NoteValue::SixtyFourth,
+    ];
+
+    pub fn get_duration(&self) -> Duration {
+        self.get_dotted_duration(0)
+    }
+
+    pub fn get_dotted_duration(&self, dots: u8) -> Duration {
+        let (numerator, denominator) = match self {
+            NoteValue::Breve => (2, 1),
+            NoteValue::Whole => (1, 1),
+            NoteValue::Half => (1, 2),
+            NoteValue::Quarter => (1, 4),
+            NoteValue::Eighth => (1, 8),
+            NoteValue::Sixteenth => (1, 16),
+            NoteValue::ThirtySecond => (1, 32),
+            NoteValue::SixtyFourth => (1, 64),
+        };
+        let dots = dots.min(6) as u32;
+
+        Duration::try_new(numerator * ((1 << (dots + 1)) - 1), denominator << dots).expect("Note value denominators are never zero")
+    }
+}
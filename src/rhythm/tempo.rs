@@ -0,0 +1,64 @@
+use std::fmt::Display;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::rhythm::Duration;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Tempo {
+    beat: Duration,
+    beats_per_minute: u16,
+}
+
+impl Default for Tempo {
+    fn default() -> Self {
+        Self { beat: Duration::default(), beats_per_minute: 120 }
+    }
+}
+
+impl Display for Tempo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {}", self.beat, self.beats_per_minute)
+    }
+}
+
+impl Tempo {
+    pub fn try_new(beat: Duration, beats_per_minute: u16) -> Result<Tempo, Box<dyn CrispiiError>> {
+        if beat.is_zero() {
+            return Err(Box::new(InvalidArgumentError::new("beat", "Must not be zero")));
+        }
+
+        if beats_per_minute == 0 {
+            return Err(Box::new(InvalidArgumentError::new("beats_per_minute", "Must be greater than zero")));
+        }
+
+        Ok(Tempo { beat, beats_per_minute })
+    }
+
+    /// Builds a quarter note tempo, rounded to the nearest beat per minute
+    pub fn from_microseconds_per_quarter(microseconds_per_quarter: u32) -> Tempo {
+        let beats_per_minute = (60_000_000.0 / microseconds_per_quarter.max(1) as f64).round().clamp(1.0, u16::MAX as f64) as u16;
+
+        Tempo { beat: Duration::default(), beats_per_minute }
+    }
+
+    pub fn get_beat(&self) -> Duration {
+        self.beat
+    }
+
+    pub fn get_beats_per_minute(&self) -> u16 {
+        self.beats_per_minute
+    }
+
+    pub fn get_quarter_notes_per_minute(&self) -> f64 {
+        self.beats_per_minute as f64 * self.beat.get_quarter_notes()
+    }
+
+    pub fn get_microseconds_per_quarter(&self) -> u32 {
+        (60_000_000.0 / self.get_quarter_notes_per_minute()).round() as u32
+    }
+
+    pub fn get_seconds(&self, duration: Duration) -> f64 {
+        duration.get_quarter_notes() * 60.0 / self.get_quarter_notes_per_minute()
+    }
+}
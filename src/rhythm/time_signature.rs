@@ -0,0 +1,64 @@
+use std::fmt::Display;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::rhythm::Duration;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct TimeSignature {
+    numerator: u8,
+    denominator: u8,
+}
+
+impl Default for TimeSignature {
+    fn default() -> Self {
+        Self { numerator: 4, denominator: 4 }
+    }
+}
+
+impl Display for TimeSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+impl TimeSignature {
+    pub fn try_new(numerator: u8, denominator: u8) -> Result<TimeSignature, Box<dyn CrispiiError>> {
+        if numerator == 0 {
+            return Err(Box::new(InvalidArgumentError::new("numerator", "Must be greater than zero")));
+        }
+
+        if !denominator.is_power_of_two() || denominator > 64 {
+            return Err(Box::new(InvalidArgumentError::new("denominator", "Must be a power of two no greater than 64")));
+        }
+
+        Ok(TimeSignature { numerator, denominator })
+    }
+
+    pub fn get_numerator(&self) -> u8 {
+        self.numerator
+    }
+
+    pub fn get_denominator(&self) -> u8 {
+        self.denominator
+    }
+
+    pub fn get_measure_duration(&self) -> Duration {
+        Duration::try_new(self.numerator as u32, self.denominator as u32).expect("Zero denominators have already been factored out")
+    }
+
+    /// Compound meters (6/8, 9/8, 12/8...) are felt in dotted beats
+    pub fn is_compound(&self) -> bool {
+        self.numerator > 3 && self.numerator % 3 == 0 && self.denominator >= 8
+    }
+
+    pub fn get_beat_duration(&self) -> Duration {
+        let beat = Duration::try_new(1, self.denominator as u32).expect("Zero denominators have already been factored out");
+
+        if self.is_compound() {
+            beat.multiply(3, 1)
+        } else {
+            beat
+        }
+    }
+}
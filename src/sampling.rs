@@ -0,0 +1,21 @@
+pub(crate) mod weighted_choice;
+
+mod letter_note_sampler;
+pub use letter_note_sampler::LetterNoteSampler;
+
+mod number_note_sampler;
+pub use number_note_sampler::NumberNoteSampler;
+
+mod cadence;
+pub use cadence::Cadence;
+
+mod melody_generator;
+pub use melody_generator::MelodyGenerator;
+
+mod n_gram_table;
+
+mod melody_model;
+pub use melody_model::MelodyModel;
+
+mod harmony_model;
+pub use harmony_model::HarmonyModel;
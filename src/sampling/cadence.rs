@@ -0,0 +1,36 @@
+use std::fmt::Display;
+
+/// How a generated melody ends
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum Cadence {
+    /// Lands on the tonic by step, from the second degree or the leading note
+    #[default]
+    Authentic,
+    /// Lands on the dominant by step
+    Half,
+    /// Ends wherever the melody happens to be
+    Free,
+}
+
+impl Display for Cadence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cadence::Authentic => write!(f, "Authentic"),
+            Cadence::Half => write!(f, "Half"),
+            Cadence::Free => write!(f, "Free"),
+        }
+    }
+}
+
+impl Cadence {
+    pub const ALL: [Cadence; 3] = [Cadence::Authentic, Cadence::Half, Cadence::Free];
+
+    /// The scale degree (1 is the tonic) the melody finishes on, if any
+    pub fn get_final_degree(&self) -> Option<u8> {
+        match self {
+            Cadence::Authentic => Some(1),
+            Cadence::Half => Some(5),
+            Cadence::Free => None,
+        }
+    }
+}
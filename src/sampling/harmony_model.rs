@@ -0,0 +1,93 @@
+use std::fmt::Display;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crispii_errors::{CrispiiError, ImpossibleOperationError};
+
+use crate::chordpro::ChordProSheet;
+use crate::chords::{ChordSymbol, RelativeChord};
+use crate::keys::Key;
+use crate::sampling::n_gram_table::NGramTable;
+use crate::scores::Melody;
+
+/// A Markov model of which chord follows the last few, trained on progressions in any key by describing each chord by its degrees
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct HarmonyModel {
+    table: NGramTable<RelativeChord>,
+}
+
+impl Default for HarmonyModel {
+    fn default() -> Self {
+        HarmonyModel::try_new(2).expect("An order of 2 is valid")
+    }
+}
+
+impl Display for HarmonyModel {
+    /// A plain text form that `try_parse` reads back
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.table.write(|relative_chord| relative_chord.to_string()))
+    }
+}
+
+impl HarmonyModel {
+    /// The order is how many previous chords each choice depends on
+    pub fn try_new(order: u8) -> Result<HarmonyModel, Box<dyn CrispiiError>> {
+        Ok(HarmonyModel { table: NGramTable::try_new(order)? })
+    }
+
+    pub fn try_parse(text: &str) -> Result<HarmonyModel, Box<dyn CrispiiError>> {
+        Ok(HarmonyModel { table: NGramTable::try_parse(text, RelativeChord::try_parse)? })
+    }
+
+    pub fn get_order(&self) -> u8 {
+        self.table.get_order()
+    }
+
+    pub fn is_trained(&self) -> bool {
+        !self.table.is_empty()
+    }
+
+    pub fn try_train_sequence(&mut self, relative_chords: &[RelativeChord]) -> Result<(), Box<dyn CrispiiError>> {
+        self.table.try_train(relative_chords)
+    }
+
+    pub fn try_train_chord_symbols(&mut self, chord_symbols: &[ChordSymbol], key: Key) -> Result<(), Box<dyn CrispiiError>> {
+        let relative_chords = chord_symbols.iter()
+            .map(|chord_symbol| RelativeChord::try_from_chord_symbol(*chord_symbol, key))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.try_train_sequence(&relative_chords)
+    }
+
+    /// Learns from the chord symbols attached to the melody's events
+    pub fn try_train(&mut self, melody: &Melody) -> Result<(), Box<dyn CrispiiError>> {
+        let chord_symbols: Vec<ChordSymbol> = melody.get_events().iter().filter_map(|event| event.get_chord_symbol()).collect();
+
+        self.try_train_chord_symbols(&chord_symbols, melody.get_key())
+    }
+
+    /// Learns from the sheet's inline chords, read as they sound once any capo is taken into account. The sheet needs a key directive
+    pub fn try_train_chordpro(&mut self, sheet: &ChordProSheet) -> Result<(), Box<dyn CrispiiError>> {
+        let key = sheet.get_key()
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(ImpossibleOperationError::new("Chord sheets without a key directive cannot be read relative to their key")) })?;
+        let sounding = sheet.try_set_capo(0)?;
+
+        self.try_train_chord_symbols(&sounding.get_chords(), key)
+    }
+
+    pub fn try_generate(&self, max_length: usize, temperature: f64, seed: u64) -> Result<Vec<RelativeChord>, Box<dyn CrispiiError>> {
+        self.try_generate_from_rng(&mut ChaCha8Rng::seed_from_u64(seed), max_length, temperature)
+    }
+
+    /// Draws up to `max_length` chords, stopping early where the training progressions tended to end. A temperature below 1 sticks to the
+    /// likeliest continuations and one above 1 takes more chances
+    pub fn try_generate_from_rng<R: rand::Rng + ?Sized>(&self, rng: &mut R, max_length: usize, temperature: f64) -> Result<Vec<RelativeChord>, Box<dyn CrispiiError>> {
+        self.table.try_sample(rng, max_length, temperature)
+    }
+
+    /// Generates a progression and spells it in the given key
+    pub fn try_generate_chord_symbols(&self, key: Key, max_length: usize, temperature: f64, seed: u64) -> Result<Vec<ChordSymbol>, Box<dyn CrispiiError>> {
+        self.try_generate(max_length, temperature, seed)?.iter().map(|relative_chord| relative_chord.try_to_chord_symbol(key)).collect()
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::keys::Key;
+use crate::notes::{LetterNote, Modifier, Octave};
+use crate::sampling::weighted_choice;
+
+/// Draws letter notes at random under a set of constraints. Unlike the uniform `Distribution` on `LetterNote`, every spelling the allowed
+/// modifiers permit can come up, including E#, Fb, B#, Cb and (if allowed) double sharps and flats
+#[derive(Clone, PartialEq, Debug)]
+pub struct LetterNoteSampler {
+    modifiers: Vec<Modifier>,
+    key: Option<Key>,
+    lowest_octave: Octave,
+    highest_octave: Octave,
+    pitch_range: Option<(i16, i16)>,
+    weights: HashMap<(u8, Modifier), f64>,
+    octave_weights: HashMap<Octave, f64>,
+    allow_repeats: bool,
+    previous: Option<LetterNote>,
+}
+
+impl Default for LetterNoteSampler {
+    /// Naturals, sharps and flats in octave four, all equally likely
+    fn default() -> Self {
+        LetterNoteSampler {
+            modifiers: vec![Modifier::Flat, Modifier::Default, Modifier::Sharp],
+            key: None,
+            lowest_octave: Octave::Four,
+            highest_octave: Octave::Four,
+            pitch_range: None,
+            weights: HashMap::new(),
+            octave_weights: HashMap::new(),
+            allow_repeats: true,
+            previous: None,
+        }
+    }
+}
+
+impl LetterNoteSampler {
+    pub fn new() -> LetterNoteSampler {
+        LetterNoteSampler::default()
+    }
+
+    pub fn get_modifiers(&self) -> &[Modifier] {
+        &self.modifiers
+    }
+
+    pub fn get_key(&self) -> Option<Key> {
+        self.key
+    }
+
+    pub fn get_octave_range(&self) -> (Octave, Octave) {
+        (self.lowest_octave, self.highest_octave)
+    }
+
+    pub fn allows_repeats(&self) -> bool {
+        self.allow_repeats
+    }
+
+    pub fn set_modifiers(&mut self, modifiers: &[Modifier]) {
+        self.modifiers = modifiers.to_vec();
+    }
+
+    /// Keeps to the notes of the key's scale, spelt as the key spells them (which the allowed modifiers must include)
+    pub fn set_key(&mut self, key: Option<Key>) {
+        self.key = key;
+    }
+
+    pub fn try_set_octave_range(&mut self, lowest: Octave, highest: Octave) -> Result<(), Box<dyn CrispiiError>> {
+        if lowest > highest {
+            return Err(Box::new(InvalidArgumentError::new("highest", "Must not be below the lowest octave")));
+        }
+
+        self.lowest_octave = lowest;
+        self.highest_octave = highest;
+        Ok(())
+    }
+
+    /// Keeps to notes sounding between the two given notes (inclusive), on top of the octave range
+    pub fn try_set_pitch_range(&mut self, lowest: LetterNote, highest: LetterNote) -> Result<(), Box<dyn CrispiiError>> {
+        if lowest.get_pitch_number() > highest.get_pitch_number() {
+            return Err(Box::new(InvalidArgumentError::new("highest", "Must not be below the lowest note")));
+        }
+
+        self.pitch_range = Some((lowest.get_pitch_number(), highest.get_pitch_number()));
+        Ok(())
+    }
+
+    pub fn clear_pitch_range(&mut self) {
+        self.pitch_range = None;
+    }
+
+    /// Makes a spelling more (or less) likely than the default weight of 1 in every octave. A weight of 0 rules it out
+    pub fn try_set_weight(&mut self, note: LetterNote, weight: f64) -> Result<(), Box<dyn CrispiiError>> {
+        weighted_choice::try_check_weight(weight)?;
+        self.weights.insert((note.get_letter_index(), note.get_modifier()), weight);
+        Ok(())
+    }
+
+    /// Multiplies the weight of every note in the octave
+    pub fn try_set_octave_weight(&mut self, octave: Octave, weight: f64) -> Result<(), Box<dyn CrispiiError>> {
+        weighted_choice::try_check_weight(weight)?;
+        self.octave_weights.insert(octave, weight);
+        Ok(())
+    }
+
+    /// When repeats are not allowed, a note never sounds the same pitch as the one drawn before it, so C# cannot follow Db either
+    pub fn set_allow_repeats(&mut self, allow_repeats: bool) {
+        self.allow_repeats = allow_repeats;
+    }
+
+    /// Every note that can currently be drawn, along with its weight
+    pub fn get_candidates(&self) -> Vec<(LetterNote, f64)> {
+        let octaves = (self.lowest_octave.get_number()..=self.highest_octave.get_number()).filter_map(|number| Octave::try_from_number(number).ok());
+
+        octaves
+            .flat_map(|octave| {
+                (0..7u8).flat_map(move |letter_index| self.modifiers.iter().map(move |modifier| LetterNote::from_letter_index(letter_index, *modifier, octave)))
+            })
+            .filter(|note| self.key.is_none_or(|key| key.contains(*note)))
+            .filter(|note| self.pitch_range.is_none_or(|(lowest, highest)| (lowest..=highest).contains(&note.get_pitch_number())))
+            .filter(|note| self.allow_repeats || self.previous.is_none_or(|previous| previous.get_pitch_number() != note.get_pitch_number()))
+            .map(|note| {
+                let weight = self.weights.get(&(note.get_letter_index(), note.get_modifier())).copied().unwrap_or(1.0);
+                let octave_weight = self.octave_weights.get(&note.get_octave()).copied().unwrap_or(1.0);
+
+                (note, weight * octave_weight)
+            })
+            .filter(|(_, weight)| *weight > 0.0)
+            .collect()
+    }
+
+    pub fn try_sample<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Result<LetterNote, Box<dyn CrispiiError>> {
+        let note = weighted_choice::try_choose(&self.get_candidates(), rng)?;
+
+        self.previous = Some(note);
+        Ok(note)
+    }
+
+    pub fn try_sample_many<R: rand::Rng + ?Sized>(&mut self, rng: &mut R, count: usize) -> Result<Vec<LetterNote>, Box<dyn CrispiiError>> {
+        (0..count).map(|_| self.try_sample(rng)).collect()
+    }
+
+    /// Forgets the last note drawn, so the next one may repeat it
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+}
@@ -0,0 +1,343 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crispii_errors::{CrispiiError, ImpossibleOperationError, InvalidArgumentError};
+
+use crate::keys::Key;
+use crate::notes::{LetterNote, Modifier, Octave};
+use crate::rhythm::{Duration, NoteValue, Tempo, TimeSignature};
+use crate::sampling::{Cadence, weighted_choice};
+use crate::scores::{Event, Melody};
+
+/// Writes short diatonic melodies for sight-singing and dictation. The same settings and seed always give the same melody
+#[derive(Clone, PartialEq, Debug)]
+pub struct MelodyGenerator {
+    key: Key,
+    lowest: LetterNote,
+    highest: LetterNote,
+    time_signature: TimeSignature,
+    tempo: Tempo,
+    measure_count: u16,
+    max_leap: u8,
+    step_probability: f64,
+    rhythms: Vec<Duration>,
+    cadence: Cadence,
+}
+
+impl Default for MelodyGenerator {
+    /// Four measures of halves and quarters in C major between C4 and C5, ending on an authentic cadence
+    fn default() -> Self {
+        MelodyGenerator {
+            key: Key::default(),
+            lowest: LetterNote::C(Modifier::Default, Octave::Four),
+            highest: LetterNote::C(Modifier::Default, Octave::Five),
+            time_signature: TimeSignature::default(),
+            tempo: Tempo::default(),
+            measure_count: 4,
+            max_leap: 4,
+            step_probability: 0.7,
+            rhythms: vec![NoteValue::Half.get_duration(), NoteValue::Quarter.get_duration()],
+            cadence: Cadence::default(),
+        }
+    }
+}
+
+impl MelodyGenerator {
+    pub fn new(key: Key) -> MelodyGenerator {
+        MelodyGenerator { key, ..MelodyGenerator::default() }
+    }
+
+    pub fn get_key(&self) -> Key {
+        self.key
+    }
+
+    pub fn get_range(&self) -> (LetterNote, LetterNote) {
+        (self.lowest, self.highest)
+    }
+
+    pub fn get_time_signature(&self) -> TimeSignature {
+        self.time_signature
+    }
+
+    pub fn get_tempo(&self) -> Tempo {
+        self.tempo
+    }
+
+    pub fn get_measure_count(&self) -> u16 {
+        self.measure_count
+    }
+
+    pub fn get_max_leap(&self) -> u8 {
+        self.max_leap
+    }
+
+    pub fn get_step_probability(&self) -> f64 {
+        self.step_probability
+    }
+
+    pub fn get_rhythms(&self) -> &[Duration] {
+        &self.rhythms
+    }
+
+    pub fn get_cadence(&self) -> Cadence {
+        self.cadence
+    }
+
+    pub fn set_key(&mut self, key: Key) {
+        self.key = key;
+    }
+
+    /// The lowest and highest notes the melody may sound (inclusive)
+    pub fn try_set_range(&mut self, lowest: LetterNote, highest: LetterNote) -> Result<(), Box<dyn CrispiiError>> {
+        if lowest.get_pitch_number() > highest.get_pitch_number() {
+            return Err(Box::new(InvalidArgumentError::new("highest", "Must not be below the lowest note")));
+        }
+
+        self.lowest = lowest;
+        self.highest = highest;
+        Ok(())
+    }
+
+    pub fn set_time_signature(&mut self, time_signature: TimeSignature) {
+        self.time_signature = time_signature;
+    }
+
+    pub fn set_tempo(&mut self, tempo: Tempo) {
+        self.tempo = tempo;
+    }
+
+    pub fn try_set_measure_count(&mut self, measure_count: u16) -> Result<(), Box<dyn CrispiiError>> {
+        if measure_count == 0 {
+            return Err(Box::new(InvalidArgumentError::new("measure_count", "Must be at least 1")));
+        }
+
+        self.measure_count = measure_count;
+        Ok(())
+    }
+
+    /// The widest leap allowed, in scale steps, so 4 allows up to a fifth. Anything below 2 keeps the melody moving by step
+    pub fn set_max_leap(&mut self, max_leap: u8) {
+        self.max_leap = max_leap;
+    }
+
+    /// How likely each move is to be a step rather than a leap
+    pub fn try_set_step_probability(&mut self, step_probability: f64) -> Result<(), Box<dyn CrispiiError>> {
+        if !(0.0..=1.0).contains(&step_probability) {
+            return Err(Box::new(InvalidArgumentError::new("step_probability", "Must be between 0 and 1 (inclusive)")));
+        }
+
+        self.step_probability = step_probability;
+        Ok(())
+    }
+
+    /// The note lengths to build measures from, all equally likely. Gaps no rhythm fits into are filled with the longest note values that do
+    pub fn try_set_rhythms(&mut self, rhythms: &[Duration]) -> Result<(), Box<dyn CrispiiError>> {
+        if rhythms.is_empty() || rhythms.iter().any(|rhythm| rhythm.is_zero()) {
+            return Err(Box::new(InvalidArgumentError::new("rhythms", "Must contain at least one duration, none of them zero")));
+        }
+
+        self.rhythms = rhythms.to_vec();
+        Ok(())
+    }
+
+    pub fn set_cadence(&mut self, cadence: Cadence) {
+        self.cadence = cadence;
+    }
+
+    pub fn try_generate(&self, seed: u64) -> Result<Melody, Box<dyn CrispiiError>> {
+        self.try_generate_from_rng(&mut ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    /// Picks a rhythm measure by measure, then walks the scale from a note of the tonic triad, stepping or leaping as the step probability
+    /// dictates and turning back by step after any leap wider than a third. A seventh lying a whole step below the tonic is raised at an
+    /// authentic cadence, as in minor keys
+    pub fn try_generate_from_rng<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Result<Melody, Box<dyn CrispiiError>> {
+        let rhythm = self.try_generate_rhythm(rng)?;
+        let scale_notes = self.get_scale_notes();
+        let positions = self.try_generate_positions(rng, &scale_notes, rhythm.len())?;
+        let mut notes: Vec<LetterNote> = positions.iter().map(|position| scale_notes[*position].0).collect();
+
+        if let [.., penultimate, last] = notes.as_mut_slice()
+            && self.cadence == Cadence::Authentic
+            && last.get_pitch_number() - penultimate.get_pitch_number() == 2
+        {
+            *penultimate = penultimate.try_offset(0, 1)?;
+        }
+
+        let mut events = Vec::new();
+
+        for (note, durations) in notes.into_iter().zip(rhythm) {
+            for (index, duration) in durations.iter().enumerate() {
+                let mut event = Event::new_note(note, *duration);
+                event.set_tied(index + 1 < durations.len());
+                events.push(event);
+            }
+        }
+
+        Ok(Melody::new(self.key, self.time_signature, self.tempo, events))
+    }
+
+    /// Each note's length, split into tied note values where it needs more than one
+    fn try_generate_rhythm<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Result<Vec<Vec<Duration>>, Box<dyn CrispiiError>> {
+        let measure_duration = self.time_signature.get_measure_duration();
+        let mut rhythm = Vec::new();
+
+        for measure in 0..self.measure_count {
+            let is_final = measure + 1 == self.measure_count && self.cadence != Cadence::Free;
+            let mut remaining = measure_duration;
+
+            while !remaining.is_zero() {
+                // The final note of a cadence is held through the second half of the last measure
+                if is_final && remaining.multiply(2, 1) <= measure_duration {
+                    rhythm.push(split_into_durations(remaining)?);
+                    break;
+                }
+
+                let fitting: Vec<(Duration, f64)> = self.rhythms.iter().filter(|rhythm| **rhythm <= remaining).map(|rhythm| (*rhythm, 1.0)).collect();
+
+                if fitting.is_empty() {
+                    rhythm.extend(split_into_durations(remaining)?.into_iter().map(|duration| vec![duration]));
+                    break;
+                }
+
+                let duration = weighted_choice::try_choose(&fitting, rng)?;
+                remaining = remaining.try_subtract(duration)?;
+                rhythm.push(vec![duration]);
+            }
+        }
+
+        Ok(rhythm)
+    }
+
+    /// Every note of the key within range, lowest first, along with its scale degree
+    fn get_scale_notes(&self) -> Vec<(LetterNote, u8)> {
+        let tonic_index = self.key.get_tonic().get_letter_index();
+        let lowest_pitch = self.lowest.get_pitch_number();
+        let highest_pitch = self.highest.get_pitch_number();
+        let octaves = (self.lowest.get_octave().get_number().saturating_sub(1)..=self.highest.get_octave().get_number().saturating_add(1))
+            .filter_map(|number| Octave::try_from_number(number).ok());
+
+        let mut notes: Vec<(LetterNote, u8)> = octaves
+            .flat_map(|octave| (0..7u8).map(move |letter_index| LetterNote::from_letter_index(letter_index, self.key.get_modifier_for_letter(letter_index), octave)))
+            .filter(|note| (lowest_pitch..=highest_pitch).contains(&note.get_pitch_number()))
+            .map(|note| (note, (note.get_letter_index() + 7 - tonic_index) % 7 + 1))
+            .collect();
+
+        notes.sort_by_key(|(note, _)| note.get_pitch_number());
+        notes
+    }
+
+    /// Indices into the scale notes, one per note of the melody
+    fn try_generate_positions<R: rand::Rng + ?Sized>(&self, rng: &mut R, scale_notes: &[(LetterNote, u8)], count: usize) -> Result<Vec<usize>, Box<dyn CrispiiError>> {
+        if scale_notes.is_empty() {
+            return Err(Box::new(ImpossibleOperationError::new(format!("There are no notes of {} between {} and {}", self.key, self.lowest, self.highest).as_str())));
+        }
+
+        let endings = match self.cadence.get_final_degree() {
+            Some(degree) => {
+                let endings = get_cadence_endings(scale_notes, degree);
+
+                if endings.is_empty() {
+                    return Err(Box::new(ImpossibleOperationError::new(format!("The range has no degree {degree} that can be approached by step").as_str())));
+                }
+
+                Some(endings)
+            }
+            None => None,
+        };
+
+        let ending_length = if endings.is_some() { count.min(2) } else { 0 };
+        let mut positions = Vec::with_capacity(count);
+
+        if count > ending_length {
+            let triad: Vec<(usize, f64)> = scale_notes.iter().enumerate().filter(|(_, (_, degree))| [1, 3, 5].contains(degree)).map(|(position, _)| (position, 1.0)).collect();
+            let starts = if triad.is_empty() { (0..scale_notes.len()).map(|position| (position, 1.0)).collect() } else { triad };
+
+            positions.push(weighted_choice::try_choose(&starts, rng)?);
+        }
+
+        while positions.len() < count - ending_length {
+            let next = self.get_next_position(rng, &positions, scale_notes.len());
+            positions.push(next);
+        }
+
+        if let Some(endings) = endings {
+            match (ending_length, positions.last()) {
+                (1, _) => positions.push(weighted_choice::try_choose(&endings.iter().map(|(_, last)| (*last, 1.0)).collect::<Vec<_>>(), rng)?),
+                (2, Some(previous)) => {
+                    let max_leap = self.max_leap.max(1) as usize;
+                    let reachable: Vec<((usize, usize), f64)> = endings.iter()
+                        .filter(|(penultimate, _)| penultimate.abs_diff(*previous) <= max_leap)
+                        .map(|ending| (*ending, 1.0 / (1.0 + ending.0.abs_diff(*previous) as f64)))
+                        .collect();
+                    let (penultimate, last) = match reachable.is_empty() {
+                        true => *endings.iter().min_by_key(|(penultimate, _)| penultimate.abs_diff(*previous)).expect("Endings have already been checked"),
+                        false => weighted_choice::try_choose(&reachable, rng)?,
+                    };
+
+                    positions.extend([penultimate, last]);
+                }
+                (2, None) => {
+                    let (penultimate, last) = weighted_choice::try_choose(&endings.iter().map(|ending| (*ending, 1.0)).collect::<Vec<_>>(), rng)?;
+                    positions.extend([penultimate, last]);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(positions)
+    }
+
+    fn get_next_position<R: rand::Rng + ?Sized>(&self, rng: &mut R, positions: &[usize], scale_length: usize) -> usize {
+        let current = *positions.last().expect("The melody has already been started");
+        let highest = scale_length - 1;
+
+        if highest == 0 {
+            return current;
+        }
+
+        // Recover from a leap wider than a third by stepping back the other way
+        if let [.., before, _] = positions {
+            let leap = current as isize - *before as isize;
+
+            if leap > 2 && current > 0 {
+                return current - 1;
+            }
+
+            if leap < -2 && current < highest {
+                return current + 1;
+            }
+        }
+
+        let leaps: Vec<usize> = (2..=self.max_leap as usize)
+            .flat_map(|size| [current.checked_sub(size), Some(current + size)])
+            .flatten()
+            .filter(|position| *position <= highest)
+            .collect();
+
+        if !leaps.is_empty() && !rng.random_bool(self.step_probability) {
+            return leaps[rng.random_range(0..leaps.len())];
+        }
+
+        match (current == 0, current == highest) {
+            (true, _) => current + 1,
+            (_, true) => current - 1,
+            _ if rng.random_bool(0.5) => current + 1,
+            _ => current - 1,
+        }
+    }
+}
+
+/// Splits a length into the longest note values that add up to it
+fn split_into_durations(duration: Duration) -> Result<Vec<Duration>, Box<dyn CrispiiError>> {
+    Ok(duration.try_split_into_note_values()?.into_iter().map(|(note_value, dots)| note_value.get_dotted_duration(dots)).collect())
+}
+
+/// Every (penultimate, last) pair of positions that lands on the given degree by step
+fn get_cadence_endings(scale_notes: &[(LetterNote, u8)], degree: u8) -> Vec<(usize, usize)> {
+    scale_notes.iter().enumerate()
+        .filter(|(_, (_, note_degree))| *note_degree == degree)
+        .flat_map(|(last, _)| [last.checked_sub(1), Some(last + 1)].into_iter().flatten().map(move |penultimate| (penultimate, last)))
+        .filter(|(penultimate, _)| *penultimate < scale_notes.len())
+        .collect()
+}
@@ -0,0 +1,121 @@
+use std::fmt::Display;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::keys::Key;
+use crate::notes::{LetterNote, Modifier, NumberNote, Octave};
+use crate::sampling::n_gram_table::NGramTable;
+use crate::scores::Melody;
+
+/// A Markov model of which scale degree follows the last few, trained on melodies in any key. Octaves are ignored, so it models the
+/// degrees sung rather than the contour
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct MelodyModel {
+    table: NGramTable<NumberNote>,
+}
+
+impl Default for MelodyModel {
+    fn default() -> Self {
+        MelodyModel::try_new(2).expect("An order of 2 is valid")
+    }
+}
+
+impl Display for MelodyModel {
+    /// A plain text form that `try_parse` reads back
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.table.write(|number_note| format!("{}{}", number_note.get_modifier().get_symbol(), number_note.get_degree())))
+    }
+}
+
+impl MelodyModel {
+    /// The order is how many previous degrees each choice depends on
+    pub fn try_new(order: u8) -> Result<MelodyModel, Box<dyn CrispiiError>> {
+        Ok(MelodyModel { table: NGramTable::try_new(order)? })
+    }
+
+    pub fn try_parse(text: &str) -> Result<MelodyModel, Box<dyn CrispiiError>> {
+        Ok(MelodyModel { table: NGramTable::try_parse(text, try_parse_token)? })
+    }
+
+    pub fn get_order(&self) -> u8 {
+        self.table.get_order()
+    }
+
+    pub fn is_trained(&self) -> bool {
+        !self.table.is_empty()
+    }
+
+    pub fn try_train_sequence(&mut self, number_notes: &[NumberNote]) -> Result<(), Box<dyn CrispiiError>> {
+        let number_notes: Vec<NumberNote> = number_notes.iter().map(|number_note| NumberNote::from_degree(number_note.get_degree(), number_note.get_modifier(), Octave::Four)).collect();
+
+        self.table.try_train(&number_notes)
+    }
+
+    /// Learns from the melody's notes relative to its key, skipping rests and tied continuations and taking the top note of any chord
+    pub fn try_train(&mut self, melody: &Melody) -> Result<(), Box<dyn CrispiiError>> {
+        let key = melody.get_key();
+        let mut number_notes = Vec::new();
+        let mut tied = false;
+
+        for event in melody.get_events() {
+            let continues_tie = tied;
+            tied = event.is_tied();
+
+            if continues_tie {
+                continue;
+            }
+
+            if let Some(note) = event.get_notes().into_iter().max_by_key(|note| note.get_pitch_number()) {
+                number_notes.push(key.try_get_number_note(note)?);
+            }
+        }
+
+        self.try_train_sequence(&number_notes)
+    }
+
+    pub fn try_generate(&self, max_length: usize, temperature: f64, seed: u64) -> Result<Vec<NumberNote>, Box<dyn CrispiiError>> {
+        self.try_generate_from_rng(&mut ChaCha8Rng::seed_from_u64(seed), max_length, temperature)
+    }
+
+    /// Draws up to `max_length` degrees, stopping early where the training melodies tended to end. A temperature below 1 sticks to the
+    /// likeliest continuations and one above 1 takes more chances
+    pub fn try_generate_from_rng<R: rand::Rng + ?Sized>(&self, rng: &mut R, max_length: usize, temperature: f64) -> Result<Vec<NumberNote>, Box<dyn CrispiiError>> {
+        self.table.try_sample(rng, max_length, temperature)
+    }
+
+    /// Generates degrees and spells them in the given key, starting in octave four and placing each note as close as it can to the last
+    pub fn try_generate_notes(&self, key: Key, max_length: usize, temperature: f64, seed: u64) -> Result<Vec<LetterNote>, Box<dyn CrispiiError>> {
+        let mut notes: Vec<LetterNote> = Vec::new();
+
+        for number_note in self.try_generate(max_length, temperature, seed)? {
+            let note = match notes.last() {
+                Some(previous) => {
+                    let octave = key.try_get_number_note(*previous)?.get_octave().get_number();
+
+                    (octave - 1..=octave + 1)
+                        .filter_map(|octave| Octave::try_from_number(octave).ok())
+                        .filter_map(|octave| key.try_get_letter_note(NumberNote::from_degree(number_note.get_degree(), number_note.get_modifier(), octave)).ok())
+                        .min_by_key(|note| (note.get_pitch_number() - previous.get_pitch_number()).abs())
+                        .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("key", format!("{key} cannot spell {number_note}").as_str())) })?
+                }
+                None => key.try_get_letter_note(number_note)?,
+            };
+
+            notes.push(note);
+        }
+
+        Ok(notes)
+    }
+}
+
+fn try_parse_token(token: &str) -> Result<NumberNote, Box<dyn CrispiiError>> {
+    let (modifier, length) = Modifier::parse_symbol_prefix(token);
+
+    match token[length..].parse::<u8>() {
+        Ok(degree) if (1..=7).contains(&degree) => Ok(NumberNote::from_degree(degree, modifier, Octave::Four)),
+        _ => Err(Box::new(InvalidArgumentError::new("text", format!("'{token}' is not a scale degree").as_str()))),
+    }
+}
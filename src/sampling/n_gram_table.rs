@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::sampling::weighted_choice;
+
+const BOUNDARY: &str = "^";
+/// Below this temperature only the likeliest continuations are drawn, as raising the odds to such high powers would lose them anyway
+const ARGMAX_TEMPERATURE: f64 = 0.01;
+
+/// Counts of which token follows each context of up to `order` tokens. `None` marks the start or end of a sequence
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub(crate) struct NGramTable<T: Copy + Ord> {
+    order: u8,
+    counts: BTreeMap<Vec<Option<T>>, BTreeMap<Option<T>, u32>>,
+}
+
+impl<T: Copy + Ord> NGramTable<T> {
+    pub(crate) fn try_new(order: u8) -> Result<NGramTable<T>, Box<dyn CrispiiError>> {
+        if order == 0 {
+            return Err(Box::new(InvalidArgumentError::new("order", "Must be at least 1")));
+        }
+
+        Ok(NGramTable { order, counts: BTreeMap::new() })
+    }
+
+    pub(crate) fn get_order(&self) -> u8 {
+        self.order
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Counts every context of every length up to the order, so sampling can fall back on shorter ones it has not seen in full
+    pub(crate) fn try_train(&mut self, sequence: &[T]) -> Result<(), Box<dyn CrispiiError>> {
+        if sequence.is_empty() {
+            return Ok(());
+        }
+
+        let order = self.order as usize;
+        let padded: Vec<Option<T>> = std::iter::repeat_n(None, order).chain(sequence.iter().map(|token| Some(*token))).chain([None]).collect();
+
+        for index in order..padded.len() {
+            for length in 0..=order {
+                let context = padded[index - length..index].to_vec();
+                self.try_add_count(context, padded[index], 1)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Draws up to `max_length` tokens, stopping early if the end of a sequence comes up. A temperature below 1 favours the likeliest
+    /// continuations and one above 1 flattens the odds, while one near 0 always takes the likeliest
+    pub(crate) fn try_sample<R: rand::Rng + ?Sized>(&self, rng: &mut R, max_length: usize, temperature: f64) -> Result<Vec<T>, Box<dyn CrispiiError>> {
+        if !(temperature.is_finite() && temperature > 0.0) {
+            return Err(Box::new(InvalidArgumentError::new("temperature", "Must be finite and above 0")));
+        }
+
+        let order = self.order as usize;
+        let mut history: Vec<Option<T>> = vec![None; order];
+        let mut sequence = Vec::new();
+
+        while sequence.len() < max_length {
+            let Some(continuations) = (0..=order).rev().find_map(|length| self.counts.get(&history[history.len() - length..])) else {
+                break;
+            };
+            // Counts are taken relative to the largest so the weights stay between 0 and 1 however low the temperature
+            let max_count = continuations.values().copied().max().unwrap_or(1).max(1) as f64;
+            let weighted: Vec<(Option<T>, f64)> = continuations.iter().map(|(token, count)| {
+                let relative_count = *count as f64 / max_count;
+
+                match temperature < ARGMAX_TEMPERATURE {
+                    true if relative_count == 1.0 => (*token, 1.0),
+                    true => (*token, 0.0),
+                    false => (*token, relative_count.powf(1.0 / temperature)),
+                }
+            }).collect();
+
+            match weighted_choice::try_choose(&weighted, rng)? {
+                Some(token) => {
+                    sequence.push(token);
+                    history.push(Some(token));
+                }
+                None => break,
+            }
+        }
+
+        Ok(sequence)
+    }
+
+    /// One line per context and continuation, such as "^ 1 -> 2 3" for 2 following 1 at the start of a sequence three times
+    pub(crate) fn write<F: Fn(&T) -> String>(&self, write_token: F) -> String {
+        let write_optional = |token: &Option<T>| token.as_ref().map(&write_token).unwrap_or(BOUNDARY.to_string());
+        let mut text = format!("order {}\n", self.order);
+
+        for (context, continuations) in &self.counts {
+            for (token, count) in continuations {
+                let context: Vec<String> = context.iter().map(write_optional).collect();
+                text.push_str(format!("{} -> {} {count}", context.join(" "), write_optional(token)).trim_start());
+                text.push('\n');
+            }
+        }
+
+        text
+    }
+
+    pub(crate) fn try_parse<F: Fn(&str) -> Result<T, Box<dyn CrispiiError>>>(text: &str, parse_token: F) -> Result<NGramTable<T>, Box<dyn CrispiiError>> {
+        let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+        let order = lines.next()
+            .and_then(|line| line.strip_prefix("order "))
+            .and_then(|order| order.trim().parse().ok())
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", "Models must start with an 'order' line")) })?;
+        let mut table = NGramTable::try_new(order)?;
+        let parse_optional = |token: &str| match token {
+            BOUNDARY => Ok(None),
+            _ => parse_token(token).map(Some),
+        };
+
+        for line in lines {
+            let (context, continuation) = line.split_once("->")
+                .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("Expected '->' in '{line}'").as_str())) })?;
+            let (token, count) = continuation.trim().rsplit_once(' ')
+                .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("Expected a token and a count after '->' in '{line}'").as_str())) })?;
+            let count: u32 = count.parse()
+                .map_err(|_| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("'{count}' is not a valid count in '{line}'").as_str())) })?;
+            let context = context.split_whitespace().map(parse_optional).collect::<Result<Vec<_>, _>>()?;
+
+            if context.len() > order as usize {
+                return Err(Box::new(InvalidArgumentError::new("text", format!("'{line}' has a context longer than the order").as_str())));
+            }
+
+            table.try_add_count(context, parse_optional(token.trim())?, count)?;
+        }
+
+        Ok(table)
+    }
+
+    fn try_add_count(&mut self, context: Vec<Option<T>>, token: Option<T>, count: u32) -> Result<(), Box<dyn CrispiiError>> {
+        let total = self.counts.entry(context).or_default().entry(token).or_default();
+        *total = total.checked_add(count)
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("count", "The counts add up to more than can be stored")) })?;
+
+        Ok(())
+    }
+}
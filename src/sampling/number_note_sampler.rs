@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::keys::Mode;
+use crate::notes::{Modifier, NumberNote, Octave};
+use crate::sampling::weighted_choice;
+
+/// Draws scale degrees at random under a set of constraints, the key-independent counterpart of `LetterNoteSampler`
+#[derive(Clone, PartialEq, Debug)]
+pub struct NumberNoteSampler {
+    modifiers: Vec<Modifier>,
+    mode: Option<Mode>,
+    lowest_octave: Octave,
+    highest_octave: Octave,
+    weights: HashMap<(u8, Modifier), f64>,
+    octave_weights: HashMap<Octave, f64>,
+    allow_repeats: bool,
+    previous: Option<NumberNote>,
+}
+
+impl Default for NumberNoteSampler {
+    /// Unaltered degrees in octave four, all equally likely
+    fn default() -> Self {
+        NumberNoteSampler {
+            modifiers: vec![Modifier::Default],
+            mode: None,
+            lowest_octave: Octave::Four,
+            highest_octave: Octave::Four,
+            weights: HashMap::new(),
+            octave_weights: HashMap::new(),
+            allow_repeats: true,
+            previous: None,
+        }
+    }
+}
+
+impl NumberNoteSampler {
+    pub fn new() -> NumberNoteSampler {
+        NumberNoteSampler::default()
+    }
+
+    pub fn get_modifiers(&self) -> &[Modifier] {
+        &self.modifiers
+    }
+
+    pub fn get_mode(&self) -> Option<Mode> {
+        self.mode
+    }
+
+    pub fn get_octave_range(&self) -> (Octave, Octave) {
+        (self.lowest_octave, self.highest_octave)
+    }
+
+    pub fn allows_repeats(&self) -> bool {
+        self.allow_repeats
+    }
+
+    pub fn set_modifiers(&mut self, modifiers: &[Modifier]) {
+        self.modifiers = modifiers.to_vec();
+    }
+
+    /// Keeps to the degrees of the mode, altered as the mode alters them relative to major (which the allowed modifiers must include)
+    pub fn set_mode(&mut self, mode: Option<Mode>) {
+        self.mode = mode;
+    }
+
+    pub fn try_set_octave_range(&mut self, lowest: Octave, highest: Octave) -> Result<(), Box<dyn CrispiiError>> {
+        if lowest > highest {
+            return Err(Box::new(InvalidArgumentError::new("highest", "Must not be below the lowest octave")));
+        }
+
+        self.lowest_octave = lowest;
+        self.highest_octave = highest;
+        Ok(())
+    }
+
+    /// Makes a degree and modifier more (or less) likely than the default weight of 1 in every octave. A weight of 0 rules it out
+    pub fn try_set_weight(&mut self, number_note: NumberNote, weight: f64) -> Result<(), Box<dyn CrispiiError>> {
+        weighted_choice::try_check_weight(weight)?;
+        self.weights.insert((number_note.get_degree(), number_note.get_modifier()), weight);
+        Ok(())
+    }
+
+    /// Multiplies the weight of every degree in the octave
+    pub fn try_set_octave_weight(&mut self, octave: Octave, weight: f64) -> Result<(), Box<dyn CrispiiError>> {
+        weighted_choice::try_check_weight(weight)?;
+        self.octave_weights.insert(octave, weight);
+        Ok(())
+    }
+
+    /// When repeats are not allowed, the same degree, modifier and octave never comes up twice in a row
+    pub fn set_allow_repeats(&mut self, allow_repeats: bool) {
+        self.allow_repeats = allow_repeats;
+    }
+
+    /// Every number note that can currently be drawn, along with its weight
+    pub fn get_candidates(&self) -> Vec<(NumberNote, f64)> {
+        let mode_notes = self.mode.map(|mode| mode.get_number_notes());
+        let octaves = (self.lowest_octave.get_number()..=self.highest_octave.get_number()).filter_map(|number| Octave::try_from_number(number).ok());
+
+        octaves
+            .flat_map(|octave| (1..=7u8).flat_map(move |degree| self.modifiers.iter().map(move |modifier| NumberNote::from_degree(degree, *modifier, octave))))
+            .filter(|number_note| {
+                mode_notes.is_none_or(|mode_notes| mode_notes.iter().any(|mode_note| mode_note.get_degree() == number_note.get_degree() && mode_note.get_modifier() == number_note.get_modifier()))
+            })
+            .filter(|number_note| self.allow_repeats || self.previous != Some(*number_note))
+            .map(|number_note| {
+                let weight = self.weights.get(&(number_note.get_degree(), number_note.get_modifier())).copied().unwrap_or(1.0);
+                let octave_weight = self.octave_weights.get(&number_note.get_octave()).copied().unwrap_or(1.0);
+
+                (number_note, weight * octave_weight)
+            })
+            .filter(|(_, weight)| *weight > 0.0)
+            .collect()
+    }
+
+    pub fn try_sample<R: rand::Rng + ?Sized>(&mut self, rng: &mut R) -> Result<NumberNote, Box<dyn CrispiiError>> {
+        let number_note = weighted_choice::try_choose(&self.get_candidates(), rng)?;
+
+        self.previous = Some(number_note);
+        Ok(number_note)
+    }
+
+    pub fn try_sample_many<R: rand::Rng + ?Sized>(&mut self, rng: &mut R, count: usize) -> Result<Vec<NumberNote>, Box<dyn CrispiiError>> {
+        (0..count).map(|_| self.try_sample(rng)).collect()
+    }
+
+    /// Forgets the last number note drawn, so the next one may repeat it
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+}
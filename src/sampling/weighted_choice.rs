@@ -0,0 +1,31 @@
+use rand::distr::weighted::WeightedIndex;
+use rand::distr::Distribution;
+
+use crispii_errors::{CrispiiError, ImpossibleOperationError, InvalidArgumentError};
+
+/// Picks a candidate with odds in proportion to its weight, failing rather than panicking if the weights are not finite or overflow
+/// when added up
+pub(crate) fn try_choose<T: Copy, R: rand::Rng + ?Sized>(candidates: &[(T, f64)], rng: &mut R) -> Result<T, Box<dyn CrispiiError>> {
+    let mut total_weight = 0.0;
+
+    for (_, weight) in candidates {
+        try_check_weight(*weight)?;
+        total_weight += weight;
+
+        if !total_weight.is_finite() {
+            return Err(Box::new(InvalidArgumentError::new("weights", "Must add up to a finite total")));
+        }
+    }
+
+    let index = WeightedIndex::new(candidates.iter().map(|(_, weight)| *weight))
+        .map_err(|_| -> Box<dyn CrispiiError> { Box::new(ImpossibleOperationError::new("There is nothing left to choose from once the constraints and weights are applied")) })?;
+
+    Ok(candidates[index.sample(rng)].0)
+}
+
+pub(crate) fn try_check_weight(weight: f64) -> Result<(), Box<dyn CrispiiError>> {
+    match weight.is_finite() && weight >= 0.0 {
+        true => Ok(()),
+        false => Err(Box::new(InvalidArgumentError::new("weight", "Must be finite and not negative"))),
+    }
+}
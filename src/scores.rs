@@ -0,0 +1,23 @@
+mod event;
+pub use event::{Event, EventKind};
+
+mod voice;
+pub use voice::Voice;
+
+mod measure;
+pub use measure::Measure;
+
+mod staff;
+pub use staff::Staff;
+
+mod part;
+pub use part::Part;
+
+mod score;
+pub use score::Score;
+
+mod melody;
+pub use melody::Melody;
+
+mod timed_note;
+pub use timed_note::TimedNote;
@@ -0,0 +1,99 @@
+use crate::chords::ChordSymbol;
+use crate::notes::LetterNote;
+use crate::rhythm::Duration;
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum EventKind {
+    Note(LetterNote),
+    Chord(Vec<LetterNote>),
+    Rest,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Event {
+    kind: EventKind,
+    duration: Duration,
+    tied: bool,
+    lyric: Option<String>,
+    chord_symbol: Option<ChordSymbol>,
+}
+
+impl Event {
+    pub fn new(kind: EventKind, duration: Duration) -> Event {
+        Event {
+            kind,
+            duration,
+            tied: false,
+            lyric: None,
+            chord_symbol: None,
+        }
+    }
+
+    pub fn new_note(note: LetterNote, duration: Duration) -> Event {
+        Event::new(EventKind::Note(note), duration)
+    }
+
+    pub fn new_chord(notes: Vec<LetterNote>, duration: Duration) -> Event {
+        Event::new(EventKind::Chord(notes), duration)
+    }
+
+    pub fn new_rest(duration: Duration) -> Event {
+        Event::new(EventKind::Rest, duration)
+    }
+
+    pub fn get_kind(&self) -> &EventKind {
+        &self.kind
+    }
+
+    pub fn get_duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Whether this event is tied into the next one
+    pub fn is_tied(&self) -> bool {
+        self.tied
+    }
+
+    /// A syllable that runs on into the next event's syllable ends with a hyphen
+    pub fn get_lyric(&self) -> Option<&str> {
+        self.lyric.as_deref()
+    }
+
+    /// The harmony written above this event, taking effect from its start
+    pub fn get_chord_symbol(&self) -> Option<ChordSymbol> {
+        self.chord_symbol
+    }
+
+    /// The sounding notes of this event, empty for rests
+    pub fn get_notes(&self) -> Vec<LetterNote> {
+        match &self.kind {
+            EventKind::Note(note) => vec![*note],
+            EventKind::Chord(notes) => notes.clone(),
+            EventKind::Rest => Vec::new(),
+        }
+    }
+
+    pub fn is_rest(&self) -> bool {
+        matches!(self.kind, EventKind::Rest)
+    }
+
+    pub fn set_kind(&mut self, kind: EventKind) {
+        self.kind = kind;
+    }
+
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = duration;
+    }
+
+    pub fn set_tied(&mut self, tied: bool) {
+        self.tied = tied;
+    }
+
+    pub fn set_lyric(&mut self, lyric: Option<String>) {
+        self.lyric = lyric;
+    }
+
+    pub fn set_chord_symbol(&mut self, chord_symbol: Option<ChordSymbol>) {
+        self.chord_symbol = chord_symbol;
+    }
+}
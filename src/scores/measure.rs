@@ -0,0 +1,30 @@
+use crate::rhythm::Duration;
+use crate::scores::Voice;
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Measure {
+    voices: Vec<Voice>,
+}
+
+impl Measure {
+    pub fn new(voices: Vec<Voice>) -> Measure {
+        Measure { voices }
+    }
+
+    pub fn get_voices(&self) -> &[Voice] {
+        &self.voices
+    }
+
+    pub fn get_voices_mut(&mut self) -> &mut Vec<Voice> {
+        &mut self.voices
+    }
+
+    pub fn add_voice(&mut self, voice: Voice) {
+        self.voices.push(voice);
+    }
+
+    /// The length of the longest voice
+    pub fn get_duration(&self) -> Duration {
+        self.voices.iter().map(|voice| voice.get_duration()).max().unwrap_or_else(Duration::zero)
+    }
+}
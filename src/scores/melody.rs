@@ -0,0 +1,94 @@
+use crate::keys::Key;
+use crate::notes::LetterNote;
+use crate::rhythm::{Duration, Tempo, TimeSignature};
+use crate::scores::{Event, Measure, Part, Score, Staff, Voice};
+
+/// A single line of events, without any barlines
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Melody {
+    key: Key,
+    time_signature: TimeSignature,
+    tempo: Tempo,
+    events: Vec<Event>,
+}
+
+impl Melody {
+    pub fn new(key: Key, time_signature: TimeSignature, tempo: Tempo, events: Vec<Event>) -> Melody {
+        Melody {
+            key,
+            time_signature,
+            tempo,
+            events,
+        }
+    }
+
+    pub fn get_key(&self) -> Key {
+        self.key
+    }
+
+    pub fn get_time_signature(&self) -> TimeSignature {
+        self.time_signature
+    }
+
+    pub fn get_tempo(&self) -> Tempo {
+        self.tempo
+    }
+
+    pub fn get_events(&self) -> &[Event] {
+        &self.events
+    }
+
+    pub fn get_events_mut(&mut self) -> &mut Vec<Event> {
+        &mut self.events
+    }
+
+    /// Every sounding note in order, with chords flattened and rests skipped
+    pub fn get_notes(&self) -> Vec<LetterNote> {
+        self.events.iter().flat_map(|event| event.get_notes()).collect()
+    }
+
+    pub fn get_duration(&self) -> Duration {
+        self.events.iter().fold(Duration::zero(), |total, event| total + event.get_duration())
+    }
+
+    pub fn set_key(&mut self, key: Key) {
+        self.key = key;
+    }
+
+    pub fn set_time_signature(&mut self, time_signature: TimeSignature) {
+        self.time_signature = time_signature;
+    }
+
+    pub fn set_tempo(&mut self, tempo: Tempo) {
+        self.tempo = tempo;
+    }
+
+    pub fn add_event(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    pub fn add_note(&mut self, note: LetterNote, duration: Duration) {
+        self.events.push(Event::new_note(note, duration));
+    }
+
+    pub fn add_rest(&mut self, duration: Duration) {
+        self.events.push(Event::new_rest(duration));
+    }
+
+    /// Splits the melody into measures on a single staff, tying notes that cross a barline
+    pub fn to_score(&self, part_name: &str) -> Score {
+        let time_signature = self.time_signature;
+        let measures = Voice::new(self.events.clone())
+            .split_into_measures(|_| time_signature.get_measure_duration())
+            .into_iter()
+            .map(|voice| Measure::new(vec![voice]))
+            .collect();
+
+        let mut score = Score::new(vec![Part::new(part_name, vec![Staff::new(measures)])]);
+        score.set_key_at(0, self.key);
+        score.set_time_signature_at(0, self.time_signature);
+        score.set_tempo_at(0, self.tempo);
+
+        score
+    }
+}
@@ -0,0 +1,51 @@
+use crate::scores::Staff;
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Part {
+    name: String,
+    midi_program: Option<u8>,
+    staves: Vec<Staff>,
+}
+
+impl Part {
+    pub fn new(name: &str, staves: Vec<Staff>) -> Part {
+        Part {
+            name: name.to_string(),
+            midi_program: None,
+            staves,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// The General MIDI program (0-127) this part should be played back with, if any
+    pub fn get_midi_program(&self) -> Option<u8> {
+        self.midi_program
+    }
+
+    pub fn get_staves(&self) -> &[Staff] {
+        &self.staves
+    }
+
+    pub fn get_staves_mut(&mut self) -> &mut Vec<Staff> {
+        &mut self.staves
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_string();
+    }
+
+    pub fn set_midi_program(&mut self, midi_program: Option<u8>) {
+        self.midi_program = midi_program.map(|program| program.min(127));
+    }
+
+    pub fn add_staff(&mut self, staff: Staff) {
+        self.staves.push(staff);
+    }
+
+    pub fn get_measure_count(&self) -> usize {
+        self.staves.iter().map(|staff| staff.get_measures().len()).max().unwrap_or(0)
+    }
+}
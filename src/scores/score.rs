@@ -0,0 +1,188 @@
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::keys::Key;
+use crate::notes::LetterNote;
+use crate::rhythm::{Duration, Tempo, TimeSignature};
+use crate::scores::{Melody, Part, TimedNote};
+
+/// Key, time signature and tempo changes are keyed by the index of the measure they take effect from
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Score {
+    title: Option<String>,
+    composer: Option<String>,
+    key_changes: Vec<(usize, Key)>,
+    time_signature_changes: Vec<(usize, TimeSignature)>,
+    tempo_changes: Vec<(usize, Tempo)>,
+    parts: Vec<Part>,
+}
+
+impl Score {
+    pub fn new(parts: Vec<Part>) -> Score {
+        Score {
+            parts,
+            ..Default::default()
+        }
+    }
+
+    pub fn get_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn get_composer(&self) -> Option<&str> {
+        self.composer.as_deref()
+    }
+
+    pub fn get_parts(&self) -> &[Part] {
+        &self.parts
+    }
+
+    pub fn get_parts_mut(&mut self) -> &mut Vec<Part> {
+        &mut self.parts
+    }
+
+    pub fn get_key_changes(&self) -> &[(usize, Key)] {
+        &self.key_changes
+    }
+
+    pub fn get_time_signature_changes(&self) -> &[(usize, TimeSignature)] {
+        &self.time_signature_changes
+    }
+
+    pub fn get_tempo_changes(&self) -> &[(usize, Tempo)] {
+        &self.tempo_changes
+    }
+
+    pub fn get_key_at(&self, measure_index: usize) -> Key {
+        get_change_at(&self.key_changes, measure_index).unwrap_or_default()
+    }
+
+    pub fn get_time_signature_at(&self, measure_index: usize) -> TimeSignature {
+        get_change_at(&self.time_signature_changes, measure_index).unwrap_or_default()
+    }
+
+    pub fn get_tempo_at(&self, measure_index: usize) -> Tempo {
+        get_change_at(&self.tempo_changes, measure_index).unwrap_or_default()
+    }
+
+    pub fn get_measure_count(&self) -> usize {
+        self.parts.iter().map(|part| part.get_measure_count()).max().unwrap_or(0)
+    }
+
+    /// The time from the start of the score to the start of the given measure, going by the time signatures in effect
+    pub fn get_measure_start(&self, measure_index: usize) -> Duration {
+        (0..measure_index).fold(Duration::zero(), |total, index| total + self.get_time_signature_at(index).get_measure_duration())
+    }
+
+    pub fn set_title(&mut self, title: Option<String>) {
+        self.title = title;
+    }
+
+    pub fn set_composer(&mut self, composer: Option<String>) {
+        self.composer = composer;
+    }
+
+    pub fn set_key_at(&mut self, measure_index: usize, key: Key) {
+        set_change_at(&mut self.key_changes, measure_index, key);
+    }
+
+    pub fn set_time_signature_at(&mut self, measure_index: usize, time_signature: TimeSignature) {
+        set_change_at(&mut self.time_signature_changes, measure_index, time_signature);
+    }
+
+    pub fn set_tempo_at(&mut self, measure_index: usize, tempo: Tempo) {
+        set_change_at(&mut self.tempo_changes, measure_index, tempo);
+    }
+
+    pub fn add_part(&mut self, part: Part) {
+        self.parts.push(part);
+    }
+
+    /// Strings the given voice of every measure on a staff together, filling measures missing that voice with rests
+    pub fn try_get_melody(&self, part_index: usize, staff_index: usize, voice_index: usize) -> Result<Melody, Box<dyn CrispiiError>> {
+        let part = self.parts.get(part_index)
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("part_index", format!("The score only has {} parts", self.parts.len()).as_str())) })?;
+        let staff = part.get_staves().get(staff_index)
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("staff_index", format!("{} only has {} staves", part.get_name(), part.get_staves().len()).as_str())) })?;
+
+        let mut melody = Melody::new(self.get_key_at(0), self.get_time_signature_at(0), self.get_tempo_at(0), Vec::new());
+
+        for (measure_index, measure) in staff.get_measures().iter().enumerate() {
+            match measure.get_voices().get(voice_index) {
+                Some(voice) => melody.get_events_mut().extend(voice.get_events().iter().cloned()),
+                None => melody.add_rest(self.get_time_signature_at(measure_index).get_measure_duration()),
+            }
+        }
+
+        Ok(melody)
+    }
+
+    /// Every sounding note in the score ordered by start time, with tied notes merged into one
+    pub fn get_timed_notes(&self) -> Vec<TimedNote> {
+        let mut timed_notes = Vec::new();
+
+        for (part_index, part) in self.parts.iter().enumerate() {
+            for staff in part.get_staves() {
+                let voice_count = staff.get_measures().iter().map(|measure| measure.get_voices().len()).max().unwrap_or(0);
+
+                for voice_index in 0..voice_count {
+                    let mut tied_notes: Vec<(LetterNote, Duration, Duration)> = Vec::new();
+
+                    for (measure_index, measure) in staff.get_measures().iter().enumerate() {
+                        let Some(voice) = measure.get_voices().get(voice_index) else {
+                            continue;
+                        };
+                        let mut start = self.get_measure_start(measure_index);
+
+                        for event in voice.get_events() {
+                            let end = start + event.get_duration();
+                            let notes = event.get_notes();
+                            let mut continuing = Vec::new();
+
+                            for (note, note_start, note_end) in tied_notes.drain(..) {
+                                if notes.iter().any(|other| other.get_pitch_number() == note.get_pitch_number()) {
+                                    continuing.push((note, note_start));
+                                } else {
+                                    timed_notes.push(TimedNote::new(part_index, note, note_start, note_end.try_subtract(note_start).unwrap_or_else(|_| Duration::zero())));
+                                }
+                            }
+
+                            for note in notes {
+                                let note_start = continuing.iter()
+                                    .find(|(other, _)| other.get_pitch_number() == note.get_pitch_number())
+                                    .map(|(_, note_start)| *note_start)
+                                    .unwrap_or(start);
+
+                                if event.is_tied() {
+                                    tied_notes.push((note, note_start, end));
+                                } else {
+                                    timed_notes.push(TimedNote::new(part_index, note, note_start, end.try_subtract(note_start).unwrap_or_else(|_| Duration::zero())));
+                                }
+                            }
+
+                            start = end;
+                        }
+                    }
+
+                    for (note, note_start, note_end) in tied_notes {
+                        timed_notes.push(TimedNote::new(part_index, note, note_start, note_end.try_subtract(note_start).unwrap_or_else(|_| Duration::zero())));
+                    }
+                }
+            }
+        }
+
+        timed_notes.sort_by_key(|timed_note| (timed_note.get_start(), timed_note.get_part_index()));
+
+        timed_notes
+    }
+}
+
+fn get_change_at<T: Copy>(changes: &[(usize, T)], measure_index: usize) -> Option<T> {
+    changes.iter().rev().find(|(index, _)| *index <= measure_index).map(|(_, change)| *change)
+}
+
+fn set_change_at<T>(changes: &mut Vec<(usize, T)>, measure_index: usize, change: T) {
+    match changes.binary_search_by_key(&measure_index, |(index, _)| *index) {
+        Ok(position) => changes[position].1 = change,
+        Err(position) => changes.insert(position, (measure_index, change)),
+    }
+}
@@ -0,0 +1,24 @@
+use crate::scores::Measure;
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Staff {
+    measures: Vec<Measure>,
+}
+
+impl Staff {
+    pub fn new(measures: Vec<Measure>) -> Staff {
+        Staff { measures }
+    }
+
+    pub fn get_measures(&self) -> &[Measure] {
+        &self.measures
+    }
+
+    pub fn get_measures_mut(&mut self) -> &mut Vec<Measure> {
+        &mut self.measures
+    }
+
+    pub fn add_measure(&mut self, measure: Measure) {
+        self.measures.push(measure);
+    }
+}
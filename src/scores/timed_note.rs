@@ -0,0 +1,42 @@
+use crate::notes::LetterNote;
+use crate::rhythm::Duration;
+
+/// A sounding note with its position measured from the start of the score, with any ties already merged
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TimedNote {
+    part_index: usize,
+    note: LetterNote,
+    start: Duration,
+    duration: Duration,
+}
+
+impl TimedNote {
+    pub fn new(part_index: usize, note: LetterNote, start: Duration, duration: Duration) -> TimedNote {
+        TimedNote {
+            part_index,
+            note,
+            start,
+            duration,
+        }
+    }
+
+    pub fn get_part_index(&self) -> usize {
+        self.part_index
+    }
+
+    pub fn get_note(&self) -> LetterNote {
+        self.note
+    }
+
+    pub fn get_start(&self) -> Duration {
+        self.start
+    }
+
+    pub fn get_duration(&self) -> Duration {
+        self.duration
+    }
+
+    pub fn get_end(&self) -> Duration {
+        self.start + self.duration
+    }
+}
@@ -0,0 +1,70 @@
+use crate::rhythm::Duration;
+use crate::scores::Event;
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Voice {
+    events: Vec<Event>,
+}
+
+impl Voice {
+    pub fn new(events: Vec<Event>) -> Voice {
+        Voice { events }
+    }
+
+    pub fn get_events(&self) -> &[Event] {
+        &self.events
+    }
+
+    pub fn get_events_mut(&mut self) -> &mut Vec<Event> {
+        &mut self.events
+    }
+
+    pub fn add_event(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    pub fn get_duration(&self) -> Duration {
+        self.events.iter().fold(Duration::zero(), |total, event| total + event.get_duration())
+    }
+
+    /// Cuts the voice at each barline, tying notes that cross one. The last measure is left short if the events run out before it is full
+    pub fn split_into_measures<F: Fn(usize) -> Duration>(&self, get_measure_duration: F) -> Vec<Voice> {
+        let mut measures = Vec::new();
+        let mut voice = Voice::default();
+        let mut remaining = get_measure_duration(0);
+
+        for event in &self.events {
+            let mut event = event.clone();
+
+            while event.get_duration() > remaining {
+                if !remaining.is_zero() {
+                    let mut head = event.clone();
+                    head.set_duration(remaining);
+                    head.set_tied(!event.is_rest());
+                    voice.add_event(head);
+
+                    event.set_duration(event.get_duration().try_subtract(remaining).expect("The event is longer than the remaining space"));
+                    event.set_lyric(None);
+                    event.set_chord_symbol(None);
+                }
+
+                measures.push(std::mem::take(&mut voice));
+                remaining = get_measure_duration(measures.len());
+            }
+
+            remaining = remaining.try_subtract(event.get_duration()).expect("The event fits in the remaining space");
+            voice.add_event(event);
+
+            if remaining.is_zero() {
+                measures.push(std::mem::take(&mut voice));
+                remaining = get_measure_duration(measures.len());
+            }
+        }
+
+        if !voice.get_events().is_empty() {
+            measures.push(voice);
+        }
+
+        measures
+    }
+}
@@ -0,0 +1,44 @@
+mod interval;
+pub use interval::Interval;
+
+mod interval_direction;
+pub use interval_direction::IntervalDirection;
+
+mod scale;
+pub use scale::Scale;
+
+mod exercise_kind;
+pub use exercise_kind::ExerciseKind;
+
+mod difficulty;
+pub use difficulty::Difficulty;
+
+mod answer;
+pub use answer::Answer;
+
+mod question;
+pub use question::Question;
+
+mod exercise_generator;
+pub use exercise_generator::ExerciseGenerator;
+
+mod mistake;
+pub use mistake::Mistake;
+
+mod note_feedback;
+pub use note_feedback::NoteFeedback;
+
+mod dictation_result;
+pub use dictation_result::DictationResult;
+
+mod dictation_grader;
+pub use dictation_grader::DictationGrader;
+
+mod review_grade;
+pub use review_grade::ReviewGrade;
+
+mod item_progress;
+pub use item_progress::ItemProgress;
+
+mod progress_tracker;
+pub use progress_tracker::ProgressTracker;
@@ -0,0 +1,84 @@
+use std::fmt::Display;
+
+use crate::chords::ChordQuality;
+use crate::keys::Mode;
+use crate::training::{ExerciseKind, Interval, Scale};
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Answer {
+    Interval(Interval),
+    ChordQuality(ChordQuality),
+    Scale(Scale),
+    Mode(Mode),
+}
+
+impl Default for Answer {
+    fn default() -> Self {
+        Answer::Interval(Interval::default())
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Interval(interval) => write!(f, "{interval}"),
+            Answer::ChordQuality(chord_quality) => write!(f, "{chord_quality}"),
+            Answer::Scale(scale) => write!(f, "{scale}"),
+            Answer::Mode(mode) => write!(f, "{mode}"),
+        }
+    }
+}
+
+impl Answer {
+    /// Every answer a question of the given kind could have
+    pub fn get_all(kind: ExerciseKind) -> Vec<Answer> {
+        match kind {
+            ExerciseKind::Interval => Interval::ALL.iter().map(|interval| Answer::Interval(*interval)).collect(),
+            ExerciseKind::ChordQuality => ChordQuality::ALL.iter().map(|chord_quality| Answer::ChordQuality(*chord_quality)).collect(),
+            ExerciseKind::Scale => Scale::ALL.iter().map(|scale| Answer::Scale(*scale)).collect(),
+            ExerciseKind::Mode => Mode::ALL.iter().map(|mode| Answer::Mode(*mode)).collect(),
+        }
+    }
+
+    pub fn get_kind(&self) -> ExerciseKind {
+        match self {
+            Answer::Interval(_) => ExerciseKind::Interval,
+            Answer::ChordQuality(_) => ExerciseKind::ChordQuality,
+            Answer::Scale(_) => ExerciseKind::Scale,
+            Answer::Mode(_) => ExerciseKind::Mode,
+        }
+    }
+
+    /// The id progress is tracked under, such as "Interval: Minor Sixth"
+    pub fn get_item_id(&self) -> String {
+        format!("{}: {self}", self.get_kind())
+    }
+
+    /// Whether the text names this answer, either in full (ignoring case) or by its abbreviation (respecting case, as "m3" and "M3" differ)
+    pub fn is_named_by(&self, text: &str) -> bool {
+        let text = text.trim();
+
+        if self.to_string().eq_ignore_ascii_case(text) {
+            return true;
+        }
+
+        match self {
+            Answer::Interval(interval) => interval.get_short_name() == text,
+            Answer::ChordQuality(chord_quality) => chord_quality.get_symbol_aliases().iter().any(|alias| !alias.is_empty() && *alias == text),
+            Answer::Scale(_) | Answer::Mode(_) => false,
+        }
+    }
+
+    /// How alike two answers of the same kind are, lower being more alike, used to pick convincing distractors
+    pub(crate) fn get_distance(&self, other: &Answer) -> u32 {
+        match (self, other) {
+            (Answer::Interval(interval), Answer::Interval(other)) => interval.get_semitones().abs_diff(other.get_semitones()) as u32,
+            _ => {
+                let all = Answer::get_all(self.get_kind());
+                let position = |answer: &Answer| all.iter().position(|candidate| candidate == answer).unwrap_or(all.len());
+
+                position(self).abs_diff(position(other)) as u32
+            }
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::keys::Key;
+use crate::notes::{LetterNote, NumberNote};
+use crate::rhythm::Duration;
+use crate::scores::{Melody, TimedNote};
+use crate::training::{DictationResult, Mistake, NoteFeedback};
+
+/// Lines a student's dictation up against the target with a Needleman-Wunsch alignment, so one missing or extra note does not throw off
+/// the marking of everything after it
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DictationGrader {
+    octave_credit: f64,
+    enharmonic_credit: f64,
+    rhythm_penalty: f64,
+}
+
+impl Default for DictationGrader {
+    /// Half credit for the wrong octave, three quarters for a misspelling and a quarter taken off for the wrong rhythm
+    fn default() -> Self {
+        DictationGrader {
+            octave_credit: 0.5,
+            enharmonic_credit: 0.75,
+            rhythm_penalty: 0.25,
+        }
+    }
+}
+
+impl DictationGrader {
+    pub fn new() -> DictationGrader {
+        DictationGrader::default()
+    }
+
+    pub fn get_octave_credit(&self) -> f64 {
+        self.octave_credit
+    }
+
+    pub fn get_enharmonic_credit(&self) -> f64 {
+        self.enharmonic_credit
+    }
+
+    pub fn get_rhythm_penalty(&self) -> f64 {
+        self.rhythm_penalty
+    }
+
+    /// The share of a note's credit kept when it is written in the wrong octave
+    pub fn try_set_octave_credit(&mut self, octave_credit: f64) -> Result<(), Box<dyn CrispiiError>> {
+        self.octave_credit = try_check_fraction("octave_credit", octave_credit)?;
+        Ok(())
+    }
+
+    /// The share of a note's credit kept when it is spelt with the wrong letter
+    pub fn try_set_enharmonic_credit(&mut self, enharmonic_credit: f64) -> Result<(), Box<dyn CrispiiError>> {
+        self.enharmonic_credit = try_check_fraction("enharmonic_credit", enharmonic_credit)?;
+        Ok(())
+    }
+
+    /// The credit taken off a note held for the wrong length
+    pub fn try_set_rhythm_penalty(&mut self, rhythm_penalty: f64) -> Result<(), Box<dyn CrispiiError>> {
+        self.rhythm_penalty = try_check_fraction("rhythm_penalty", rhythm_penalty)?;
+        Ok(())
+    }
+
+    /// Grades pitches alone
+    pub fn grade_notes(&self, target: &[LetterNote], response: &[LetterNote]) -> DictationResult {
+        let target: Vec<(LetterNote, Option<Duration>)> = target.iter().map(|note| (*note, None)).collect();
+        let response: Vec<(LetterNote, Option<Duration>)> = response.iter().map(|note| (*note, None)).collect();
+
+        self.grade(&target, &response)
+    }
+
+    /// Grades scale degrees alone, reading both sequences in the same (arbitrary) key so spelling and octave mistakes still show
+    pub fn try_grade_number_notes(&self, target: &[NumberNote], response: &[NumberNote]) -> Result<DictationResult, Box<dyn CrispiiError>> {
+        let key = Key::default();
+        let target = target.iter().map(|number_note| key.try_get_letter_note(*number_note)).collect::<Result<Vec<_>, _>>()?;
+        let response = response.iter().map(|number_note| key.try_get_letter_note(*number_note)).collect::<Result<Vec<_>, _>>()?;
+
+        Ok(self.grade_notes(&target, &response))
+    }
+
+    /// Grades pitches and how long each is held
+    pub fn grade_timed_notes(&self, target: &[TimedNote], response: &[TimedNote]) -> DictationResult {
+        let target: Vec<(LetterNote, Option<Duration>)> = target.iter().map(|timed_note| (timed_note.get_note(), Some(timed_note.get_duration()))).collect();
+        let response: Vec<(LetterNote, Option<Duration>)> = response.iter().map(|timed_note| (timed_note.get_note(), Some(timed_note.get_duration()))).collect();
+
+        self.grade(&target, &response)
+    }
+
+    /// Grades the sounding notes of two melodies, with tied notes merged
+    pub fn grade_melodies(&self, target: &Melody, response: &Melody) -> DictationResult {
+        self.grade_timed_notes(&target.to_score("Target").get_timed_notes(), &response.to_score("Response").get_timed_notes())
+    }
+
+    fn grade(&self, target: &[(LetterNote, Option<Duration>)], response: &[(LetterNote, Option<Duration>)]) -> DictationResult {
+        let rows = target.len() + 1;
+        let columns = response.len() + 1;
+        let mut costs = vec![0.0; rows * columns];
+
+        for row in 0..rows {
+            for column in 0..columns {
+                costs[row * columns + column] = match (row, column) {
+                    (0, _) => column as f64,
+                    (_, 0) => row as f64,
+                    _ => {
+                        let (_, credit) = self.compare(target[row - 1], response[column - 1]);
+                        let matched = costs[(row - 1) * columns + column - 1] + 1.0 - credit;
+                        let missing = costs[(row - 1) * columns + column] + 1.0;
+                        let extra = costs[row * columns + column - 1] + 1.0;
+
+                        matched.min(missing).min(extra)
+                    }
+                };
+            }
+        }
+
+        let mut feedback = Vec::new();
+        let (mut row, mut column) = (target.len(), response.len());
+
+        while row > 0 || column > 0 {
+            let cost = costs[row * columns + column];
+
+            if row > 0 && column > 0 {
+                let (mistakes, credit) = self.compare(target[row - 1], response[column - 1]);
+
+                if (costs[(row - 1) * columns + column - 1] + 1.0 - credit - cost).abs() < 1e-9 {
+                    feedback.push(NoteFeedback::new(Some(row - 1), Some(column - 1), Some(target[row - 1]), Some(response[column - 1]), mistakes, credit));
+                    row -= 1;
+                    column -= 1;
+                    continue;
+                }
+            }
+
+            if row > 0 && (column == 0 || (costs[(row - 1) * columns + column] + 1.0 - cost).abs() < 1e-9) {
+                feedback.push(NoteFeedback::new(Some(row - 1), None, Some(target[row - 1]), None, vec![Mistake::Missing], 0.0));
+                row -= 1;
+            } else {
+                feedback.push(NoteFeedback::new(None, Some(column - 1), None, Some(response[column - 1]), vec![Mistake::Extra], 0.0));
+                column -= 1;
+            }
+        }
+
+        feedback.reverse();
+        DictationResult::new(feedback)
+    }
+
+    fn compare(&self, (target, target_duration): (LetterNote, Option<Duration>), (response, response_duration): (LetterNote, Option<Duration>)) -> (Vec<Mistake>, f64) {
+        let same_spelling = target.get_letter_index() == response.get_letter_index() && target.get_modifier() == response.get_modifier();
+        let same_pitch_class = (target.get_pitch_number() - response.get_pitch_number()).rem_euclid(12) == 0;
+        let mut mistakes = Vec::new();
+        let mut credit = 1.0;
+
+        if !same_pitch_class {
+            mistakes.push(Mistake::WrongPitch);
+            credit = 0.0;
+        } else {
+            if !same_spelling {
+                mistakes.push(Mistake::EnharmonicMisspelling);
+                credit *= self.enharmonic_credit;
+            }
+
+            if target.get_pitch_number() != response.get_pitch_number() {
+                mistakes.push(Mistake::WrongOctave);
+                credit *= self.octave_credit;
+            }
+        }
+
+        if let (Some(target_duration), Some(response_duration)) = (target_duration, response_duration)
+            && target_duration != response_duration
+        {
+            mistakes.push(Mistake::WrongRhythm);
+            credit = f64::max(credit - self.rhythm_penalty, 0.0);
+        }
+
+        (mistakes, credit)
+    }
+}
+
+fn try_check_fraction(name: &str, value: f64) -> Result<f64, Box<dyn CrispiiError>> {
+    match (0.0..=1.0).contains(&value) {
+        true => Ok(value),
+        false => Err(Box::new(InvalidArgumentError::new(name, "Must be between 0 and 1 (inclusive)"))),
+    }
+}
@@ -0,0 +1,33 @@
+use crate::training::{Mistake, NoteFeedback};
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct DictationResult {
+    feedback: Vec<NoteFeedback>,
+}
+
+impl DictationResult {
+    pub(crate) fn new(feedback: Vec<NoteFeedback>) -> DictationResult {
+        DictationResult { feedback }
+    }
+
+    /// One entry per aligned column, in order
+    pub fn get_feedback(&self) -> &[NoteFeedback] {
+        &self.feedback
+    }
+
+    /// The average credit over every column, from 0 (nothing right) to 1 (everything right). Extra notes count as columns with no credit
+    pub fn get_score(&self) -> f64 {
+        match self.feedback.is_empty() {
+            true => 1.0,
+            false => self.feedback.iter().map(|feedback| feedback.get_credit()).sum::<f64>() / self.feedback.len() as f64,
+        }
+    }
+
+    pub fn get_mistake_count(&self, mistake: Mistake) -> usize {
+        self.feedback.iter().filter(|feedback| feedback.get_mistakes().contains(&mistake)).count()
+    }
+
+    pub fn is_perfect(&self) -> bool {
+        self.feedback.iter().all(|feedback| feedback.is_correct())
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt::Display;
+
+use crate::chords::ChordQuality;
+use crate::keys::Mode;
+use crate::notes::Modifier;
+use crate::training::{Interval, IntervalDirection, Scale};
+
+/// Sets the starting pools of an `ExerciseGenerator`, each level adding harder material to the last
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum Difficulty {
+    #[default]
+    Beginner,
+    Intermediate,
+    Advanced,
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Difficulty::Beginner => write!(f, "Beginner"),
+            Difficulty::Intermediate => write!(f, "Intermediate"),
+            Difficulty::Advanced => write!(f, "Advanced"),
+        }
+    }
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Beginner, Difficulty::Intermediate, Difficulty::Advanced];
+
+    pub fn get_intervals(&self) -> &'static [Interval] {
+        match self {
+            Difficulty::Beginner => &[Interval::MajorSecond, Interval::MajorThird, Interval::PerfectFourth, Interval::PerfectFifth, Interval::Octave],
+            Difficulty::Intermediate => &[
+                Interval::MinorSecond,
+                Interval::MajorSecond,
+                Interval::MinorThird,
+                Interval::MajorThird,
+                Interval::PerfectFourth,
+                Interval::PerfectFifth,
+                Interval::MinorSixth,
+                Interval::MajorSixth,
+                Interval::Octave,
+            ],
+            Difficulty::Advanced => &Interval::ALL,
+        }
+    }
+
+    pub fn get_interval_directions(&self) -> &'static [IntervalDirection] {
+        match self {
+            Difficulty::Beginner => &[IntervalDirection::Ascending],
+            Difficulty::Intermediate => &[IntervalDirection::Ascending, IntervalDirection::Descending],
+            Difficulty::Advanced => &IntervalDirection::ALL,
+        }
+    }
+
+    pub fn get_chord_qualities(&self) -> &'static [ChordQuality] {
+        match self {
+            Difficulty::Beginner => &[ChordQuality::Major, ChordQuality::Minor],
+            Difficulty::Intermediate => &[ChordQuality::Major, ChordQuality::Minor, ChordQuality::Diminished, ChordQuality::Augmented],
+            Difficulty::Advanced => &[
+                ChordQuality::Major,
+                ChordQuality::Minor,
+                ChordQuality::Diminished,
+                ChordQuality::Augmented,
+                ChordQuality::SuspendedFourth,
+                ChordQuality::DominantSeventh,
+                ChordQuality::MajorSeventh,
+                ChordQuality::MinorSeventh,
+                ChordQuality::HalfDiminishedSeventh,
+                ChordQuality::DiminishedSeventh,
+            ],
+        }
+    }
+
+    pub fn get_scales(&self) -> &'static [Scale] {
+        match self {
+            Difficulty::Beginner => &[Scale::Major, Scale::NaturalMinor],
+            Difficulty::Intermediate => &[Scale::Major, Scale::NaturalMinor, Scale::HarmonicMinor, Scale::MajorPentatonic, Scale::MinorPentatonic],
+            Difficulty::Advanced => &Scale::ALL,
+        }
+    }
+
+    pub fn get_modes(&self) -> &'static [Mode] {
+        match self {
+            Difficulty::Beginner => &[Mode::Major, Mode::Minor],
+            Difficulty::Intermediate => &[Mode::Major, Mode::Dorian, Mode::Mixolydian, Mode::Minor],
+            Difficulty::Advanced => &Mode::ALL,
+        }
+    }
+
+    /// The modifiers question roots may be spelt with
+    pub fn get_root_modifiers(&self) -> &'static [Modifier] {
+        match self {
+            Difficulty::Beginner => &[Modifier::Default],
+            _ => &[Modifier::Flat, Modifier::Default, Modifier::Sharp],
+        }
+    }
+
+    /// How many choices each question offers, the right answer included
+    pub fn get_choice_count(&self) -> usize {
+        match self {
+            Difficulty::Beginner => 3,
+            Difficulty::Intermediate => 4,
+            Difficulty::Advanced => 6,
+        }
+    }
+}
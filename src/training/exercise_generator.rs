@@ -0,0 +1,230 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rand::seq::SliceRandom;
+
+use crispii_errors::{CrispiiError, ImpossibleOperationError, InvalidArgumentError};
+
+use crate::chords::ChordQuality;
+use crate::keys::Mode;
+use crate::notes::{LetterNote, Modifier, Octave};
+use crate::sampling::{LetterNoteSampler, weighted_choice};
+use crate::training::{Answer, Difficulty, ExerciseKind, Interval, IntervalDirection, ProgressTracker, Question, Scale};
+
+const MAX_ROOT_ATTEMPTS: usize = 64;
+
+/// Draws recognition questions from configurable pools, with roots drawn by a `LetterNoteSampler`
+#[derive(Clone, PartialEq, Debug)]
+pub struct ExerciseGenerator {
+    intervals: Vec<Interval>,
+    interval_directions: Vec<IntervalDirection>,
+    chord_qualities: Vec<ChordQuality>,
+    scales: Vec<Scale>,
+    modes: Vec<Mode>,
+    choice_count: usize,
+    root_sampler: LetterNoteSampler,
+}
+
+impl Default for ExerciseGenerator {
+    fn default() -> Self {
+        ExerciseGenerator::new(Difficulty::default())
+    }
+}
+
+impl ExerciseGenerator {
+    /// Starts from the pools, root spellings and choice count of the difficulty level, with roots in octave four. Below the advanced level
+    /// E#, B#, Fb and Cb are never roots
+    pub fn new(difficulty: Difficulty) -> ExerciseGenerator {
+        let mut root_sampler = LetterNoteSampler::new();
+        root_sampler.set_modifiers(difficulty.get_root_modifiers());
+
+        if difficulty < Difficulty::Advanced {
+            for root in [LetterNote::E(Modifier::Sharp, Octave::Four), LetterNote::B(Modifier::Sharp, Octave::Four), LetterNote::F(Modifier::Flat, Octave::Four), LetterNote::C(Modifier::Flat, Octave::Four)] {
+                root_sampler.try_set_weight(root, 0.0).expect("A weight of 0 is valid");
+            }
+        }
+
+        ExerciseGenerator {
+            intervals: difficulty.get_intervals().to_vec(),
+            interval_directions: difficulty.get_interval_directions().to_vec(),
+            chord_qualities: difficulty.get_chord_qualities().to_vec(),
+            scales: difficulty.get_scales().to_vec(),
+            modes: difficulty.get_modes().to_vec(),
+            choice_count: difficulty.get_choice_count(),
+            root_sampler,
+        }
+    }
+
+    pub fn get_intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn get_interval_directions(&self) -> &[IntervalDirection] {
+        &self.interval_directions
+    }
+
+    pub fn get_chord_qualities(&self) -> &[ChordQuality] {
+        &self.chord_qualities
+    }
+
+    pub fn get_scales(&self) -> &[Scale] {
+        &self.scales
+    }
+
+    pub fn get_modes(&self) -> &[Mode] {
+        &self.modes
+    }
+
+    pub fn get_choice_count(&self) -> usize {
+        self.choice_count
+    }
+
+    pub fn get_root_sampler(&self) -> &LetterNoteSampler {
+        &self.root_sampler
+    }
+
+    pub fn get_root_sampler_mut(&mut self) -> &mut LetterNoteSampler {
+        &mut self.root_sampler
+    }
+
+    pub fn set_intervals(&mut self, intervals: &[Interval]) {
+        self.intervals = intervals.to_vec();
+    }
+
+    pub fn set_interval_directions(&mut self, interval_directions: &[IntervalDirection]) {
+        self.interval_directions = interval_directions.to_vec();
+    }
+
+    pub fn set_chord_qualities(&mut self, chord_qualities: &[ChordQuality]) {
+        self.chord_qualities = chord_qualities.to_vec();
+    }
+
+    pub fn set_scales(&mut self, scales: &[Scale]) {
+        self.scales = scales.to_vec();
+    }
+
+    pub fn set_modes(&mut self, modes: &[Mode]) {
+        self.modes = modes.to_vec();
+    }
+
+    /// Choices beyond the size of the pool are made up with the answers closest to the right one from outside it
+    pub fn try_set_choice_count(&mut self, choice_count: usize) -> Result<(), Box<dyn CrispiiError>> {
+        if choice_count < 2 {
+            return Err(Box::new(InvalidArgumentError::new("choice_count", "Must be at least 2")));
+        }
+
+        self.choice_count = choice_count;
+        Ok(())
+    }
+
+    pub fn set_root_sampler(&mut self, root_sampler: LetterNoteSampler) {
+        self.root_sampler = root_sampler;
+    }
+
+    pub fn try_generate(&mut self, kind: ExerciseKind, seed: u64) -> Result<Question, Box<dyn CrispiiError>> {
+        self.try_generate_from_rng(kind, &mut ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    pub fn try_generate_from_rng<R: rand::Rng + ?Sized>(&mut self, kind: ExerciseKind, rng: &mut R) -> Result<Question, Box<dyn CrispiiError>> {
+        let pool = self.try_get_pool(kind)?;
+
+        self.try_generate_for_answer(pool[rng.random_range(0..pool.len())], rng)
+    }
+
+    /// Lets the tracker decide which answer from the pool to ask about next (new items are taken in a random order), so practice goes
+    /// where it is due rather than anywhere at random
+    pub fn try_generate_scheduled<R: rand::Rng + ?Sized>(&mut self, kind: ExerciseKind, tracker: &ProgressTracker, now: u64, rng: &mut R) -> Result<Question, Box<dyn CrispiiError>> {
+        let mut pool = self.try_get_pool(kind)?;
+        pool.shuffle(rng);
+
+        let item_ids: Vec<String> = pool.iter().map(|answer| answer.get_item_id()).collect();
+        let candidates: Vec<&str> = item_ids.iter().map(String::as_str).collect();
+        let next = tracker.select_next(&candidates, now).expect("The pool has already been checked for emptiness");
+        let answer = pool[candidates.iter().position(|candidate| *candidate == next).expect("The tracker picks from the candidates")];
+
+        self.try_generate_for_answer(answer, rng)
+    }
+
+    /// Builds a question with the given answer on a sampled root, whether or not the answer is in its pool
+    pub fn try_generate_for_answer<R: rand::Rng + ?Sized>(&mut self, answer: Answer, rng: &mut R) -> Result<Question, Box<dyn CrispiiError>> {
+        let kind = answer.get_kind();
+        let pool = self.get_pool(kind);
+        let direction = match self.interval_directions.is_empty() {
+            true => IntervalDirection::default(),
+            false => self.interval_directions[rng.random_range(0..self.interval_directions.len())],
+        };
+
+        let mut notes = None;
+
+        for _ in 0..MAX_ROOT_ATTEMPTS {
+            let root = self.root_sampler.try_sample(rng)?;
+
+            if let Ok(built) = build_notes(root, answer, direction) {
+                notes = Some(built);
+                break;
+            }
+        }
+
+        let notes = notes.ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(ImpossibleOperationError::new(format!("None of the roots tried could spell a {answer} without going beyond double sharps or flats").as_str())) })?;
+        let harmonic = match answer {
+            Answer::Interval(_) => direction == IntervalDirection::Harmonic,
+            Answer::ChordQuality(_) => true,
+            Answer::Scale(_) | Answer::Mode(_) => false,
+        };
+
+        let mut choices = vec![answer];
+        let mut distractors: Vec<(Answer, f64)> = pool.iter()
+            .filter(|candidate| **candidate != answer)
+            .map(|candidate| (*candidate, 1.0 / (1.0 + answer.get_distance(candidate) as f64)))
+            .collect();
+
+        while choices.len() < self.choice_count && !distractors.is_empty() {
+            let distractor = weighted_choice::try_choose(&distractors, rng)?;
+            distractors.retain(|(candidate, _)| *candidate != distractor);
+            choices.push(distractor);
+        }
+
+        let mut outsiders: Vec<Answer> = Answer::get_all(kind).into_iter().filter(|candidate| !choices.contains(candidate)).collect();
+        outsiders.sort_by_key(|candidate| answer.get_distance(candidate));
+        choices.extend(outsiders.into_iter().take(self.choice_count.saturating_sub(choices.len())));
+        choices.shuffle(rng);
+
+        Ok(Question::new(notes, harmonic, answer, choices))
+    }
+
+    fn try_get_pool(&self, kind: ExerciseKind) -> Result<Vec<Answer>, Box<dyn CrispiiError>> {
+        let pool = self.get_pool(kind);
+
+        if pool.is_empty() {
+            return Err(Box::new(ImpossibleOperationError::new(format!("There are no {} questions to ask with an empty pool", kind.to_string().to_lowercase()).as_str())));
+        }
+
+        Ok(pool)
+    }
+
+    fn get_pool(&self, kind: ExerciseKind) -> Vec<Answer> {
+        match kind {
+            ExerciseKind::Interval => self.intervals.iter().map(|interval| Answer::Interval(*interval)).collect(),
+            ExerciseKind::ChordQuality => self.chord_qualities.iter().map(|chord_quality| Answer::ChordQuality(*chord_quality)).collect(),
+            ExerciseKind::Scale => self.scales.iter().map(|scale| Answer::Scale(*scale)).collect(),
+            ExerciseKind::Mode => self.modes.iter().map(|mode| Answer::Mode(*mode)).collect(),
+        }
+    }
+}
+
+fn build_notes(root: LetterNote, answer: Answer, direction: IntervalDirection) -> Result<Vec<LetterNote>, Box<dyn CrispiiError>> {
+    match answer {
+        Answer::Interval(interval) => match direction {
+            IntervalDirection::Descending => Ok(vec![root, root.try_offset(-interval.get_letter_steps(), -interval.get_semitones())?]),
+            _ => Ok(vec![root, root.try_offset(interval.get_letter_steps(), interval.get_semitones())?]),
+        },
+        Answer::ChordQuality(chord_quality) => chord_quality.get_tones().iter().map(|(letter_steps, semitones)| root.try_offset(*letter_steps, *semitones)).collect(),
+        Answer::Scale(scale) => scale.get_tones().iter().map(|(letter_steps, semitones)| root.try_offset(*letter_steps, *semitones)).collect(),
+        Answer::Mode(mode) => mode.get_number_notes().iter()
+            .map(|number_note| {
+                let (letter_steps, semitones) = Scale::Major.get_tones()[number_note.get_degree() as usize - 1];
+                root.try_offset(letter_steps, semitones + number_note.get_modifier().get_semitone_offset() as i16)
+            })
+            .chain([root.try_offset(7, 12)])
+            .collect(),
+    }
+}
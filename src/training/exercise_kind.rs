@@ -0,0 +1,25 @@
+use std::fmt::Display;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum ExerciseKind {
+    #[default]
+    Interval,
+    ChordQuality,
+    Scale,
+    Mode,
+}
+
+impl Display for ExerciseKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExerciseKind::Interval => write!(f, "Interval"),
+            ExerciseKind::ChordQuality => write!(f, "Chord Quality"),
+            ExerciseKind::Scale => write!(f, "Scale"),
+            ExerciseKind::Mode => write!(f, "Mode"),
+        }
+    }
+}
+
+impl ExerciseKind {
+    pub const ALL: [ExerciseKind; 4] = [ExerciseKind::Interval, ExerciseKind::ChordQuality, ExerciseKind::Scale, ExerciseKind::Mode];
+}
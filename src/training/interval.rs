@@ -0,0 +1,120 @@
+use std::fmt::Display;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum Interval {
+    Unison,
+    MinorSecond,
+    #[default]
+    MajorSecond,
+    MinorThird,
+    MajorThird,
+    PerfectFourth,
+    Tritone,
+    PerfectFifth,
+    MinorSixth,
+    MajorSixth,
+    MinorSeventh,
+    MajorSeventh,
+    Octave,
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Interval::Unison => write!(f, "Unison"),
+            Interval::MinorSecond => write!(f, "Minor Second"),
+            Interval::MajorSecond => write!(f, "Major Second"),
+            Interval::MinorThird => write!(f, "Minor Third"),
+            Interval::MajorThird => write!(f, "Major Third"),
+            Interval::PerfectFourth => write!(f, "Perfect Fourth"),
+            Interval::Tritone => write!(f, "Tritone"),
+            Interval::PerfectFifth => write!(f, "Perfect Fifth"),
+            Interval::MinorSixth => write!(f, "Minor Sixth"),
+            Interval::MajorSixth => write!(f, "Major Sixth"),
+            Interval::MinorSeventh => write!(f, "Minor Seventh"),
+            Interval::MajorSeventh => write!(f, "Major Seventh"),
+            Interval::Octave => write!(f, "Octave"),
+        }
+    }
+}
+
+impl Interval {
+    pub const ALL: [Interval; 13] = [
+        Interval::Unison,
+        Interval::MinorSecond,
+        Interval::MajorSecond,
+        Interval::MinorThird,
+        Interval::MajorThird,
+        Interval::PerfectFourth,
+        Interval::Tritone,
+        Interval::PerfectFifth,
+        Interval::MinorSixth,
+        Interval::MajorSixth,
+        Interval::MinorSeventh,
+        Interval::MajorSeventh,
+        Interval::Octave,
+    ];
+
+    pub fn get_semitones(&self) -> i16 {
+        match self {
+            Interval::Unison => 0,
+            Interval::MinorSecond => 1,
+            Interval::MajorSecond => 2,
+            Interval::MinorThird => 3,
+            Interval::MajorThird => 4,
+            Interval::PerfectFourth => 5,
+            Interval::Tritone => 6,
+            Interval::PerfectFifth => 7,
+            Interval::MinorSixth => 8,
+            Interval::MajorSixth => 9,
+            Interval::MinorSeventh => 10,
+            Interval::MajorSeventh => 11,
+            Interval::Octave => 12,
+        }
+    }
+
+    /// How many letters the upper note sits above the lower one. The tritone is spelt as an augmented fourth
+    pub fn get_letter_steps(&self) -> i8 {
+        match self {
+            Interval::Unison => 0,
+            Interval::MinorSecond | Interval::MajorSecond => 1,
+            Interval::MinorThird | Interval::MajorThird => 2,
+            Interval::PerfectFourth | Interval::Tritone => 3,
+            Interval::PerfectFifth => 4,
+            Interval::MinorSixth | Interval::MajorSixth => 5,
+            Interval::MinorSeventh | Interval::MajorSeventh => 6,
+            Interval::Octave => 7,
+        }
+    }
+
+    /// The usual abbreviation, such as "m3" or "P5"
+    pub fn get_short_name(&self) -> &'static str {
+        match self {
+            Interval::Unison => "P1",
+            Interval::MinorSecond => "m2",
+            Interval::MajorSecond => "M2",
+            Interval::MinorThird => "m3",
+            Interval::MajorThird => "M3",
+            Interval::PerfectFourth => "P4",
+            Interval::Tritone => "TT",
+            Interval::PerfectFifth => "P5",
+            Interval::MinorSixth => "m6",
+            Interval::MajorSixth => "M6",
+            Interval::MinorSeventh => "m7",
+            Interval::MajorSeventh => "M7",
+            Interval::Octave => "P8",
+        }
+    }
+
+    /// Recognises intervals by their sound alone, so augmented and diminished spellings count as whichever interval they sound like.
+    /// Anything wider than an octave is folded back into one
+    pub fn from_semitones(semitones: i16) -> Interval {
+        let semitones = semitones.abs();
+
+        match semitones {
+            0 => Interval::Unison,
+            _ if semitones % 12 == 0 => Interval::Octave,
+            _ => Interval::ALL[(semitones % 12) as usize],
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use std::fmt::Display;
+
+/// How the two notes of an interval question are played
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum IntervalDirection {
+    #[default]
+    Ascending,
+    Descending,
+    /// Both notes together
+    Harmonic,
+}
+
+impl Display for IntervalDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntervalDirection::Ascending => write!(f, "Ascending"),
+            IntervalDirection::Descending => write!(f, "Descending"),
+            IntervalDirection::Harmonic => write!(f, "Harmonic"),
+        }
+    }
+}
+
+impl IntervalDirection {
+    pub const ALL: [IntervalDirection; 3] = [IntervalDirection::Ascending, IntervalDirection::Descending, IntervalDirection::Harmonic];
+}
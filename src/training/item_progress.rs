@@ -0,0 +1,156 @@
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::training::ReviewGrade;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+const RELEARNING_SECONDS: u64 = 600;
+/// Intervals stop growing after a hundred years, which keeps due times well within range however many easy answers come in a row
+const MAX_INTERVAL_DAYS: f64 = 36_500.0;
+
+/// The SM-2 scheduling state of one exercise item. Times are seconds since the Unix epoch
+#[derive(Clone, PartialEq, Debug)]
+pub struct ItemProgress {
+    id: String,
+    repetitions: u32,
+    ease: f64,
+    interval_days: f64,
+    due: u64,
+    last_review: Option<u64>,
+    review_count: u32,
+    lapse_count: u32,
+}
+
+impl ItemProgress {
+    /// A new item, due straight away
+    pub fn new(id: &str) -> ItemProgress {
+        ItemProgress {
+            id: id.to_string(),
+            repetitions: 0,
+            ease: 2.5,
+            interval_days: 0.0,
+            due: 0,
+            last_review: None,
+            review_count: 0,
+            lapse_count: 0,
+        }
+    }
+
+    pub fn get_id(&self) -> &str {
+        &self.id
+    }
+
+    /// How many reviews in a row have been passed since the last lapse
+    pub fn get_repetitions(&self) -> u32 {
+        self.repetitions
+    }
+
+    /// The SM-2 ease factor, never below 1.3
+    pub fn get_ease(&self) -> f64 {
+        self.ease
+    }
+
+    pub fn get_interval_days(&self) -> f64 {
+        self.interval_days
+    }
+
+    pub fn get_due(&self) -> u64 {
+        self.due
+    }
+
+    pub fn get_last_review(&self) -> Option<u64> {
+        self.last_review
+    }
+
+    pub fn get_review_count(&self) -> u32 {
+        self.review_count
+    }
+
+    /// How many times the item has been forgotten after being learnt
+    pub fn get_lapse_count(&self) -> u32 {
+        self.lapse_count
+    }
+
+    pub fn is_new(&self) -> bool {
+        self.review_count == 0
+    }
+
+    pub fn is_due(&self, now: u64) -> bool {
+        self.due <= now
+    }
+
+    /// How far past due the item is as a share of its interval, so a long-interval item a day late is less urgent than a short one
+    pub fn get_overdue_ratio(&self, now: u64) -> f64 {
+        let overdue = now as f64 - self.due as f64;
+
+        overdue / (self.interval_days * SECONDS_PER_DAY).max(RELEARNING_SECONDS as f64)
+    }
+
+    /// Applies SM-2: a failed item is relearnt ten minutes later, a passed one waits 1 day, then 6, then its last interval times the ease.
+    /// Hard answers grow the interval more slowly and easy ones faster, and no interval grows beyond a hundred years
+    pub fn review(&mut self, grade: ReviewGrade, now: u64) {
+        let quality = grade.get_quality() as f64;
+
+        self.ease = (self.ease + 0.1 - (5.0 - quality) * (0.08 + (5.0 - quality) * 0.02)).max(1.3);
+        self.review_count = self.review_count.saturating_add(1);
+        self.last_review = Some(now);
+
+        if grade == ReviewGrade::Again {
+            if self.repetitions > 0 {
+                self.lapse_count = self.lapse_count.saturating_add(1);
+            }
+
+            self.repetitions = 0;
+            self.interval_days = 0.0;
+            self.due = now.saturating_add(RELEARNING_SECONDS);
+            return;
+        }
+
+        self.repetitions = self.repetitions.saturating_add(1);
+        self.interval_days = match (self.repetitions, grade) {
+            (1, _) => 1.0,
+            (2, _) => 6.0,
+            (_, ReviewGrade::Hard) => self.interval_days * 1.2,
+            (_, ReviewGrade::Easy) => self.interval_days * self.ease * 1.3,
+            _ => self.interval_days * self.ease,
+        }.min(MAX_INTERVAL_DAYS);
+        self.due = now.saturating_add((self.interval_days * SECONDS_PER_DAY).round() as u64);
+    }
+
+    /// One tab separated line: id, repetitions, ease, interval in days, due, last review (or "-"), review count and lapse count
+    pub(crate) fn write_line(&self) -> String {
+        let last_review = self.last_review.map(|last_review| last_review.to_string()).unwrap_or("-".to_string());
+
+        format!("{}\t{}\t{}\t{}\t{}\t{last_review}\t{}\t{}", self.id, self.repetitions, self.ease, self.interval_days, self.due, self.review_count, self.lapse_count)
+    }
+
+    pub(crate) fn try_parse_line(line: &str) -> Result<ItemProgress, Box<dyn CrispiiError>> {
+        let fields: Vec<&str> = line.split('\t').collect();
+
+        if fields.len() != 8 {
+            return Err(Box::new(InvalidArgumentError::new("text", format!("Expected 8 tab separated fields in '{line}'").as_str())));
+        }
+
+        let invalid = |field: &str| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("'{field}' is not a valid value in '{line}'").as_str())) };
+        let try_parse_amount = |field: &str| -> Result<f64, Box<dyn CrispiiError>> {
+            match field.parse::<f64>() {
+                Ok(amount) if amount.is_finite() && amount >= 0.0 => Ok(amount),
+                _ => Err(invalid(field)),
+            }
+        };
+        let last_review = match fields[5] {
+            "-" => None,
+            field => Some(field.parse().map_err(|_| invalid(field))?),
+        };
+
+        Ok(ItemProgress {
+            id: fields[0].to_string(),
+            repetitions: fields[1].parse().map_err(|_| invalid(fields[1]))?,
+            ease: try_parse_amount(fields[2])?,
+            interval_days: try_parse_amount(fields[3])?,
+            due: fields[4].parse().map_err(|_| invalid(fields[4]))?,
+            last_review,
+            review_count: fields[6].parse().map_err(|_| invalid(fields[6]))?,
+            lapse_count: fields[7].parse().map_err(|_| invalid(fields[7]))?,
+        })
+    }
+}
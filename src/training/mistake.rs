@@ -0,0 +1,34 @@
+use std::fmt::Display;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum Mistake {
+    #[default]
+    WrongPitch,
+    /// The right letter and modifier in the wrong octave
+    WrongOctave,
+    /// The right sound spelt with the wrong letter, such as G# for Ab
+    EnharmonicMisspelling,
+    /// A note of the target with nothing written for it
+    Missing,
+    /// A note written that the target does not have
+    Extra,
+    /// The right note held for the wrong length
+    WrongRhythm,
+}
+
+impl Display for Mistake {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mistake::WrongPitch => write!(f, "Wrong Pitch"),
+            Mistake::WrongOctave => write!(f, "Wrong Octave"),
+            Mistake::EnharmonicMisspelling => write!(f, "Enharmonic Misspelling"),
+            Mistake::Missing => write!(f, "Missing"),
+            Mistake::Extra => write!(f, "Extra"),
+            Mistake::WrongRhythm => write!(f, "Wrong Rhythm"),
+        }
+    }
+}
+
+impl Mistake {
+    pub const ALL: [Mistake; 6] = [Mistake::WrongPitch, Mistake::WrongOctave, Mistake::EnharmonicMisspelling, Mistake::Missing, Mistake::Extra, Mistake::WrongRhythm];
+}
@@ -0,0 +1,78 @@
+use std::fmt::Display;
+
+use crate::notes::LetterNote;
+use crate::rhythm::Duration;
+use crate::training::Mistake;
+
+/// One column of a graded dictation: a target note, a written note, or a pair of them lined up against each other
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct NoteFeedback {
+    target_index: Option<usize>,
+    response_index: Option<usize>,
+    target: Option<(LetterNote, Option<Duration>)>,
+    response: Option<(LetterNote, Option<Duration>)>,
+    mistakes: Vec<Mistake>,
+    credit: f64,
+}
+
+impl Display for NoteFeedback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = |note: &LetterNote| format!("{}{}", note.to_string().trim_end(), note.get_octave().get_number());
+
+        match (&self.target, &self.response) {
+            (Some(_), Some((response, _))) if self.mistakes.is_empty() => write!(f, "{} is correct", name(response)),
+            (Some((target, _)), Some((response, _))) => {
+                let mistakes: Vec<String> = self.mistakes.iter().map(|mistake| mistake.to_string()).collect();
+                write!(f, "Expected {}, got {} ({})", name(target), name(response), mistakes.join(", "))
+            }
+            (Some((target, _)), None) => write!(f, "Missing {}", name(target)),
+            (None, Some((response, _))) => write!(f, "Extra {}", name(response)),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+impl NoteFeedback {
+    pub(crate) fn new(target_index: Option<usize>, response_index: Option<usize>, target: Option<(LetterNote, Option<Duration>)>, response: Option<(LetterNote, Option<Duration>)>, mistakes: Vec<Mistake>, credit: f64) -> NoteFeedback {
+        NoteFeedback { target_index, response_index, target, response, mistakes, credit }
+    }
+
+    /// The position of the note in the target, unless this is an extra note
+    pub fn get_target_index(&self) -> Option<usize> {
+        self.target_index
+    }
+
+    /// The position of the note in the response, unless this is a missing note
+    pub fn get_response_index(&self) -> Option<usize> {
+        self.response_index
+    }
+
+    pub fn get_target_note(&self) -> Option<LetterNote> {
+        self.target.map(|(note, _)| note)
+    }
+
+    pub fn get_response_note(&self) -> Option<LetterNote> {
+        self.response.map(|(note, _)| note)
+    }
+
+    pub fn get_target_duration(&self) -> Option<Duration> {
+        self.target.and_then(|(_, duration)| duration)
+    }
+
+    pub fn get_response_duration(&self) -> Option<Duration> {
+        self.response.and_then(|(_, duration)| duration)
+    }
+
+    pub fn get_mistakes(&self) -> &[Mistake] {
+        &self.mistakes
+    }
+
+    pub fn is_correct(&self) -> bool {
+        self.mistakes.is_empty()
+    }
+
+    /// How much this column counts towards the score, from 0 to 1
+    pub fn get_credit(&self) -> f64 {
+        self.credit
+    }
+}
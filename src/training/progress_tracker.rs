@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::Path;
+
+use crispii_errors::{CrispiiError, ImpossibleOperationError, InvalidArgumentError};
+
+use crate::training::{ItemProgress, ReviewGrade};
+
+const HEADER: &str = "crispii progress 1";
+
+/// Keeps the review history of every exercise item, keyed by an id such as "Interval: Minor Sixth" or "Spell: E Flat Major Scale"
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct ProgressTracker {
+    items: BTreeMap<String, ItemProgress>,
+}
+
+impl Display for ProgressTracker {
+    /// A header line and then one tab separated line per item, which `try_parse` reads back
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{HEADER}")?;
+
+        for item in self.items.values() {
+            writeln!(f, "{}", item.write_line())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ProgressTracker {
+    pub fn new() -> ProgressTracker {
+        ProgressTracker::default()
+    }
+
+    pub fn try_parse(text: &str) -> Result<ProgressTracker, Box<dyn CrispiiError>> {
+        let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+
+        if lines.next().map(str::trim) != Some(HEADER) {
+            return Err(Box::new(InvalidArgumentError::new("text", format!("Progress files must start with '{HEADER}'").as_str())));
+        }
+
+        let mut tracker = ProgressTracker::new();
+
+        for line in lines {
+            let item = ItemProgress::try_parse_line(line)?;
+            tracker.items.insert(item.get_id().to_string(), item);
+        }
+
+        Ok(tracker)
+    }
+
+    pub fn try_read_from_path<P: AsRef<Path>>(path: P) -> Result<ProgressTracker, Box<dyn CrispiiError>> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|error| -> Box<dyn CrispiiError> { Box::new(ImpossibleOperationError::new(format!("Could not read the progress file: {error}").as_str())) })?;
+
+        ProgressTracker::try_parse(&text)
+    }
+
+    pub fn try_write_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn CrispiiError>> {
+        std::fs::write(path, self.to_string())
+            .map_err(|error| -> Box<dyn CrispiiError> { Box::new(ImpossibleOperationError::new(format!("Could not write the progress file: {error}").as_str())) })
+    }
+
+    pub fn get_item(&self, id: &str) -> Option<&ItemProgress> {
+        self.items.get(id)
+    }
+
+    pub fn get_items(&self) -> impl Iterator<Item = &ItemProgress> {
+        self.items.values()
+    }
+
+    /// Records a review at the given time (in seconds since the Unix epoch), adding the item if it is new
+    pub fn try_record(&mut self, id: &str, grade: ReviewGrade, now: u64) -> Result<(), Box<dyn CrispiiError>> {
+        if id.is_empty() || id.contains(['\t', '\n', '\r']) {
+            return Err(Box::new(InvalidArgumentError::new("id", "Must not be empty or contain tabs or line breaks")));
+        }
+
+        self.items.entry(id.to_string()).or_insert_with(|| ItemProgress::new(id)).review(grade, now);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, id: &str) -> Option<ItemProgress> {
+        self.items.remove(id)
+    }
+
+    /// Every tracked item due by the given time, most urgent first
+    pub fn get_due_items(&self, now: u64) -> Vec<&ItemProgress> {
+        let mut due: Vec<&ItemProgress> = self.items.values().filter(|item| item.is_due(now)).collect();
+        due.sort_by(|item, other| other.get_overdue_ratio(now).total_cmp(&item.get_overdue_ratio(now)));
+        due
+    }
+
+    /// Picks which of the candidate items to practise next: the most overdue item if any are due, otherwise the first one never seen,
+    /// otherwise whichever falls due soonest
+    pub fn select_next<'a>(&self, candidates: &[&'a str], now: u64) -> Option<&'a str> {
+        let overdue = candidates.iter()
+            .filter_map(|id| self.items.get(*id).filter(|item| item.is_due(now)).map(|item| (*id, item.get_overdue_ratio(now))))
+            .max_by(|(_, ratio), (_, other_ratio)| ratio.total_cmp(other_ratio))
+            .map(|(id, _)| id);
+
+        overdue
+            .or_else(|| candidates.iter().find(|id| !self.items.contains_key(**id)).copied())
+            .or_else(|| candidates.iter().min_by_key(|id| self.items.get(**id).map(|item| item.get_due()).unwrap_or(0)).copied())
+    }
+}
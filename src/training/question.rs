@@ -0,0 +1,82 @@
+use crate::keys::Key;
+use crate::notes::LetterNote;
+use crate::rhythm::{Duration, Tempo, TimeSignature};
+use crate::scores::{Event, Melody};
+use crate::training::{Answer, ExerciseKind};
+
+/// A recognition question: notes to play, the answer they stand for and the choices offered
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Question {
+    notes: Vec<LetterNote>,
+    harmonic: bool,
+    answer: Answer,
+    choices: Vec<Answer>,
+}
+
+impl Question {
+    /// The answer is added to the choices if they leave it out
+    pub fn new(notes: Vec<LetterNote>, harmonic: bool, answer: Answer, choices: Vec<Answer>) -> Question {
+        let mut choices = choices;
+
+        if !choices.contains(&answer) {
+            choices.push(answer);
+        }
+
+        Question { notes, harmonic, answer, choices }
+    }
+
+    pub fn get_kind(&self) -> ExerciseKind {
+        self.answer.get_kind()
+    }
+
+    /// The notes in the order they are played
+    pub fn get_notes(&self) -> &[LetterNote] {
+        &self.notes
+    }
+
+    /// Whether the notes are played together rather than one after another
+    pub fn is_harmonic(&self) -> bool {
+        self.harmonic
+    }
+
+    pub fn get_answer(&self) -> Answer {
+        self.answer
+    }
+
+    pub fn get_choices(&self) -> &[Answer] {
+        &self.choices
+    }
+
+    pub fn get_item_id(&self) -> String {
+        self.answer.get_item_id()
+    }
+
+    pub fn get_prompt(&self) -> String {
+        match self.get_kind() {
+            ExerciseKind::Interval => "Which interval do you hear?".to_string(),
+            ExerciseKind::ChordQuality => "Which chord quality do you hear?".to_string(),
+            ExerciseKind::Scale => "Which scale do you hear?".to_string(),
+            ExerciseKind::Mode => "Which mode do you hear?".to_string(),
+        }
+    }
+
+    pub fn is_correct(&self, answer: Answer) -> bool {
+        self.answer == answer
+    }
+
+    /// Checks an answer typed out in full or abbreviated, such as "Perfect Fifth", "P5", "minor" or "m7"
+    pub fn is_correct_text(&self, text: &str) -> bool {
+        self.answer.is_named_by(text)
+    }
+
+    /// The notes as a melody in C major for playback, one beat each or all at once for harmonic questions
+    pub fn to_melody(&self, tempo: Tempo) -> Melody {
+        let beat = Duration::default();
+        let events = match self.harmonic {
+            true => vec![Event::new_chord(self.notes.clone(), beat.multiply(2, 1))],
+            false => self.notes.iter().map(|note| Event::new_note(*note, beat)).collect(),
+        };
+
+        Melody::new(Key::default(), TimeSignature::default(), tempo, events)
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt::Display;
+
+/// How well an item was recalled, as in SM-2 and FSRS
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum ReviewGrade {
+    Again,
+    Hard,
+    #[default]
+    Good,
+    Easy,
+}
+
+impl Display for ReviewGrade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReviewGrade::Again => write!(f, "Again"),
+            ReviewGrade::Hard => write!(f, "Hard"),
+            ReviewGrade::Good => write!(f, "Good"),
+            ReviewGrade::Easy => write!(f, "Easy"),
+        }
+    }
+}
+
+impl ReviewGrade {
+    pub const ALL: [ReviewGrade; 4] = [ReviewGrade::Again, ReviewGrade::Hard, ReviewGrade::Good, ReviewGrade::Easy];
+
+    /// The matching SM-2 quality of response, from 0 to 5
+    pub fn get_quality(&self) -> u8 {
+        match self {
+            ReviewGrade::Again => 1,
+            ReviewGrade::Hard => 3,
+            ReviewGrade::Good => 4,
+            ReviewGrade::Easy => 5,
+        }
+    }
+
+    /// Turns a score from 0 to 1, such as a dictation score, into a grade
+    pub fn from_score(score: f64) -> ReviewGrade {
+        match score {
+            _ if score >= 0.95 => ReviewGrade::Easy,
+            _ if score >= 0.8 => ReviewGrade::Good,
+            _ if score >= 0.5 => ReviewGrade::Hard,
+            _ => ReviewGrade::Again,
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt::Display;
+
+/// Scales heard as a whole rather than as a mode of the major scale
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum Scale {
+    #[default]
+    Major,
+    NaturalMinor,
+    HarmonicMinor,
+    MelodicMinor,
+    MajorPentatonic,
+    MinorPentatonic,
+    Blues,
+    WholeTone,
+    Chromatic,
+}
+
+impl Display for Scale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Scale::Major => write!(f, "Major"),
+            Scale::NaturalMinor => write!(f, "Natural Minor"),
+            Scale::HarmonicMinor => write!(f, "Harmonic Minor"),
+            Scale::MelodicMinor => write!(f, "Melodic Minor"),
+            Scale::MajorPentatonic => write!(f, "Major Pentatonic"),
+            Scale::MinorPentatonic => write!(f, "Minor Pentatonic"),
+            Scale::Blues => write!(f, "Blues"),
+            Scale::WholeTone => write!(f, "Whole Tone"),
+            Scale::Chromatic => write!(f, "Chromatic"),
+        }
+    }
+}
+
+impl Scale {
+    pub const ALL: [Scale; 9] = [
+        Scale::Major,
+        Scale::NaturalMinor,
+        Scale::HarmonicMinor,
+        Scale::MelodicMinor,
+        Scale::MajorPentatonic,
+        Scale::MinorPentatonic,
+        Scale::Blues,
+        Scale::WholeTone,
+        Scale::Chromatic,
+    ];
+
+    /// Each note of the ascending scale as a (letter steps, semitones) offset from the tonic, up to and including the octave. The melodic
+    /// minor is given in its ascending form
+    pub fn get_tones(&self) -> &'static [(i8, i16)] {
+        match self {
+            Scale::Major => &[(0, 0), (1, 2), (2, 4), (3, 5), (4, 7), (5, 9), (6, 11), (7, 12)],
+            Scale::NaturalMinor => &[(0, 0), (1, 2), (2, 3), (3, 5), (4, 7), (5, 8), (6, 10), (7, 12)],
+            Scale::HarmonicMinor => &[(0, 0), (1, 2), (2, 3), (3, 5), (4, 7), (5, 8), (6, 11), (7, 12)],
+            Scale::MelodicMinor => &[(0, 0), (1, 2), (2, 3), (3, 5), (4, 7), (5, 9), (6, 11), (7, 12)],
+            Scale::MajorPentatonic => &[(0, 0), (1, 2), (2, 4), (4, 7), (5, 9), (7, 12)],
+            Scale::MinorPentatonic => &[(0, 0), (2, 3), (3, 5), (4, 7), (6, 10), (7, 12)],
+            Scale::Blues => &[(0, 0), (2, 3), (3, 5), (3, 6), (4, 7), (6, 10), (7, 12)],
+            Scale::WholeTone => &[(0, 0), (1, 2), (2, 4), (3, 6), (4, 8), (5, 10), (7, 12)],
+            Scale::Chromatic => &[(0, 0), (0, 1), (1, 2), (1, 3), (2, 4), (3, 5), (3, 6), (4, 7), (4, 8), (5, 9), (5, 10), (6, 11), (7, 12)],
+        }
+    }
+}
@@ -0,0 +1,17 @@
+mod tuning_system;
+pub use tuning_system::TuningSystem;
+
+mod tuning;
+pub use tuning::Tuning;
+
+mod scala_pitch;
+pub use scala_pitch::ScalaPitch;
+
+mod scala_scale;
+pub use scala_scale::ScalaScale;
+
+mod keyboard_mapping;
+pub use keyboard_mapping::KeyboardMapping;
+
+mod scala_tuning;
+pub use scala_tuning::ScalaTuning;
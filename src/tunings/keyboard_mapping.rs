@@ -0,0 +1,184 @@
+use std::fmt::Display;
+use std::path::Path;
+
+use crispii_errors::{CrispiiError, ImpossibleOperationError, InvalidArgumentError};
+
+/// A keyboard mapping in the Scala .kbm format, saying which scale degree each MIDI key plays and which key sounds at a reference
+/// frequency
+#[derive(Clone, PartialEq, Debug)]
+pub struct KeyboardMapping {
+    first_key: u8,
+    last_key: u8,
+    middle_key: u8,
+    reference_key: u8,
+    reference_frequency: f64,
+    octave_degree: u32,
+    mapping: Vec<Option<u32>>,
+}
+
+impl Default for KeyboardMapping {
+    /// Every key plays the next scale degree, with 1/1 on middle C and A4 at 440 Hz
+    fn default() -> Self {
+        KeyboardMapping {
+            first_key: 0,
+            last_key: 127,
+            middle_key: 60,
+            reference_key: 69,
+            reference_frequency: 440.0,
+            octave_degree: 0,
+            mapping: Vec::new(),
+        }
+    }
+}
+
+impl Display for KeyboardMapping {
+    /// Writes the mapping as a .kbm file, which `try_parse` reads back
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "! Size of map:")?;
+        writeln!(f, "{}", self.mapping.len())?;
+        writeln!(f, "! First and last MIDI keys to retune:")?;
+        writeln!(f, "{}", self.first_key)?;
+        writeln!(f, "{}", self.last_key)?;
+        writeln!(f, "! Middle key, where the first entry of the map is mapped to:")?;
+        writeln!(f, "{}", self.middle_key)?;
+        writeln!(f, "! Reference key and its frequency:")?;
+        writeln!(f, "{}", self.reference_key)?;
+        writeln!(f, "{:.6}", self.reference_frequency)?;
+        writeln!(f, "! Scale degree to consider as the formal octave:")?;
+        writeln!(f, "{}", self.octave_degree)?;
+        writeln!(f, "! Mapping:")?;
+
+        for degree in &self.mapping {
+            match degree {
+                Some(degree) => writeln!(f, "{degree}")?,
+                None => writeln!(f, "x")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl KeyboardMapping {
+    /// A mapping where every key from the first to the last plays the next scale degree, with 1/1 on the middle key
+    pub fn try_new(first_key: u8, last_key: u8, middle_key: u8, reference_key: u8, reference_frequency: f64) -> Result<KeyboardMapping, Box<dyn CrispiiError>> {
+        for (name, key) in [("first_key", first_key), ("last_key", last_key), ("middle_key", middle_key), ("reference_key", reference_key)] {
+            if key > 127 {
+                return Err(Box::new(InvalidArgumentError::new(name, "Must be between 0 and 127 (inclusive)")));
+            }
+        }
+
+        if first_key > last_key {
+            return Err(Box::new(InvalidArgumentError::new("last_key", "Must not be below the first key")));
+        }
+
+        if !(reference_frequency.is_finite() && reference_frequency > 0.0) {
+            return Err(Box::new(InvalidArgumentError::new("reference_frequency", "Must be finite and above 0")));
+        }
+
+        Ok(KeyboardMapping { first_key, last_key, middle_key, reference_key, reference_frequency, ..KeyboardMapping::default() })
+    }
+
+    /// Reads a .kbm file: after lines starting with '!' are skipped, the map size, first key, last key, middle key, reference key,
+    /// reference frequency and formal octave degree come one per line, followed by the map entries, where 'x' leaves a key silent
+    pub fn try_parse(text: &str) -> Result<KeyboardMapping, Box<dyn CrispiiError>> {
+        let mut words = text.lines()
+            .filter(|line| !line.starts_with('!') && !line.trim().is_empty())
+            .filter_map(|line| line.split_whitespace().next());
+        let mut next = |name: &str| -> Result<&str, Box<dyn CrispiiError>> {
+            words.next().ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("Keyboard mappings must give the {name}").as_str())) })
+        };
+
+        let size = try_parse_number::<usize>("map size", next("map size")?)?;
+        let first_key = try_parse_number("first key", next("first key")?)?;
+        let last_key = try_parse_number("last key", next("last key")?)?;
+        let middle_key = try_parse_number("middle key", next("middle key")?)?;
+        let reference_key = try_parse_number("reference key", next("reference key")?)?;
+        let reference_frequency = try_parse_number("reference frequency", next("reference frequency")?)?;
+        let octave_degree = try_parse_number("formal octave degree", next("formal octave degree")?)?;
+        let mut mapping = Vec::new();
+
+        // Scala leaves the keys at the end of a short map unmapped
+        for _ in 0..size {
+            match next("map entry") {
+                Ok("x") | Err(_) => mapping.push(None),
+                Ok(word) => mapping.push(Some(try_parse_number("map entry", word)?)),
+            }
+        }
+
+        let mut keyboard_mapping = KeyboardMapping::try_new(first_key, last_key, middle_key, reference_key, reference_frequency)?;
+        keyboard_mapping.set_mapping(mapping, octave_degree);
+
+        Ok(keyboard_mapping)
+    }
+
+    pub fn try_read_from_path<P: AsRef<Path>>(path: P) -> Result<KeyboardMapping, Box<dyn CrispiiError>> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|error| -> Box<dyn CrispiiError> { Box::new(ImpossibleOperationError::new(format!("Could not read the keyboard mapping file: {error}").as_str())) })?;
+
+        KeyboardMapping::try_parse(&text)
+    }
+
+    pub fn try_write_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn CrispiiError>> {
+        std::fs::write(path, self.to_string())
+            .map_err(|error| -> Box<dyn CrispiiError> { Box::new(ImpossibleOperationError::new(format!("Could not write the keyboard mapping file: {error}").as_str())) })
+    }
+
+    pub fn get_first_key(&self) -> u8 {
+        self.first_key
+    }
+
+    pub fn get_last_key(&self) -> u8 {
+        self.last_key
+    }
+
+    pub fn get_middle_key(&self) -> u8 {
+        self.middle_key
+    }
+
+    pub fn get_reference_key(&self) -> u8 {
+        self.reference_key
+    }
+
+    pub fn get_reference_frequency(&self) -> f64 {
+        self.reference_frequency
+    }
+
+    pub fn get_octave_degree(&self) -> u32 {
+        self.octave_degree
+    }
+
+    pub fn get_mapping(&self) -> &[Option<u32>] {
+        &self.mapping
+    }
+
+    /// The scale degree each key from the middle key upwards plays, repeating every map entry a formal octave higher, with `None` for
+    /// silent keys. An empty map plays every scale degree in turn, and a formal octave of 0 stands for the scale's period
+    pub fn set_mapping(&mut self, mapping: Vec<Option<u32>>, octave_degree: u32) {
+        self.mapping = mapping;
+        self.octave_degree = octave_degree;
+    }
+
+    /// The scale degree a key's map entry plays (0 is 1/1 on the middle key) and how many formal octaves above (or below) it sits. An
+    /// empty map has a single entry for each key
+    pub(crate) fn get_entry(&self, key: u8) -> Option<(i64, i64)> {
+        let offset = key as i64 - self.middle_key as i64;
+
+        if self.mapping.is_empty() {
+            return Some((offset, 0));
+        }
+
+        let size = self.mapping.len() as i64;
+        let degree = self.mapping[offset.rem_euclid(size) as usize]?;
+
+        Some((degree as i64, offset.div_euclid(size)))
+    }
+
+    pub fn is_retuned(&self, key: u8) -> bool {
+        (self.first_key..=self.last_key).contains(&key)
+    }
+}
+
+fn try_parse_number<T: std::str::FromStr>(name: &str, word: &str) -> Result<T, Box<dyn CrispiiError>> {
+    word.parse().map_err(|_| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("'{word}' is not a valid {name}").as_str())) })
+}
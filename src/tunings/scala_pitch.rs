@@ -0,0 +1,56 @@
+use std::fmt::Display;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+/// One pitch of a Scala scale above its 1/1, either in cents or as a frequency ratio
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ScalaPitch {
+    Cents(f64),
+    Ratio(u64, u64),
+}
+
+impl Display for ScalaPitch {
+    /// Cents always have a decimal point, which is how Scala tells them apart from ratios
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScalaPitch::Cents(cents) => write!(f, "{cents:.5}"),
+            ScalaPitch::Ratio(numerator, denominator) => write!(f, "{numerator}/{denominator}"),
+        }
+    }
+}
+
+impl ScalaPitch {
+    /// Reads the first word of a Scala pitch line, such as "701.955", "3/2" or "2" (meaning 2/1). Anything after it is a label and ignored
+    pub fn try_parse(text: &str) -> Result<ScalaPitch, Box<dyn CrispiiError>> {
+        let word = text.split_whitespace().next()
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", "Pitch lines must not be empty")) })?;
+        let invalid = || -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("'{word}' is not a cents value or a ratio").as_str())) };
+
+        let pitch = match (word.contains('.'), word.split_once('/')) {
+            (true, _) => ScalaPitch::Cents(word.parse().map_err(|_| invalid())?),
+            (false, Some((numerator, denominator))) => ScalaPitch::Ratio(numerator.parse().map_err(|_| invalid())?, denominator.parse().map_err(|_| invalid())?),
+            (false, None) => ScalaPitch::Ratio(word.parse().map_err(|_| invalid())?, 1),
+        };
+
+        pitch.try_check()?;
+        Ok(pitch)
+    }
+
+    pub fn get_cents(&self) -> f64 {
+        match self {
+            ScalaPitch::Cents(cents) => *cents,
+            ScalaPitch::Ratio(numerator, denominator) => 1200.0 * (*numerator as f64 / *denominator as f64).log2(),
+        }
+    }
+
+    /// Rejects cents that are not finite and ratios with a zero in them
+    pub(crate) fn try_check(&self) -> Result<(), Box<dyn CrispiiError>> {
+        match self {
+            ScalaPitch::Cents(cents) if !cents.is_finite() => Err(Box::new(InvalidArgumentError::new("pitch", "Cents must be finite"))),
+            ScalaPitch::Ratio(numerator, denominator) if *numerator == 0 || *denominator == 0 => {
+                Err(Box::new(InvalidArgumentError::new("pitch", format!("{self} must have a numerator and denominator above 0").as_str())))
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::fmt::Display;
+use std::path::Path;
+
+use crispii_errors::{CrispiiError, ImpossibleOperationError, InvalidArgumentError};
+
+use crate::keys::{Key, Mode};
+use crate::tunings::{ScalaPitch, Tuning, TuningSystem};
+
+/// A scale in the Scala .scl format: a description and the pitches above 1/1, the last of which is the period the scale repeats at
+/// (usually 2/1)
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScalaScale {
+    description: String,
+    pitches: Vec<ScalaPitch>,
+}
+
+impl Display for ScalaScale {
+    /// Writes the scale as a .scl file, which `try_parse` reads back
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "!")?;
+        writeln!(f, "{}", self.description)?;
+        writeln!(f, " {}", self.pitches.len())?;
+        writeln!(f, "!")?;
+
+        for pitch in &self.pitches {
+            writeln!(f, " {pitch}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ScalaScale {
+    pub fn try_new(description: &str, pitches: Vec<ScalaPitch>) -> Result<ScalaScale, Box<dyn CrispiiError>> {
+        if description.contains(['\n', '\r']) {
+            return Err(Box::new(InvalidArgumentError::new("description", "Must not contain line breaks")));
+        }
+
+        if pitches.is_empty() {
+            return Err(Box::new(InvalidArgumentError::new("pitches", "Must have at least one pitch, the period")));
+        }
+
+        for pitch in &pitches {
+            pitch.try_check()?;
+        }
+
+        Ok(ScalaScale { description: description.to_string(), pitches })
+    }
+
+    /// The octave split into equal steps, written in cents apart from the 2/1 at the top
+    pub fn try_from_equal_division(divisions: u16) -> Result<ScalaScale, Box<dyn CrispiiError>> {
+        if divisions == 0 {
+            return Err(Box::new(InvalidArgumentError::new("divisions", "Must be at least 1")));
+        }
+
+        let mut pitches: Vec<ScalaPitch> = (1..divisions).map(|step| ScalaPitch::Cents(step as f64 * 1200.0 / divisions as f64)).collect();
+        pitches.push(ScalaPitch::Ratio(2, 1));
+
+        ScalaScale::try_new(format!("{divisions} Tone Equal Temperament").as_str(), pitches)
+    }
+
+    /// The twelve notes of the tuning's chromatic scale from its tonic, spelt as they would be in the tonic's major key. Equal divisions
+    /// of the octave keep every step instead
+    pub fn try_from_tuning(tuning: &Tuning) -> Result<ScalaScale, Box<dyn CrispiiError>> {
+        if let TuningSystem::EqualDivision(divisions) = tuning.get_system() {
+            return ScalaScale::try_from_equal_division(divisions);
+        }
+
+        let key = Key::try_new(tuning.get_tonic(), Mode::Major)?;
+        let tonic_pitch = tuning.get_tonic().get_pitch_number();
+        let mut pitches = Vec::new();
+
+        for semitones in 1..12 {
+            pitches.push(ScalaPitch::Cents(tuning.get_cents_from_tonic(key.try_spell(tonic_pitch + semitones)?)));
+        }
+
+        pitches.push(ScalaPitch::Ratio(2, 1));
+        ScalaScale::try_new(tuning.to_string().as_str(), pitches)
+    }
+
+    /// Reads a .scl file: lines starting with '!' are comments, the first other line is the description, the next is the number of
+    /// pitches and then come the pitches, one per line
+    pub fn try_parse(text: &str) -> Result<ScalaScale, Box<dyn CrispiiError>> {
+        let mut lines = text.lines().filter(|line| !line.starts_with('!'));
+        let description = lines.next()
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", "Scala files must have a description line")) })?;
+        let count_line = lines.next()
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", "Scala files must give the number of pitches after the description")) })?;
+        let count: usize = count_line.split_whitespace().next().and_then(|word| word.parse().ok())
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(InvalidArgumentError::new("text", format!("'{}' is not a number of pitches", count_line.trim()).as_str())) })?;
+
+        let pitches = lines.filter(|line| !line.trim().is_empty()).take(count).map(ScalaPitch::try_parse).collect::<Result<Vec<_>, _>>()?;
+
+        if pitches.len() != count {
+            return Err(Box::new(InvalidArgumentError::new("text", format!("Expected {count} pitches but found {}", pitches.len()).as_str())));
+        }
+
+        ScalaScale::try_new(description.trim_end_matches('\r'), pitches)
+    }
+
+    pub fn try_read_from_path<P: AsRef<Path>>(path: P) -> Result<ScalaScale, Box<dyn CrispiiError>> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|error| -> Box<dyn CrispiiError> { Box::new(ImpossibleOperationError::new(format!("Could not read the Scala file: {error}").as_str())) })?;
+
+        ScalaScale::try_parse(&text)
+    }
+
+    pub fn try_write_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn CrispiiError>> {
+        std::fs::write(path, self.to_string())
+            .map_err(|error| -> Box<dyn CrispiiError> { Box::new(ImpossibleOperationError::new(format!("Could not write the Scala file: {error}").as_str())) })
+    }
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn get_pitches(&self) -> &[ScalaPitch] {
+        &self.pitches
+    }
+
+    /// The number of steps before the scale repeats
+    pub fn get_size(&self) -> usize {
+        self.pitches.len()
+    }
+
+    pub fn get_period_cents(&self) -> f64 {
+        self.pitches[self.pitches.len() - 1].get_cents()
+    }
+
+    /// How many cents a scale degree sits above 1/1, where degree 0 is 1/1 and degrees outside the scale carry on into the periods above
+    /// and below
+    pub fn get_cents(&self, degree: i64) -> f64 {
+        let size = self.pitches.len() as i64;
+        let step = degree.rem_euclid(size);
+        let cents = match step {
+            0 => 0.0,
+            _ => self.pitches[step as usize - 1].get_cents(),
+        };
+
+        cents + degree.div_euclid(size) as f64 * self.get_period_cents()
+    }
+}
@@ -0,0 +1,71 @@
+use crispii_errors::{CrispiiError, ImpossibleOperationError};
+
+use crate::notes::LetterNote;
+use crate::tunings::{KeyboardMapping, ScalaScale, Tuning};
+
+/// A Scala scale played through a keyboard mapping, giving a frequency for every retuned MIDI key
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScalaTuning {
+    scale: ScalaScale,
+    mapping: KeyboardMapping,
+}
+
+impl ScalaTuning {
+    pub fn new(scale: ScalaScale, mapping: KeyboardMapping) -> ScalaTuning {
+        ScalaTuning { scale, mapping }
+    }
+
+    /// The tuning's scale mapped key by key from its tonic in octave four, with the tonic sounding at the frequency the tuning gives it
+    pub fn try_from_tuning(tuning: &Tuning) -> Result<ScalaTuning, Box<dyn CrispiiError>> {
+        let tonic = tuning.get_tonic();
+        let tonic_key = tonic.try_get_midi_number()?;
+        let mapping = KeyboardMapping::try_new(0, 127, tonic_key, tonic_key, tuning.get_frequency(tonic))?;
+
+        Ok(ScalaTuning::new(ScalaScale::try_from_tuning(tuning)?, mapping))
+    }
+
+    pub fn get_scale(&self) -> &ScalaScale {
+        &self.scale
+    }
+
+    pub fn get_mapping(&self) -> &KeyboardMapping {
+        &self.mapping
+    }
+
+    pub fn set_scale(&mut self, scale: ScalaScale) {
+        self.scale = scale;
+    }
+
+    pub fn set_mapping(&mut self, mapping: KeyboardMapping) {
+        self.mapping = mapping;
+    }
+
+    /// The frequency in hertz a MIDI key sounds at, failing for keys the mapping leaves silent or does not retune
+    pub fn try_get_key_frequency(&self, key: u8) -> Result<f64, Box<dyn CrispiiError>> {
+        if !self.mapping.is_retuned(key) {
+            return Err(Box::new(ImpossibleOperationError::new(format!("Key {key} is outside the range of keys the mapping retunes").as_str())));
+        }
+
+        let cents = self.try_get_cents(key)? - self.try_get_cents(self.mapping.get_reference_key())?;
+
+        Ok(self.mapping.get_reference_frequency() * 2f64.powf(cents / 1200.0))
+    }
+
+    /// The frequency of the key the note would be played on, so enharmonic spellings sound the same
+    pub fn try_get_frequency(&self, note: LetterNote) -> Result<f64, Box<dyn CrispiiError>> {
+        self.try_get_key_frequency(note.try_get_midi_number()?)
+    }
+
+    fn try_get_cents(&self, key: u8) -> Result<f64, Box<dyn CrispiiError>> {
+        let (degree, octaves) = self.mapping.get_entry(key)
+            .ok_or_else(|| -> Box<dyn CrispiiError> { Box::new(ImpossibleOperationError::new(format!("Key {key} is not mapped to a scale degree").as_str())) })?;
+
+        // A map repeats at its formal octave, which need not be the scale's period
+        let octave_cents = match self.mapping.get_octave_degree() {
+            0 => self.scale.get_period_cents(),
+            octave_degree => self.scale.get_cents(octave_degree as i64),
+        };
+
+        Ok(self.scale.get_cents(degree) + octaves as f64 * octave_cents)
+    }
+}
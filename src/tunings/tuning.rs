@@ -0,0 +1,85 @@
+use std::fmt::Display;
+
+use crispii_errors::{CrispiiError, InvalidArgumentError};
+
+use crate::notes::{LetterNote, Octave};
+use crate::tunings::TuningSystem;
+
+/// A tuning system laid out from a tonic. The tonic in octave four keeps its equal tempered frequency, and every other note is tuned
+/// from it, so A4 only sounds at the reference pitch when the tonic is A or the system is equal temperament
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Tuning {
+    system: TuningSystem,
+    tonic: LetterNote,
+    a4_frequency: f64,
+}
+
+impl Default for Tuning {
+    /// Twelve tone equal temperament with A4 at 440 Hz
+    fn default() -> Self {
+        Tuning {
+            system: TuningSystem::default(),
+            tonic: LetterNote::default(),
+            a4_frequency: 440.0,
+        }
+    }
+}
+
+impl Display for Tuning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} on {}", self.system, self.tonic.to_string().trim_end())
+    }
+}
+
+impl Tuning {
+    /// The octave of the tonic is ignored. The well temperaments keep their usual layout from C whatever the tonic
+    pub fn try_new(system: TuningSystem, tonic: LetterNote) -> Result<Tuning, Box<dyn CrispiiError>> {
+        if system == TuningSystem::EqualDivision(0) {
+            return Err(Box::new(InvalidArgumentError::new("system", "Must divide the octave into at least one step")));
+        }
+
+        Ok(Tuning { system, tonic: tonic.with_octave(Octave::Four), ..Tuning::default() })
+    }
+
+    pub fn get_system(&self) -> TuningSystem {
+        self.system
+    }
+
+    pub fn get_tonic(&self) -> LetterNote {
+        self.tonic
+    }
+
+    pub fn get_reference_frequency(&self) -> f64 {
+        self.a4_frequency
+    }
+
+    /// The frequency of A4 in equal temperament, from which the tonic's frequency is found
+    pub fn try_set_reference_frequency(&mut self, a4_frequency: f64) -> Result<(), Box<dyn CrispiiError>> {
+        if !(a4_frequency.is_finite() && a4_frequency > 0.0) {
+            return Err(Box::new(InvalidArgumentError::new("a4_frequency", "Must be finite and above 0")));
+        }
+
+        self.a4_frequency = a4_frequency;
+        Ok(())
+    }
+
+    /// How many cents the note sits above the tonic in octave four (or below, if negative)
+    pub fn get_cents_from_tonic(&self, note: LetterNote) -> f64 {
+        self.system.get_cents_from_tonic(self.tonic, note)
+    }
+
+    /// How many cents the note sits above (or below) where it would be in twelve tone equal temperament from the same tonic, such as
+    /// -13.7 for a just major third
+    pub fn get_cents_from_equal_temperament(&self, note: LetterNote) -> f64 {
+        self.get_cents_from_tonic(note) - 100.0 * (note.get_pitch_number() - self.tonic.get_pitch_number()) as f64
+    }
+
+    /// The size in cents of the interval from one note up to another (negative if the second is lower)
+    pub fn get_cents_between(&self, from: LetterNote, to: LetterNote) -> f64 {
+        self.get_cents_from_tonic(to) - self.get_cents_from_tonic(from)
+    }
+
+    pub fn get_frequency(&self, note: LetterNote) -> f64 {
+        self.tonic.get_frequency_with_reference(self.a4_frequency) * 2f64.powf(self.get_cents_from_tonic(note) / 1200.0)
+    }
+}
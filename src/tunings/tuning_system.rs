@@ -0,0 +1,110 @@
+use std::fmt::Display;
+
+use crate::notes::LetterNote;
+
+/// A way of tuning notes relative to a tonic. Apart from the well temperaments, every system is built from a chain of fifths, so the
+/// spelling of a note matters and G# is not the same pitch as Ab
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TuningSystem {
+    /// The octave split into the given number of equal steps, with the fifth taken as the step closest to a pure one
+    EqualDivision(u16),
+    /// Five-limit just intonation, with pure fifths and major thirds from the tonic
+    JustIntonation,
+    /// Every note reached by pure fifths
+    Pythagorean,
+    /// Fifths narrowed by a quarter of the syntonic comma, giving pure major thirds
+    QuarterCommaMeantone,
+    /// Werckmeister's third temperament, narrowing C-G, G-D, D-A and B-F# by a quarter of the Pythagorean comma
+    WerckmeisterThree,
+    /// Vallotti's temperament, narrowing the six fifths from F to B by a sixth of the Pythagorean comma
+    Vallotti,
+}
+
+impl Default for TuningSystem {
+    fn default() -> Self {
+        TuningSystem::EqualDivision(12)
+    }
+}
+
+impl Display for TuningSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TuningSystem::EqualDivision(divisions) => write!(f, "{divisions} Tone Equal Temperament"),
+            TuningSystem::JustIntonation => write!(f, "Just Intonation"),
+            TuningSystem::Pythagorean => write!(f, "Pythagorean"),
+            TuningSystem::QuarterCommaMeantone => write!(f, "Quarter-Comma Meantone"),
+            TuningSystem::WerckmeisterThree => write!(f, "Werckmeister III"),
+            TuningSystem::Vallotti => write!(f, "Vallotti"),
+        }
+    }
+}
+
+impl TuningSystem {
+    /// The size of the fifth in cents, for the systems built from a chain of them
+    pub fn get_fifth_cents(&self) -> Option<f64> {
+        match self {
+            TuningSystem::EqualDivision(divisions) => {
+                let divisions = *divisions as f64;
+                Some((divisions * 1.5f64.log2()).round() * 1200.0 / divisions)
+            }
+            TuningSystem::JustIntonation | TuningSystem::Pythagorean => Some(get_pure_fifth_cents()),
+            TuningSystem::QuarterCommaMeantone => Some(get_pure_fifth_cents() - get_syntonic_comma_cents() / 4.0),
+            TuningSystem::WerckmeisterThree | TuningSystem::Vallotti => None,
+        }
+    }
+
+    /// How many cents the note sits above the tonic. Both notes keep their octaves. The division count of an `EqualDivision` must not be
+    /// zero
+    pub(crate) fn get_cents_from_tonic(&self, tonic: LetterNote, note: LetterNote) -> f64 {
+        let semitones = note.get_pitch_number() - tonic.get_pitch_number();
+        let fifths = note.get_fifths() - tonic.get_fifths();
+        // Stacking that many fifths overshoots the note by this many octaves
+        let octaves = (7 * fifths - semitones).div_euclid(12) as f64;
+
+        match (self, self.get_fifth_cents()) {
+            (TuningSystem::JustIntonation, Some(fifth_cents)) => {
+                // Every four fifths along the line swap for a pure major third, a syntonic comma lower, so above C, A is 5/3 and Eb is 6/5
+                let commas = (fifths + 1).div_euclid(4) as f64;
+                fifths as f64 * fifth_cents - octaves * 1200.0 - commas * get_syntonic_comma_cents()
+            }
+            (_, Some(fifth_cents)) => fifths as f64 * fifth_cents - octaves * 1200.0,
+            (_, None) => {
+                let deviations = self.get_deviations();
+                semitones as f64 * 100.0 + deviations[get_pitch_class(note)] - deviations[get_pitch_class(tonic)]
+            }
+        }
+    }
+
+    /// For the well temperaments, how many cents each pitch class (0 is C) sits from equal temperament with C left in place
+    fn get_deviations(&self) -> [f64; 12] {
+        // How much each fifth round the circle from C-G to F-C is narrowed, in Pythagorean commas
+        let narrowing: [f64; 12] = match self {
+            TuningSystem::WerckmeisterThree => [0.25, 0.25, 0.25, 0.0, 0.0, 0.25, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            TuningSystem::Vallotti => [1.0 / 6.0, 1.0 / 6.0, 1.0 / 6.0, 1.0 / 6.0, 1.0 / 6.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0 / 6.0],
+            _ => [1.0 / 12.0; 12],
+        };
+
+        let pythagorean_comma = 12.0 * get_pure_fifth_cents() - 7.0 * 1200.0;
+        let mut deviations = [0.0; 12];
+        let mut cents = 0.0;
+
+        for (fifths, narrowing) in narrowing.iter().enumerate() {
+            deviations[fifths * 7 % 12] = cents - 700.0 * fifths as f64;
+            cents += get_pure_fifth_cents() - narrowing * pythagorean_comma;
+        }
+
+        deviations
+    }
+}
+
+fn get_pure_fifth_cents() -> f64 {
+    1200.0 * 1.5f64.log2()
+}
+
+fn get_syntonic_comma_cents() -> f64 {
+    1200.0 * (81.0f64 / 80.0).log2()
+}
+
+fn get_pitch_class(note: LetterNote) -> usize {
+    note.get_pitch_number().rem_euclid(12) as usize
+}